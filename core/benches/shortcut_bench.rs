@@ -0,0 +1,106 @@
+//! Shortcut matching benchmark
+//!
+//! Measures per-key cost of shortcut matching as the table grows.
+//! Run with `cargo bench --bench shortcut_bench`.
+//!
+//! Expected: roughly flat ns/key from 10 to 10k shortcuts (hash lookup for
+//! word triggers, reversed-trigger trie for suffix matches, binary search for
+//! trigger prefixes). All triggers start with "q"; the "tieng" cases match no
+//! trigger prefix.
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::shortcut::{InputMethod, Shortcut, ShortcutTable};
+use gonhanh_core::engine::Engine;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: usize = 200_000;
+const SIZES: [usize; 4] = [10, 100, 1_000, 10_000];
+
+/// Deterministic trigger for index i: "qa", "qb", … plus a symbol-suffix variant
+fn trigger(i: usize) -> String {
+    let mut s = String::from("q");
+    let mut n = i;
+    loop {
+        s.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    s
+}
+
+fn table(size: usize) -> ShortcutTable {
+    let mut table = ShortcutTable::new();
+    for i in 0..size {
+        table.add(Shortcut::new(&trigger(i), "replacement"));
+        table.add(Shortcut::immediate(&format!("{}->", trigger(i)), "→"));
+    }
+    table
+}
+
+fn bench(name: &str, size: usize, mut f: impl FnMut()) {
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let ns = start.elapsed().as_nanos() as f64 / ITERATIONS as f64;
+    println!("{:<28} {:>7} shortcuts {:>9.1} ns/op", name, size, ns);
+}
+
+fn main() {
+    for size in SIZES {
+        let table = table(size);
+        let hit = trigger(size / 2);
+        let suffix_text = format!("≈ç{}->", trigger(size / 3));
+
+        bench("word boundary hit", size, || {
+            black_box(table.try_match_for_method(
+                black_box(&hit),
+                Some(' '),
+                true,
+                InputMethod::Telex,
+            ));
+        });
+        bench("word boundary miss", size, || {
+            black_box(table.try_match_for_method(
+                black_box("xyz"),
+                Some(' '),
+                true,
+                InputMethod::Telex,
+            ));
+        });
+        bench("suffix hit", size, || {
+            black_box(table.try_match_suffix_for_method(
+                black_box(&suffix_text),
+                None,
+                false,
+                InputMethod::Telex,
+            ));
+        });
+
+        bench("trigger prefix miss", size, || {
+            black_box(table.has_trigger_prefix_for_method(black_box("tieng"), InputMethod::Telex));
+        });
+
+        // End-to-end: type the word + space through the engine
+        let mut engine = Engine::new();
+        *engine.shortcuts_mut() = table;
+        bench("engine key (qa + space)", size, || {
+            black_box(engine.on_key(keys::Q, false, false));
+            black_box(engine.on_key(keys::A, false, false));
+            black_box(engine.on_key(keys::SPACE, false, false));
+        });
+        bench("engine key (tieng + space)", size, || {
+            for key in [keys::T, keys::I, keys::E, keys::N, keys::G, keys::SPACE] {
+                black_box(engine.on_key(key, false, false));
+            }
+        });
+        println!();
+    }
+}
@@ -0,0 +1,119 @@
+//! Build script: compile embedded word lists into compact DAWGs
+//!
+//! Each word list is turned into a minimized trie (directed acyclic word graph)
+//! and written to `OUT_DIR`. The binary layout is documented in `src/data/dawg.rs`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// (source file, lines to skip, output file)
+const WORD_LISTS: &[(&str, usize, &str)] = &[
+    ("src/data/english_dict_merged.txt", 0, "english.dawg"),
+    ("src/data/dictionaries/vi_daumoi.dic", 1, "vi_daumoi.dawg"),
+    ("src/data/telex_doubles.txt", 0, "telex_doubles.dawg"),
+];
+
+const HEADER_LEN: usize = 8;
+const MAX_CHILDREN: usize = 0x7F;
+const MAX_OFFSET: usize = 0xFF_FFFF;
+
+#[derive(Default)]
+struct TrieNode {
+    terminal: bool,
+    children: BTreeMap<u8, usize>,
+}
+
+struct Builder {
+    trie: Vec<TrieNode>,
+    out: Vec<u8>,
+    /// (terminal, [(label, offset)]) → offset of an already emitted identical node
+    registry: HashMap<(bool, Vec<(u8, usize)>), usize>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            trie: vec![TrieNode::default()],
+            out: vec![0; HEADER_LEN],
+            registry: HashMap::new(),
+        }
+    }
+
+    /// Insert word, returns true if it was not present yet
+    fn insert(&mut self, word: &str) -> bool {
+        let mut node = 0;
+        for &b in word.as_bytes() {
+            node = match self.trie[node].children.get(&b) {
+                Some(&next) => next,
+                None => {
+                    self.trie.push(TrieNode::default());
+                    let next = self.trie.len() - 1;
+                    self.trie[node].children.insert(b, next);
+                    next
+                }
+            };
+        }
+        !std::mem::replace(&mut self.trie[node].terminal, true)
+    }
+
+    /// Emit node in post-order, sharing identical sub-graphs
+    fn emit(&mut self, node: usize) -> usize {
+        let children: Vec<(u8, usize)> = self.trie[node]
+            .children
+            .iter()
+            .map(|(&b, &child)| (b, child))
+            .collect();
+        let edges: Vec<(u8, usize)> = children
+            .into_iter()
+            .map(|(b, child)| (b, self.emit(child)))
+            .collect();
+        let key = (self.trie[node].terminal, edges);
+        if let Some(&offset) = self.registry.get(&key) {
+            return offset;
+        }
+
+        let (terminal, edges) = &key;
+        assert!(edges.len() <= MAX_CHILDREN, "too many children in node");
+        let offset = self.out.len();
+        assert!(offset <= MAX_OFFSET, "word graph exceeds 24-bit offsets");
+        self.out.push(((*terminal as u8) << 7) | edges.len() as u8);
+        self.out.extend(edges.iter().map(|(b, _)| *b));
+        for (_, child) in edges {
+            self.out
+                .extend_from_slice(&(*child as u32).to_le_bytes()[..3]);
+        }
+        self.registry.insert(key, offset);
+        offset
+    }
+
+    fn finish(mut self, count: u32) -> Vec<u8> {
+        let root = self.emit(0) as u32;
+        self.out[0..4].copy_from_slice(&count.to_le_bytes());
+        self.out[4..8].copy_from_slice(&root.to_le_bytes());
+        self.out
+    }
+}
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+
+    for &(source, skip, output) in WORD_LISTS {
+        println!("cargo:rerun-if-changed={}", source);
+        let content =
+            fs::read_to_string(source).unwrap_or_else(|e| panic!("read {}: {}", source, e));
+
+        let mut builder = Builder::new();
+        let mut count = 0u32;
+        for word in content.lines().skip(skip).filter(|l| !l.is_empty()) {
+            if builder.insert(word) {
+                count += 1;
+            }
+        }
+
+        fs::write(Path::new(&out_dir).join(output), builder.finish(count))
+            .unwrap_or_else(|e| panic!("write {}: {}", output, e));
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
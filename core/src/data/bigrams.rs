@@ -0,0 +1,100 @@
+//! Word pair (bigram) tables for context-aware auto-restore
+//!
+//! Each table holds "previous next" pairs that are common in that language.
+//! Used to disambiguate words whose keystrokes are both valid English and valid
+//! Telex Vietnamese: "the box" vs "chẳng bõ".
+//!
+//! No pairs are embedded: there is no corpus-derived table yet, so the tables
+//! start empty and are filled by the host (user or downloaded pair lists).
+//! Without pairs, context restore still leans towards the previous word's language.
+//!
+//! Vietnamese pairs are stored with modern tone placement ("mã hoá"); words
+//! typed in another placement are normalized on insert and on lookup.
+
+use super::vietnamese_spellcheck::normalize_tone_position;
+use super::vowel::TonePlacement;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// English and Vietnamese word pairs (stored lowercase)
+#[derive(Debug, Default)]
+pub struct Bigrams {
+    english: HashSet<String>,
+    vietnamese: HashSet<String>,
+}
+
+fn pair_key(prev: &str, next: &str) -> String {
+    format!("{} {}", prev, next).to_lowercase()
+}
+
+fn modern(word: &str, placement: TonePlacement) -> Cow<'_, str> {
+    normalize_tone_position(word, placement).unwrap_or(Cow::Borrowed(word))
+}
+
+impl Bigrams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an English word pair (case-insensitive)
+    pub fn add_english_pair(&mut self, prev: &str, next: &str) {
+        self.english.insert(pair_key(prev, next));
+    }
+
+    /// Add a Vietnamese word pair typed with `placement` (case-insensitive)
+    pub fn add_vietnamese_pair(&mut self, prev: &str, next: &str, placement: TonePlacement) {
+        self.vietnamese
+            .insert(pair_key(&modern(prev, placement), &modern(next, placement)));
+    }
+
+    /// Check if (prev, next) is a known English word pair (case-insensitive)
+    pub fn is_english_pair(&self, prev: &str, next: &str) -> bool {
+        !self.english.is_empty() && self.english.contains(&pair_key(prev, next))
+    }
+
+    /// Check if (prev, next) is a known Vietnamese word pair (case-insensitive)
+    ///
+    /// `placement` is the tone placement the words were typed with ("mã hóa" with
+    /// traditional oa matches "mã hoá").
+    pub fn is_vietnamese_pair(&self, prev: &str, next: &str, placement: TonePlacement) -> bool {
+        !self.vietnamese.is_empty()
+            && self.vietnamese.contains(&pair_key(
+                &modern(prev, placement),
+                &modern(next, placement),
+            ))
+    }
+
+    /// Remove all pairs
+    pub fn clear(&mut self) {
+        self.english.clear();
+        self.vietnamese.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairs() {
+        let mut pairs = Bigrams::new();
+        assert!(!pairs.is_english_pair("the", "box"));
+        pairs.add_english_pair("the", "box");
+        pairs.add_vietnamese_pair("Chẳng", "bõ", TonePlacement::MODERN);
+        assert!(pairs.is_english_pair("The", "BOX"));
+        assert!(!pairs.is_english_pair("the", "bõ"));
+        assert!(pairs.is_vietnamese_pair("chẳng", "bõ", TonePlacement::MODERN));
+        assert!(!pairs.is_vietnamese_pair("the", "box", TonePlacement::MODERN));
+        pairs.clear();
+        assert!(!pairs.is_english_pair("the", "box"));
+    }
+
+    #[test]
+    fn test_pairs_follow_tone_placement() {
+        let mut pairs = Bigrams::new();
+        pairs.add_vietnamese_pair("mã", "hóa", TonePlacement::TRADITIONAL);
+        assert!(pairs.is_vietnamese_pair("mã", "hoá", TonePlacement::MODERN));
+        assert!(pairs.is_vietnamese_pair("mã", "hóa", TonePlacement::TRADITIONAL));
+        assert!(!pairs.is_vietnamese_pair("mã", "hóa", TonePlacement::MODERN));
+    }
+}
@@ -15,6 +15,7 @@
 //! - Case: lowercase, uppercase
 
 use super::keys;
+use crate::utils;
 
 /// Tone modifiers (dấu phụ) - changes base vowel form
 pub mod tone {
@@ -143,7 +144,7 @@ pub fn get_d(caps: bool) -> char {
 // ============================================================
 
 /// Parsed character components for buffer restoration
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct ParsedChar {
     pub key: u16,
     pub caps: bool,
@@ -404,6 +405,24 @@ pub fn parse_char(c: char) -> Option<ParsedChar> {
     }
 }
 
+/// Parse a composed word into its letters, None if any char is not a letter
+pub fn parse_word(word: &str) -> Option<Vec<ParsedChar>> {
+    word.chars().map(parse_char).collect()
+}
+
+/// Compose parsed letters back into a word (inverse of `parse_word`)
+pub fn compose_word(letters: &[ParsedChar]) -> String {
+    letters
+        .iter()
+        .filter_map(|c| {
+            if c.key == keys::D && c.stroke {
+                return Some(get_d(c.caps));
+            }
+            to_char(c.key, c.caps, c.tone, c.mark).or_else(|| utils::key_to_char(c.key, c.caps))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,163 @@
+//! Compact word graph (DAWG) for embedded word lists
+//!
+//! Word lists are compiled by `build.rs` into a minimized trie over UTF-8 bytes,
+//! so lookups need no startup parsing and no heap allocation.
+//!
+//! ## Binary Layout
+//!
+//! ```text
+//! header: word_count: u32 LE, root_offset: u32 LE
+//! node:   flags: u8        (bit 7 = terminal, bits 0-6 = child count N)
+//!         labels: [u8; N]  (sorted)
+//!         offsets: [u24 LE; N]
+//! ```
+
+/// Read-only view over a compiled word graph
+pub struct Dawg {
+    bytes: &'static [u8],
+}
+
+/// Lowercase a word on the fly as UTF-8 bytes (no allocation)
+fn lowercase_bytes(word: &str) -> impl Iterator<Item = u8> + '_ {
+    word.chars().flat_map(char::to_lowercase).flat_map(|c| {
+        let mut buf = [0u8; 4];
+        let len = c.encode_utf8(&mut buf).len();
+        buf.into_iter().take(len)
+    })
+}
+
+impl Dawg {
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Number of distinct words in the graph
+    pub fn len(&self) -> usize {
+        self.read_u32(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read_u32(&self, at: usize) -> u32 {
+        let b = &self.bytes[at..at + 4];
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+    }
+
+    fn root(&self) -> usize {
+        self.read_u32(4) as usize
+    }
+
+    fn is_terminal(&self, node: usize) -> bool {
+        self.bytes[node] & 0x80 != 0
+    }
+
+    fn child_count(&self, node: usize) -> usize {
+        (self.bytes[node] & 0x7F) as usize
+    }
+
+    fn label(&self, node: usize, i: usize) -> u8 {
+        self.bytes[node + 1 + i]
+    }
+
+    fn child_at(&self, node: usize, i: usize) -> usize {
+        let at = node + 1 + self.child_count(node) + i * 3;
+        let b = &self.bytes[at..at + 3];
+        u32::from_le_bytes([b[0], b[1], b[2], 0]) as usize
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        let n = self.child_count(node);
+        let labels = &self.bytes[node + 1..node + 1 + n];
+        labels
+            .binary_search(&byte)
+            .ok()
+            .map(|i| self.child_at(node, i))
+    }
+
+    fn walk(&self, bytes: impl Iterator<Item = u8>) -> Option<usize> {
+        let mut node = self.root();
+        for b in bytes {
+            node = self.child(node, b)?;
+        }
+        Some(node)
+    }
+
+    /// Exact, case-sensitive lookup
+    pub fn contains(&self, word: &str) -> bool {
+        self.walk(word.bytes())
+            .map(|n| self.is_terminal(n))
+            .unwrap_or(false)
+    }
+
+    /// Case-insensitive lookup (graph stores lowercase words)
+    pub fn contains_ignore_case(&self, word: &str) -> bool {
+        self.walk(lowercase_bytes(word))
+            .map(|n| self.is_terminal(n))
+            .unwrap_or(false)
+    }
+
+    /// Check if any word starts with `prefix` (case-insensitive)
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.walk(lowercase_bytes(prefix)).is_some()
+    }
+
+    /// Collect up to `max` words starting with `prefix` (case-insensitive), in byte order
+    pub fn words_with_prefix(&self, prefix: &str, max: usize) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut path: Vec<u8> = lowercase_bytes(prefix).collect();
+        if let Some(node) = self.walk(path.iter().copied()) {
+            self.collect(node, &mut path, max, &mut result);
+        }
+        result
+    }
+
+    fn collect(&self, node: usize, path: &mut Vec<u8>, max: usize, out: &mut Vec<String>) {
+        if out.len() >= max {
+            return;
+        }
+        if self.is_terminal(node) {
+            if let Ok(word) = std::str::from_utf8(path) {
+                out.push(word.to_string());
+            }
+        }
+        for i in 0..self.child_count(node) {
+            path.push(self.label(node, i));
+            self.collect(self.child_at(node, i), path, max, out);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static WORDS: Dawg = Dawg::new(include_bytes!(concat!(env!("OUT_DIR"), "/english.dawg")));
+
+    #[test]
+    fn test_exact_lookup() {
+        assert!(WORDS.contains("view"));
+        assert!(!WORDS.contains("View"));
+        assert!(WORDS.contains_ignore_case("View"));
+        assert!(!WORDS.contains("vie"));
+        assert!(!WORDS.contains(""));
+    }
+
+    #[test]
+    fn test_non_ascii_words() {
+        assert!(WORDS.contains("düsseldorf"));
+        assert!(WORDS.contains_ignore_case("DÜSSELDORF"));
+    }
+
+    #[test]
+    fn test_prefix_queries() {
+        assert!(WORDS.has_prefix("vie"));
+        assert!(!WORDS.has_prefix("qqq"));
+        let words = WORDS.words_with_prefix("view", 5);
+        assert_eq!(words.len(), 5);
+        assert_eq!(words[0], "view");
+        assert!(words.iter().all(|w| w.starts_with("view")));
+    }
+}
@@ -0,0 +1,43 @@
+//! Keystroke edits - one-typo variants of a word
+//!
+//! Shared by engine autocorrect (buffer chars) and the ranked suggestions of
+//! `vietnamese_spellcheck::suggest` (parsed chars); `key` picks the letter key.
+
+/// Two adjacent letters swapped (pairs of the same key are skipped)
+pub fn swaps<T: Copy>(letters: &[T], key: impl Fn(&T) -> u16) -> Vec<Vec<T>> {
+    (0..letters.len().saturating_sub(1))
+        .filter(|&i| key(&letters[i]) != key(&letters[i + 1]))
+        .map(|i| {
+            let mut swapped = letters.to_vec();
+            swapped.swap(i, i + 1);
+            swapped
+        })
+        .collect()
+}
+
+/// One letter removed (none for single letters)
+pub fn deletions<T: Copy>(letters: &[T]) -> Vec<Vec<T>> {
+    if letters.len() < 2 {
+        return Vec::new();
+    }
+    (0..letters.len())
+        .map(|i| {
+            let mut removed = letters.to_vec();
+            removed.remove(i);
+            removed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits() {
+        let word = [1u16, 2, 2];
+        assert_eq!(swaps(&word, |&k| k), vec![vec![2, 1, 2]]);
+        assert_eq!(deletions(&word), vec![vec![2, 2], vec![1, 2], vec![1, 2]]);
+        assert!(deletions(&[1u16]).is_empty());
+    }
+}
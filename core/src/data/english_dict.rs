@@ -3,24 +3,19 @@
 //! Uses merged dictionary: 10k common words + words with double telex chars.
 //! Only restores to English when raw_input is a known English word.
 
-use std::collections::HashSet;
-use std::sync::LazyLock;
+use super::dawg::Dawg;
 
-/// Embedded English word list (10k + double telex patterns)
-const ENGLISH_WORDS: &str = include_str!("english_dict_merged.txt");
-
-/// HashSet for O(1) lookup
-static DICT: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    ENGLISH_WORDS
-        .lines()
-        .filter(|line| !line.is_empty())
-        .collect()
-});
+/// Embedded English word list (10k + double telex patterns), compiled by build.rs
+static DICT: Dawg = Dawg::new(include_bytes!(concat!(env!("OUT_DIR"), "/english.dawg")));
 
 /// Check if a word is in the English dictionary (case-insensitive)
 pub fn is_english_word(word: &str) -> bool {
-    let lower = word.to_lowercase();
-    DICT.contains(lower.as_str())
+    DICT.contains_ignore_case(word)
+}
+
+/// English words starting with `prefix` (case-insensitive), up to `max`
+pub fn words_with_prefix(prefix: &str, max: usize) -> Vec<String> {
+    DICT.words_with_prefix(prefix, max)
 }
 
 #[cfg(test)]
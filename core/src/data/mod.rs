@@ -5,9 +5,15 @@
 //! - `chars`: Unicode character conversion (includes tone/mark constants)
 //! - `vowel`: Vietnamese vowel phonology system
 //! - `telex_doubles`: English words with Telex double patterns for auto-restore
+//! - `dawg`: Compact word graph used by the embedded word lists
+//! - `edits`: One-typo keystroke variants for autocorrect and suggestions
+//! - `bigrams`: Word pairs for context-aware auto-restore
 
+pub mod bigrams;
 pub mod chars;
 pub mod constants;
+pub mod dawg;
+pub mod edits;
 pub mod english_dict;
 pub mod keys;
 pub mod telex_doubles;
@@ -17,4 +23,4 @@ pub mod vowel;
 pub use chars::{get_d, mark, to_char, tone};
 pub use constants::*;
 pub use keys::{is_break, is_letter, is_vowel};
-pub use vowel::{Modifier, Phonology, Role, Vowel};
+pub use vowel::{Modifier, Phonology, Role, StyledRhyme, TonePlacement, Vowel};
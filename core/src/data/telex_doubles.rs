@@ -1,10032 +1,14 @@
 //! English words containing Telex patterns that should auto-restore.
-//! Binary search O(log n) lookup.
+//! Compiled by build.rs from `telex_doubles.txt` into a compact word graph.
 
-pub static WORDS: [&str; 10022] = [
-    "aa",
-    "aaaa",
-    "aaai",
-    "aaas",
-    "aab",
-    "aabb",
-    "aac",
-    "aachen",
-    "aad",
-    "aae",
-    "aaf",
-    "aag",
-    "aage",
-    "aah",
-    "aai",
-    "aal",
-    "aalborg",
-    "aalto",
-    "aam",
-    "aan",
-    "aap",
-    "aar",
-    "aarhus",
-    "aaron",
-    "aaronson",
-    "aarp",
-    "aas",
-    "aashto",
-    "aat",
-    "aau",
-    "aaup",
-    "aav",
-    "abbess",
-    "abdurrahman",
-    "aberdeen",
-    "aberdeenshire",
-    "aberrant",
-    "aberration",
-    "aberrations",
-    "abhorred",
-    "abhorrence",
-    "abhorrent",
-    "abruptness",
-    "abscess",
-    "abscesses",
-    "abscissa",
-    "abscission",
-    "absentee",
-    "absenteeism",
-    "absentees",
-    "absoluteness",
-    "abstractness",
-    "abyss",
-    "abyssal",
-    "abysses",
-    "abyssinia",
-    "abyssinian",
-    "abyssinians",
-    "acas",
-    "access",
-    "accessed",
-    "accesses",
-    "accessibility",
-    "accessible",
-    "accessing",
-    "accession",
-    "accessions",
-    "accessor",
-    "accessories",
-    "accessory",
-    "achr",
-    "ackoff",
-    "acquisitiveness",
-    "across",
-    "acrs",
-    "actress",
-    "actresses",
-    "acupressure",
-    "acuteness",
-    "adaptiveness",
-    "add",
-    "adda",
-    "addams",
-    "added",
-    "addend",
-    "addenda",
-    "addendum",
-    "adder",
-    "adderley",
-    "adders",
-    "addi",
-    "addict",
-    "addicted",
-    "addicting",
-    "addiction",
-    "addictions",
-    "addictive",
-    "addicts",
-    "addie",
-    "adding",
-    "addington",
-    "addis",
-    "addison",
-    "addisonwesley",
-    "addition",
-    "additional",
-    "additionally",
-    "additions",
-    "additive",
-    "additively",
-    "additives",
-    "additivity",
-    "addled",
-    "addr",
-    "address",
-    "addressable",
-    "addressed",
-    "addressee",
-    "addressees",
-    "addresses",
-    "addressing",
-    "adds",
-    "adduce",
-    "adduced",
-    "adduces",
-    "adducing",
-    "adduct",
-    "adducted",
-    "adduction",
-    "adductor",
-    "adductors",
-    "adducts",
-    "addy",
-    "adhesiveness",
-    "admissibility",
-    "admissible",
-    "admission",
-    "admissions",
-    "adoptee",
-    "adoptees",
-    "adresse",
-    "adriaan",
-    "adroitness",
-    "adulteress",
-    "adulthood",
-    "aee",
-    "afar",
-    "aff",
-    "affability",
-    "affable",
-    "affably",
-    "affair",
-    "affaire",
-    "affaires",
-    "affairs",
-    "affd",
-    "affect",
-    "affectation",
-    "affectations",
-    "affected",
-    "affecting",
-    "affection",
-    "affectional",
-    "affectionate",
-    "affectionately",
-    "affections",
-    "affective",
-    "affectively",
-    "affectivity",
-    "affects",
-    "afferent",
-    "afferents",
-    "affiant",
-    "affidavit",
-    "affidavits",
-    "affiliate",
-    "affiliated",
-    "affiliates",
-    "affiliating",
-    "affiliation",
-    "affiliations",
-    "affiliative",
-    "affinal",
-    "affine",
-    "affines",
-    "affinis",
-    "affinities",
-    "affinity",
-    "affirm",
-    "affirmance",
-    "affirmation",
-    "affirmations",
-    "affirmative",
-    "affirmatively",
-    "affirmed",
-    "affirming",
-    "affirms",
-    "affix",
-    "affixation",
-    "affixed",
-    "affixes",
-    "affixing",
-    "affleck",
-    "afflict",
-    "afflicted",
-    "afflicting",
-    "affliction",
-    "afflictions",
-    "afflicts",
-    "affluence",
-    "affluent",
-    "afford",
-    "affordability",
-    "affordable",
-    "affordably",
-    "affordances",
-    "afforded",
-    "affording",
-    "affords",
-    "afforestation",
-    "affray",
-    "affrighted",
-    "affront",
-    "affronted",
-    "affronts",
-    "afoot",
-    "afrikaans",
-    "afsc",
-    "aftereffect",
-    "aftereffects",
-    "afternoon",
-    "afternoons",
-    "afterschool",
-    "agassi",
-    "agassiz",
-    "agee",
-    "ageless",
-    "aggression",
-    "aggressions",
-    "aggressive",
-    "aggressively",
-    "aggressiveness",
-    "aggressor",
-    "aggressors",
-    "agree",
-    "agreeable",
-    "agreeableness",
-    "agreeably",
-    "agreed",
-    "agreeing",
-    "agreement",
-    "agreements",
-    "agrees",
-    "agribusiness",
-    "aguirre",
-    "aiaa",
-    "aiee",
-    "aileen",
-    "aimee",
-    "aimless",
-    "aimlessly",
-    "aimlessness",
-    "airless",
-    "airs",
-    "airspeed",
-    "airworthiness",
-    "ajar",
-    "ajax",
-    "ajay",
-    "ajnr",
-    "aladdin",
-    "alauddin",
-    "albatross",
-    "albatrosses",
-    "albee",
-    "alertness",
-    "alessandra",
-    "alessandri",
-    "alessandro",
-    "alessio",
-    "algaas",
-    "alissa",
-    "aliveness",
-    "allaah",
-    "allee",
-    "aloneness",
-    "aloof",
-    "aloofness",
-    "althusser",
-    "altoona",
-    "alyssa",
-    "amar",
-    "amass",
-    "amassed",
-    "amassing",
-    "amax",
-    "ambassador",
-    "ambassadorial",
-    "ambassadors",
-    "ameer",
-    "amenorrhea",
-    "amenorrhoea",
-    "amiss",
-    "amputee",
-    "amputees",
-    "anas",
-    "ancestress",
-    "andersson",
-    "andorra",
-    "andrassy",
-    "andree",
-    "andreev",
-    "anga",
-    "angleterre",
-    "annee",
-    "annees",
-    "ansa",
-    "ansar",
-    "anschluss",
-    "anteroom",
-    "antiarrhythmic",
-    "anticorruption",
-    "antidepressant",
-    "antidepressants",
-    "antiferromagnetic",
-    "antifreeze",
-    "antiterrorism",
-    "anwar",
-    "apogee",
-    "appellee",
-    "appellees",
-    "appointee",
-    "appointees",
-    "appressed",
-    "appropriateness",
-    "aptness",
-    "arai",
-    "aram",
-    "aran",
-    "aras",
-    "arawak",
-    "arbitrariness",
-    "archduchess",
-    "armageddon",
-    "armas",
-    "armee",
-    "arms",
-    "arna",
-    "aronoff",
-    "arr",
-    "arraigned",
-    "arraignment",
-    "arran",
-    "arrange",
-    "arranged",
-    "arrangement",
-    "arrangements",
-    "arranger",
-    "arrangers",
-    "arranges",
-    "arranging",
-    "arrant",
-    "arras",
-    "array",
-    "arrayed",
-    "arraylist",
-    "arrays",
-    "arrear",
-    "arrears",
-    "arrest",
-    "arrested",
-    "arrestee",
-    "arrestees",
-    "arrester",
-    "arresting",
-    "arrests",
-    "arrhenius",
-    "arrhythmia",
-    "arrhythmias",
-    "arrhythmic",
-    "arriaga",
-    "arrian",
-    "arriba",
-    "arrighi",
-    "arrigo",
-    "arrington",
-    "arrival",
-    "arrivals",
-    "arrive",
-    "arrived",
-    "arrives",
-    "arriving",
-    "arrogance",
-    "arrogant",
-    "arrogantly",
-    "arrogate",
-    "arrogated",
-    "arrondissement",
-    "arrow",
-    "arrowed",
-    "arrowhead",
-    "arrowheads",
-    "arrowroot",
-    "arrows",
-    "arrowsmith",
-    "arroyo",
-    "arroyos",
-    "artless",
-    "asaf",
-    "asan",
-    "asap",
-    "asat",
-    "asch",
-    "aschoff",
-    "ashbee",
-    "asleep",
-    "asps",
-    "aspx",
-    "assad",
-    "assai",
-    "assail",
-    "assailant",
-    "assailants",
-    "assailed",
-    "assailing",
-    "assails",
-    "assam",
-    "assamese",
-    "assassin",
-    "assassinate",
-    "assassinated",
-    "assassinating",
-    "assassination",
-    "assassinations",
-    "assassins",
-    "assault",
-    "assaulted",
-    "assaulting",
-    "assaultive",
-    "assaults",
-    "assay",
-    "assayed",
-    "assaying",
-    "assays",
-    "asse",
-    "assed",
-    "assemblage",
-    "assemblages",
-    "assemble",
-    "assembled",
-    "assemblee",
-    "assembler",
-    "assemblers",
-    "assembles",
-    "assemblies",
-    "assembling",
-    "assembly",
-    "assemblyman",
-    "assemblymen",
-    "assen",
-    "assent",
-    "assented",
-    "assenting",
-    "assents",
-    "asser",
-    "assert",
-    "asserted",
-    "asserting",
-    "assertion",
-    "assertions",
-    "assertive",
-    "assertively",
-    "assertiveness",
-    "asserts",
-    "asses",
-    "assess",
-    "assessable",
-    "assessed",
-    "assessee",
-    "assesses",
-    "assessing",
-    "assessment",
-    "assessments",
-    "assessor",
-    "assessors",
-    "asset",
-    "assets",
-    "assez",
-    "asshole",
-    "assholes",
-    "assi",
-    "assiduity",
-    "assiduous",
-    "assiduously",
-    "assign",
-    "assignable",
-    "assignation",
-    "assigned",
-    "assignee",
-    "assignees",
-    "assigning",
-    "assignment",
-    "assignments",
-    "assignor",
-    "assigns",
-    "assimilable",
-    "assimilate",
-    "assimilated",
-    "assimilates",
-    "assimilating",
-    "assimilation",
-    "assimilationist",
-    "assimilative",
-    "assiniboine",
-    "assis",
-    "assisi",
-    "assist",
-    "assistance",
-    "assistant",
-    "assistants",
-    "assistantship",
-    "assistantships",
-    "assisted",
-    "assisting",
-    "assistive",
-    "assists",
-    "assize",
-    "assizes",
-    "assmann",
-    "assn",
-    "asso",
-    "assoc",
-    "associa",
-    "associate",
-    "associated",
-    "associates",
-    "associating",
-    "association",
-    "associational",
-    "associationism",
-    "associationist",
-    "associations",
-    "associative",
-    "associativity",
-    "associazione",
-    "assocs",
-    "assonance",
-    "assortative",
-    "assorted",
-    "assortment",
-    "assortments",
-    "assr",
-    "asst",
-    "assuage",
-    "assuaged",
-    "assume",
-    "assumed",
-    "assumes",
-    "assuming",
-    "assumpsit",
-    "assumption",
-    "assumptions",
-    "assur",
-    "assurance",
-    "assurances",
-    "assure",
-    "assured",
-    "assuredly",
-    "assurer",
-    "assures",
-    "assuring",
-    "assyria",
-    "assyrian",
-    "assyrians",
-    "astr",
-    "astuteness",
-    "aswan",
-    "atas",
-    "atlee",
-    "attendees",
-    "attentiveness",
-    "attlee",
-    "attractiveness",
-    "attwood",
-    "atwood",
-    "auchincloss",
-    "auctioneer",
-    "auctioneers",
-    "audiocassette",
-    "auffassung",
-    "aura",
-    "auras",
-    "aussi",
-    "aussie",
-    "ausstellung",
-    "authoress",
-    "autocorrect",
-    "autocorrelation",
-    "autocorrelations",
-    "autoregressive",
-    "auxerre",
-    "averred",
-    "averroes",
-    "avhrr",
-    "awacs",
-    "awareness",
-    "aways",
-    "awfulness",
-    "awkwardness",
-    "awwa",
-    "baa",
-    "baade",
-    "baader",
-    "baal",
-    "baas",
-    "baath",
-    "baboon",
-    "baboons",
-    "babyhood",
-    "baca",
-    "backcross",
-    "backdoor",
-    "backroom",
-    "backwardness",
-    "backwoods",
-    "baddeley",
-    "badness",
-    "baffin",
-    "baffle",
-    "baffled",
-    "bafflement",
-    "baffles",
-    "baffling",
-    "bagasse",
-    "bailee",
-    "bailiff",
-    "bailiffs",
-    "bair",
-    "baja",
-    "baji",
-    "bajo",
-    "bakr",
-    "balaam",
-    "balassa",
-    "baldassare",
-    "baldness",
-    "baleen",
-    "balloon",
-    "ballooned",
-    "ballooning",
-    "balloons",
-    "ballroom",
-    "ballrooms",
-    "ballyhoo",
-    "bama",
-    "bamboo",
-    "bamboos",
-    "bana",
-    "banarsidass",
-    "bandpass",
-    "banerjee",
-    "banff",
-    "bangs",
-    "bans",
-    "banshee",
-    "bara",
-    "barbarossa",
-    "barbee",
-    "barberry",
-    "bardeen",
-    "barefoot",
-    "barefooted",
-    "bareness",
-    "bari",
-    "barn",
-    "barna",
-    "barns",
-    "baroness",
-    "barr",
-    "barra",
-    "barrack",
-    "barracks",
-    "barraclough",
-    "barracuda",
-    "barrage",
-    "barrages",
-    "barral",
-    "barras",
-    "barratt",
-    "barrault",
-    "barre",
-    "barred",
-    "barrel",
-    "barreled",
-    "barreling",
-    "barrell",
-    "barrelled",
-    "barrels",
-    "barren",
-    "barrenness",
-    "barrens",
-    "barrera",
-    "barres",
-    "barret",
-    "barreto",
-    "barrett",
-    "barricade",
-    "barricaded",
-    "barricades",
-    "barrie",
-    "barrier",
-    "barriers",
-    "barring",
-    "barringer",
-    "barrington",
-    "barrio",
-    "barrios",
-    "barrister",
-    "barristers",
-    "barro",
-    "barron",
-    "barroom",
-    "barros",
-    "barrow",
-    "barrows",
-    "barry",
-    "barrymore",
-    "bars",
-    "baru",
-    "barua",
-    "basa",
-    "basch",
-    "baseless",
-    "baseness",
-    "basf",
-    "bashfulness",
-    "basi",
-    "basis",
-    "bask",
-    "basor",
-    "bass",
-    "bassa",
-    "bassanio",
-    "bassano",
-    "basse",
-    "basses",
-    "basset",
-    "bassett",
-    "bassi",
-    "bassin",
-    "bassinet",
-    "bassist",
-    "basso",
-    "bassoon",
-    "bassoons",
-    "basswood",
-    "bast",
-    "basta",
-    "basu",
-    "bataan",
-    "bathroom",
-    "bathrooms",
-    "bats",
-    "baur",
-    "bayliss",
-    "bays",
-    "bazaar",
-    "bazaars",
-    "bazooka",
-    "bdd",
-    "beardless",
-    "beaverbrook",
-    "becher",
-    "bedded",
-    "bedding",
-    "beddoes",
-    "bedridden",
-    "bedroom",
-    "bedrooms",
-    "beebe",
-    "beech",
-    "beecham",
-    "beecher",
-    "beeches",
-    "beechey",
-    "beechwood",
-    "beef",
-    "beefsteak",
-    "beefy",
-    "beehive",
-    "beehives",
-    "beek",
-    "beekeeper",
-    "beekeepers",
-    "beekeeping",
-    "beekman",
-    "beeline",
-    "beelzebub",
-    "beeman",
-    "been",
-    "beene",
-    "beep",
-    "beeped",
-    "beeper",
-    "beeping",
-    "beeps",
-    "beer",
-    "beerbohm",
-    "beers",
-    "beersheba",
-    "beery",
-    "bees",
-    "beeson",
-    "beeston",
-    "beeswax",
-    "beet",
-    "beethoven",
-    "beetle",
-    "beetles",
-    "beeton",
-    "beetroot",
-    "beets",
-    "befuddled",
-    "beginningless",
-    "begriff",
-    "begriffe",
-    "behooves",
-    "beloff",
-    "belongingness",
-    "belorussia",
-    "belorussian",
-    "benacerraf",
-    "bene",
-    "benes",
-    "bengalee",
-    "bengtsson",
-    "benj",
-    "benteen",
-    "bere",
-    "berm",
-    "bern",
-    "berne",
-    "berner",
-    "berners",
-    "berns",
-    "bernstorff",
-    "berra",
-    "berri",
-    "berridge",
-    "berrien",
-    "berries",
-    "berrigan",
-    "berry",
-    "berryman",
-    "bers",
-    "bertalanffy",
-    "berween",
-    "beseech",
-    "beseeched",
-    "beseeching",
-    "bess",
-    "bessarabia",
-    "bessel",
-    "bessemer",
-    "besser",
-    "bessie",
-    "besson",
-    "bessy",
-    "best",
-    "beste",
-    "betook",
-    "bets",
-    "between",
-    "betweene",
-    "betweens",
-    "bhoodan",
-    "biarritz",
-    "bias",
-    "bibliobazaar",
-    "bidden",
-    "bidder",
-    "bidders",
-    "bidding",
-    "biddle",
-    "biddulph",
-    "biddy",
-    "bienes",
-    "biens",
-    "bier",
-    "biff",
-    "bigfoot",
-    "bigness",
-    "bins",
-    "bioassay",
-    "bioassays",
-    "bioengineering",
-    "biofeedback",
-    "biomass",
-    "bioterrorism",
-    "birr",
-    "birrell",
-    "birren",
-    "bisbee",
-    "bischoff",
-    "bissau",
-    "bissell",
-    "bisset",
-    "bisson",
-    "bist",
-    "bits",
-    "bitterness",
-    "bitterroot",
-    "bittersweet",
-    "biweekly",
-    "bizarre",
-    "bizarrely",
-    "bjerrum",
-    "blackberries",
-    "blackberry",
-    "blackfeet",
-    "blackfoot",
-    "blackness",
-    "blackpool",
-    "blackwood",
-    "bladder",
-    "bladders",
-    "blakeslee",
-    "blameless",
-    "blandness",
-    "blankness",
-    "blass",
-    "blassingame",
-    "bleakness",
-    "bleecker",
-    "bleed",
-    "bleeder",
-    "bleeding",
-    "bleeds",
-    "bleek",
-    "bleeker",
-    "bless",
-    "blessed",
-    "blessedly",
-    "blessedness",
-    "blesses",
-    "blessing",
-    "blessings",
-    "blessington",
-    "blindness",
-    "bliss",
-    "blissful",
-    "blissfully",
-    "blood",
-    "bloodbath",
-    "blooded",
-    "bloodhound",
-    "bloodhounds",
-    "bloodied",
-    "bloodiest",
-    "bloodless",
-    "bloodletting",
-    "bloodline",
-    "bloodlines",
-    "bloods",
-    "bloodshed",
-    "bloodshot",
-    "bloodstained",
-    "bloodstains",
-    "bloodstream",
-    "bloodthirsty",
-    "bloody",
-    "bloom",
-    "bloomberg",
-    "bloomed",
-    "bloomer",
-    "bloomers",
-    "bloomfield",
-    "blooming",
-    "bloomingdale",
-    "bloomington",
-    "blooms",
-    "bloomsbury",
-    "bloor",
-    "blossom",
-    "blossomed",
-    "blossoming",
-    "blossoms",
-    "blueberries",
-    "blueberry",
-    "bluegrass",
-    "bluegreen",
-    "blueness",
-    "bluetooth",
-    "bluff",
-    "bluffing",
-    "bluffs",
-    "bluntness",
-    "blurred",
-    "blurring",
-    "blurry",
-    "boar",
-    "boardroom",
-    "boardrooms",
-    "boars",
-    "boas",
-    "boast",
-    "boasts",
-    "boats",
-    "boddy",
-    "bodiless",
-    "boerhaave",
-    "boers",
-    "boff",
-    "boldness",
-    "bollywood",
-    "boneless",
-    "bonferroni",
-    "bongo",
-    "bonhoeffer",
-    "bono",
-    "bons",
-    "boo",
-    "boob",
-    "boobs",
-    "booby",
-    "booed",
-    "boogie",
-    "booing",
-    "book",
-    "bookbinder",
-    "bookbinders",
-    "bookbinding",
-    "bookcase",
-    "bookcases",
-    "bookchin",
-    "booke",
-    "booked",
-    "booker",
-    "bookes",
-    "bookie",
-    "booking",
-    "bookings",
-    "bookish",
-    "bookkeeper",
-    "bookkeepers",
-    "bookkeeping",
-    "booklet",
-    "booklets",
-    "booklist",
-    "bookmaker",
-    "bookmakers",
-    "bookmaking",
-    "bookman",
-    "bookmark",
-    "bookmarks",
-    "books",
-    "bookseller",
-    "booksellers",
-    "bookselling",
-    "bookshelf",
-    "bookshelves",
-    "bookshop",
-    "bookshops",
-    "bookstore",
-    "bookstores",
-    "bookworm",
-    "bool",
-    "boole",
-    "boolean",
-    "boom",
-    "boomed",
-    "boomer",
-    "boomerang",
-    "boomers",
-    "booming",
-    "booms",
-    "boon",
-    "boone",
-    "boons",
-    "boor",
-    "boorish",
-    "boorman",
-    "boorstin",
-    "boos",
-    "boosey",
-    "boost",
-    "boosted",
-    "booster",
-    "boosters",
-    "boosting",
-    "boosts",
-    "boot",
-    "bootable",
-    "booted",
-    "booth",
-    "boothby",
-    "boothe",
-    "booths",
-    "booting",
-    "bootleg",
-    "bootlegger",
-    "bootleggers",
-    "bootlegging",
-    "boots",
-    "bootstrap",
-    "bootstrapping",
-    "bootstraps",
-    "booty",
-    "booz",
-    "booze",
-    "bora",
-    "borax",
-    "borderless",
-    "bori",
-    "boris",
-    "born",
-    "borno",
-    "borns",
-    "boro",
-    "borrego",
-    "borrelia",
-    "borromeo",
-    "borromini",
-    "borrow",
-    "borrowed",
-    "borrower",
-    "borrowers",
-    "borrowing",
-    "borrowings",
-    "borrows",
-    "bors",
-    "borst",
-    "bosch",
-    "bosco",
-    "boss",
-    "bosse",
-    "bossed",
-    "bosses",
-    "bossing",
-    "bossuet",
-    "bossy",
-    "bost",
-    "botox",
-    "bots",
-    "bottomless",
-    "boundedness",
-    "boundless",
-    "bourassa",
-    "boussinesq",
-    "bowra",
-    "bows",
-    "boxwood",
-    "boyhood",
-    "braddock",
-    "braddon",
-    "bradlee",
-    "bradstreet",
-    "braidwood",
-    "brainless",
-    "braintree",
-    "braniff",
-    "brass",
-    "brasserie",
-    "brasses",
-    "brassey",
-    "brassica",
-    "brassicae",
-    "brassiere",
-    "brassy",
-    "breastfeed",
-    "breastfeeding",
-    "breathless",
-    "breathlessly",
-    "breathlessness",
-    "bree",
-    "breech",
-    "breeches",
-    "breed",
-    "breeder",
-    "breeders",
-    "breeding",
-    "breedlove",
-    "breeds",
-    "breen",
-    "breese",
-    "breeze",
-    "breezed",
-    "breezes",
-    "breezy",
-    "bremsstrahlung",
-    "brentwood",
-    "bresson",
-    "bridegroom",
-    "brightness",
-    "brisson",
-    "brissot",
-    "britishness",
-    "brittleness",
-    "broadmoor",
-    "broadsheet",
-    "broadsheets",
-    "brokenness",
-    "brooch",
-    "brooches",
-    "brood",
-    "brooded",
-    "brooding",
-    "broods",
-    "brook",
-    "brooke",
-    "brooked",
-    "brooker",
-    "brookes",
-    "brookfield",
-    "brookhaven",
-    "brookings",
-    "brookline",
-    "brooklyn",
-    "brooks",
-    "broom",
-    "broome",
-    "brooms",
-    "broomstick",
-    "brotherhood",
-    "brotherhoods",
-    "broussard",
-    "brownlee",
-    "brushwood",
-    "brussel",
-    "brussels",
-    "bss",
-    "buccaneer",
-    "buccaneers",
-    "budd",
-    "budded",
-    "buddha",
-    "buddhaghosa",
-    "buddhahood",
-    "buddhas",
-    "buddhi",
-    "buddhism",
-    "buddhist",
-    "buddhistic",
-    "buddhists",
-    "buddies",
-    "budding",
-    "buddy",
-    "buff",
-    "buffa",
-    "buffalo",
-    "buffaloes",
-    "buffed",
-    "buffer",
-    "buffered",
-    "buffering",
-    "buffers",
-    "buffet",
-    "buffeted",
-    "buffeting",
-    "buffets",
-    "buffett",
-    "buffing",
-    "buffon",
-    "buffoon",
-    "buffoonery",
-    "buffoons",
-    "buffs",
-    "buffy",
-    "bufo",
-    "buisson",
-    "bulletproof",
-    "bumblebee",
-    "bumblebees",
-    "bums",
-    "bungee",
-    "buns",
-    "buonarroti",
-    "burgess",
-    "burgesses",
-    "burn",
-    "burns",
-    "burr",
-    "burra",
-    "burrell",
-    "burridge",
-    "burris",
-    "burrito",
-    "burritos",
-    "burro",
-    "burros",
-    "burrough",
-    "burroughs",
-    "burrow",
-    "burrowed",
-    "burrowing",
-    "burrows",
-    "burrs",
-    "burrus",
-    "burs",
-    "burst",
-    "bursts",
-    "bury",
-    "busch",
-    "business",
-    "businesses",
-    "businesslike",
-    "businessman",
-    "businessmen",
-    "businesspeople",
-    "businessperson",
-    "businessweek",
-    "businesswoman",
-    "buss",
-    "busse",
-    "busses",
-    "bussey",
-    "bussy",
-    "bust",
-    "busts",
-    "busy",
-    "busyness",
-    "buts",
-    "buttress",
-    "buttressed",
-    "buttresses",
-    "buttressing",
-    "buxom",
-    "buyer",
-    "buyers",
-    "buys",
-    "bybee",
-    "byelorussia",
-    "byelorussian",
-    "byers",
-    "byes",
-    "bypass",
-    "bypassed",
-    "bypasses",
-    "bypassing",
-    "bysshe",
-    "caa",
-    "caboose",
-    "cachar",
-    "caddie",
-    "caddies",
-    "caddis",
-    "caddo",
-    "caddy",
-    "caffe",
-    "caffeine",
-    "cahoots",
-    "cairncross",
-    "caisse",
-    "caisson",
-    "caissons",
-    "caithness",
-    "caja",
-    "calderwood",
-    "callousness",
-    "calmness",
-    "cama",
-    "camas",
-    "cameroon",
-    "cameroonian",
-    "cameroons",
-    "cams",
-    "cana",
-    "canaan",
-    "canaanite",
-    "canaanites",
-    "canberra",
-    "canr",
-    "cans",
-    "canteen",
-    "canteens",
-    "canvass",
-    "canvassed",
-    "canvassers",
-    "canvasses",
-    "canvassing",
-    "capriciousness",
-    "caps",
-    "cara",
-    "carcass",
-    "carcasses",
-    "carcassonne",
-    "cardiff",
-    "careen",
-    "careened",
-    "careening",
-    "career",
-    "careering",
-    "careerism",
-    "careerist",
-    "careerists",
-    "careers",
-    "carefree",
-    "carefulness",
-    "careless",
-    "carelessly",
-    "carelessness",
-    "caress",
-    "caressed",
-    "caresses",
-    "caressing",
-    "cari",
-    "cariboo",
-    "carkhuff",
-    "carlsson",
-    "caro",
-    "carr",
-    "carra",
-    "carracci",
-    "carrageenan",
-    "carranza",
-    "carrara",
-    "carrasco",
-    "carrefour",
-    "carrel",
-    "carrera",
-    "carreras",
-    "carretera",
-    "carriage",
-    "carriages",
-    "carriageway",
-    "carrick",
-    "carrie",
-    "carried",
-    "carrier",
-    "carriere",
-    "carriers",
-    "carries",
-    "carrigan",
-    "carrillo",
-    "carrington",
-    "carrion",
-    "carrol",
-    "carroll",
-    "carrollton",
-    "carron",
-    "carrot",
-    "carrots",
-    "carruthers",
-    "carry",
-    "carryback",
-    "carryforward",
-    "carrying",
-    "carryover",
-    "carryovers",
-    "cars",
-    "cartoon",
-    "cartooning",
-    "cartoonist",
-    "cartoonists",
-    "cartoons",
-    "cartwheel",
-    "cartwheels",
-    "carus",
-    "cary",
-    "casa",
-    "casebook",
-    "casi",
-    "casos",
-    "cass",
-    "cassa",
-    "cassady",
-    "cassandra",
-    "cassation",
-    "cassatt",
-    "cassava",
-    "cassegrain",
-    "cassel",
-    "cassell",
-    "casserole",
-    "casseroles",
-    "cassette",
-    "cassettes",
-    "cassia",
-    "cassian",
-    "cassidy",
-    "cassie",
-    "cassin",
-    "cassini",
-    "cassino",
-    "cassio",
-    "cassiodorus",
-    "cassiopeia",
-    "cassirer",
-    "cassis",
-    "cassiterite",
-    "cassius",
-    "cassock",
-    "casson",
-    "cassy",
-    "cast",
-    "casta",
-    "casts",
-    "casu",
-    "casualness",
-    "casus",
-    "catarrh",
-    "catarrhal",
-    "catherwood",
-    "cathleen",
-    "cats",
-    "caus",
-    "causa",
-    "causas",
-    "cautiousness",
-    "cays",
-    "ceaseless",
-    "ceaselessly",
-    "cee",
-    "ceecs",
-    "centeredness",
-    "centredness",
-    "cerrado",
-    "cerro",
-    "cess",
-    "cessation",
-    "cesse",
-    "cesses",
-    "cession",
-    "cessions",
-    "cessna",
-    "cesspool",
-    "cesspools",
-    "cetera",
-    "ceteris",
-    "cff",
-    "chafee",
-    "chaff",
-    "chaffee",
-    "chair",
-    "chairs",
-    "chaka",
-    "chakra",
-    "chakras",
-    "chama",
-    "chamar",
-    "chamars",
-    "chambliss",
-    "chamorro",
-    "chana",
-    "changeless",
-    "chaos",
-    "chaparral",
-    "chapbook",
-    "chapbooks",
-    "chaps",
-    "char",
-    "chara",
-    "chari",
-    "charioteer",
-    "charis",
-    "charm",
-    "charms",
-    "charred",
-    "charring",
-    "charron",
-    "chars",
-    "chary",
-    "chas",
-    "chasm",
-    "chasms",
-    "chasse",
-    "chasseurs",
-    "chassis",
-    "chats",
-    "chattahoochee",
-    "chattanooga",
-    "chatterjee",
-    "chauffeur",
-    "chauffeurs",
-    "cheapness",
-    "checkbook",
-    "cheddar",
-    "chee",
-    "cheek",
-    "cheekbone",
-    "cheekbones",
-    "cheeked",
-    "cheeks",
-    "cheeky",
-    "cheer",
-    "cheered",
-    "cheerful",
-    "cheerfully",
-    "cheerfulness",
-    "cheerily",
-    "cheering",
-    "cheerleader",
-    "cheerleaders",
-    "cheerleading",
-    "cheerless",
-    "cheers",
-    "cheery",
-    "cheese",
-    "cheeseburger",
-    "cheesecake",
-    "cheesecloth",
-    "cheeseman",
-    "cheeses",
-    "cheesy",
-    "cheetah",
-    "cheetahs",
-    "cheetham",
-    "cheever",
-    "chef",
-    "chefs",
-    "cher",
-    "chere",
-    "cherokee",
-    "cherokees",
-    "cheroot",
-    "cherries",
-    "cherry",
-    "ches",
-    "chess",
-    "chessboard",
-    "chessman",
-    "chest",
-    "chests",
-    "chiapas",
-    "chiasm",
-    "chickadee",
-    "chickadees",
-    "chief",
-    "chiefest",
-    "chiefs",
-    "chiffon",
-    "childhood",
-    "childhoods",
-    "childishness",
-    "childless",
-    "childlessness",
-    "childress",
-    "chimpanzee",
-    "chimpanzees",
-    "chinook",
-    "chips",
-    "chir",
-    "choir",
-    "choirs",
-    "choix",
-    "choo",
-    "choose",
-    "chooser",
-    "choosers",
-    "chooses",
-    "choosing",
-    "choosy",
-    "chops",
-    "chor",
-    "chora",
-    "chos",
-    "chosa",
-    "chow",
-    "chowk",
-    "chrissie",
-    "chrissy",
-    "christiaan",
-    "christoffel",
-    "chromaffin",
-    "chums",
-    "chur",
-    "churn",
-    "churns",
-    "cimarron",
-    "circassian",
-    "circassians",
-    "cirrhosis",
-    "cirrhotic",
-    "cirri",
-    "cirrus",
-    "cissy",
-    "cladding",
-    "clarissa",
-    "clarisse",
-    "class",
-    "classe",
-    "classed",
-    "classen",
-    "classes",
-    "classi",
-    "classic",
-    "classica",
-    "classical",
-    "classically",
-    "classicism",
-    "classicist",
-    "classicists",
-    "classics",
-    "classicus",
-    "classifiable",
-    "classification",
-    "classifications",
-    "classificatory",
-    "classified",
-    "classifieds",
-    "classifier",
-    "classifiers",
-    "classifies",
-    "classify",
-    "classifying",
-    "classique",
-    "classiques",
-    "classis",
-    "classism",
-    "classless",
-    "classmate",
-    "classmates",
-    "classpath",
-    "classroom",
-    "classrooms",
-    "classwork",
-    "classy",
-    "cleanliness",
-    "cleanness",
-    "clearness",
-    "cleverness",
-    "cliff",
-    "cliffe",
-    "clifford",
-    "cliffs",
-    "cloakroom",
-    "clooney",
-    "closeness",
-    "cloudiness",
-    "cloudless",
-    "clubfoot",
-    "clueless",
-    "clumsiness",
-    "coarseness",
-    "coast",
-    "coasts",
-    "coats",
-    "coax",
-    "cockatoo",
-    "cockatoos",
-    "coco",
-    "cocoon",
-    "cocoons",
-    "cocos",
-    "codd",
-    "coddington",
-    "coddled",
-    "coddling",
-    "codebook",
-    "coeff",
-    "coefficient",
-    "coefficients",
-    "coetzee",
-    "coffee",
-    "coffeehouse",
-    "coffeehouses",
-    "coffeemaker",
-    "coffeepot",
-    "coffees",
-    "coffer",
-    "coffers",
-    "coffey",
-    "coffin",
-    "coffins",
-    "coffman",
-    "cohesiveness",
-    "coif",
-    "coiffure",
-    "coir",
-    "coldness",
-    "colebrook",
-    "colebrooke",
-    "colleen",
-    "collingwood",
-    "colorless",
-    "colossal",
-    "colosseum",
-    "colossians",
-    "colossus",
-    "colourless",
-    "comaroff",
-    "combativeness",
-    "comeliness",
-    "comfortless",
-    "commandeer",
-    "commandeered",
-    "commissaire",
-    "commissar",
-    "commissariat",
-    "commissaries",
-    "commissars",
-    "commissary",
-    "commission",
-    "commissioned",
-    "commissioner",
-    "commissioners",
-    "commissioning",
-    "commissions",
-    "commissural",
-    "commissure",
-    "commissures",
-    "committee",
-    "committeeman",
-    "committeemen",
-    "committees",
-    "commonness",
-    "como",
-    "comoros",
-    "compactness",
-    "comparee",
-    "compass",
-    "compassed",
-    "compasses",
-    "compassion",
-    "compassionate",
-    "compassionately",
-    "competitiveness",
-    "completeness",
-    "comprehensiveness",
-    "compress",
-    "compressed",
-    "compresses",
-    "compressibility",
-    "compressible",
-    "compressing",
-    "compression",
-    "compressional",
-    "compressions",
-    "compressive",
-    "compressor",
-    "compressors",
-    "comr",
-    "coms",
-    "comtesse",
-    "concession",
-    "concessionaire",
-    "concessionaires",
-    "concessional",
-    "concessionary",
-    "concessions",
-    "conciseness",
-    "concreteness",
-    "concurred",
-    "concurrence",
-    "concurrency",
-    "concurrent",
-    "concurrently",
-    "concurring",
-    "concussion",
-    "condoleezza",
-    "conferees",
-    "conferred",
-    "conferring",
-    "confess",
-    "confessed",
-    "confessedly",
-    "confesses",
-    "confessing",
-    "confessio",
-    "confession",
-    "confessional",
-    "confessions",
-    "confessor",
-    "confessors",
-    "congo",
-    "congr",
-    "congress",
-    "congresses",
-    "congressional",
-    "congressman",
-    "congressmen",
-    "congresso",
-    "congresswoman",
-    "conj",
-    "connaissance",
-    "connaissances",
-    "connectedness",
-    "connectionless",
-    "connoisseur",
-    "connoisseurs",
-    "connoisseurship",
-    "conor",
-    "cons",
-    "conscientiousness",
-    "consciousness",
-    "consciousnesses",
-    "consignee",
-    "consignees",
-    "consultee",
-    "contessa",
-    "coo",
-    "cooch",
-    "cooed",
-    "coogan",
-    "cooh",
-    "cooing",
-    "cook",
-    "cookbook",
-    "cookbooks",
-    "cooke",
-    "cooked",
-    "cooker",
-    "cookers",
-    "cookery",
-    "cookhouse",
-    "cookie",
-    "cookies",
-    "cooking",
-    "cooks",
-    "cookson",
-    "cookware",
-    "cool",
-    "coolant",
-    "coole",
-    "cooled",
-    "cooler",
-    "coolers",
-    "coolest",
-    "cooley",
-    "coolidge",
-    "coolie",
-    "coolies",
-    "cooling",
-    "coolly",
-    "coolness",
-    "cools",
-    "coomaraswamy",
-    "coomassie",
-    "coombe",
-    "coombes",
-    "coombs",
-    "coon",
-    "cooney",
-    "coons",
-    "coop",
-    "cooped",
-    "cooper",
-    "cooperate",
-    "cooperated",
-    "cooperates",
-    "cooperating",
-    "cooperation",
-    "cooperative",
-    "cooperatively",
-    "cooperativeness",
-    "cooperatives",
-    "cooperativity",
-    "cooperators",
-    "coopers",
-    "cooperstown",
-    "coops",
-    "cooptation",
-    "coopted",
-    "coord",
-    "coordinate",
-    "coordinated",
-    "coordinates",
-    "coordinating",
-    "coordination",
-    "coordinations",
-    "coordinative",
-    "coordinator",
-    "coordinators",
-    "coorg",
-    "coors",
-    "coos",
-    "coosa",
-    "coot",
-    "coote",
-    "coots",
-    "coover",
-    "coprocessor",
-    "cops",
-    "cora",
-    "coram",
-    "cordless",
-    "cori",
-    "corliss",
-    "corm",
-    "corms",
-    "corn",
-    "corns",
-    "coro",
-    "corr",
-    "corrado",
-    "corral",
-    "corralled",
-    "corrals",
-    "corre",
-    "correa",
-    "correcdy",
-    "correct",
-    "correctable",
-    "corrected",
-    "correcting",
-    "correction",
-    "correctional",
-    "corrections",
-    "corrective",
-    "correctives",
-    "correctly",
-    "correctness",
-    "corrector",
-    "corrects",
-    "correggio",
-    "corregidor",
-    "correia",
-    "correlate",
-    "correlated",
-    "correlates",
-    "correlating",
-    "correlation",
-    "correlational",
-    "correlations",
-    "correlative",
-    "correlatives",
-    "correlator",
-    "correll",
-    "correo",
-    "corresp",
-    "correspond",
-    "correspondance",
-    "correspondant",
-    "corresponded",
-    "correspondence",
-    "correspondences",
-    "correspondent",
-    "correspondents",
-    "corresponding",
-    "correspondingly",
-    "corresponds",
-    "corrida",
-    "corrido",
-    "corridor",
-    "corridors",
-    "corrie",
-    "corrientes",
-    "corriere",
-    "corrigan",
-    "corrine",
-    "corroborate",
-    "corroborated",
-    "corroborates",
-    "corroborating",
-    "corroboration",
-    "corroborative",
-    "corrode",
-    "corroded",
-    "corrodes",
-    "corroding",
-    "corrosion",
-    "corrosive",
-    "corrugated",
-    "corrugation",
-    "corrugations",
-    "corrupt",
-    "corrupted",
-    "corruptible",
-    "corrupting",
-    "corruption",
-    "corruptions",
-    "corruptly",
-    "corrupts",
-    "corry",
-    "corvee",
-    "cosa",
-    "cosas",
-    "cosi",
-    "cosmo",
-    "cosmos",
-    "coso",
-    "coss",
-    "cossack",
-    "cossacks",
-    "cost",
-    "costeffective",
-    "costless",
-    "costliness",
-    "costo",
-    "costs",
-    "cots",
-    "cottonseed",
-    "cottonwood",
-    "cottonwoods",
-    "coulee",
-    "counselee",
-    "countee",
-    "countercurrent",
-    "counteroffensive",
-    "counteroffer",
-    "counterrevolution",
-    "counterrevolutionaries",
-    "counterrevolutionary",
-    "counterterrorism",
-    "countess",
-    "countless",
-    "courrier",
-    "courtroom",
-    "courtrooms",
-    "covarrubias",
-    "covetousness",
-    "cowan",
-    "cowperwood",
-    "cowpox",
-    "cows",
-    "coxa",
-    "coyness",
-    "crabgrass",
-    "crabtree",
-    "craddock",
-    "craftiness",
-    "cranberries",
-    "cranberry",
-    "cranbrook",
-    "crass",
-    "crassa",
-    "crassus",
-    "craziness",
-    "creativeness",
-    "creditworthiness",
-    "cree",
-    "creech",
-    "creed",
-    "creedal",
-    "creeds",
-    "creek",
-    "creeks",
-    "creel",
-    "creeley",
-    "creep",
-    "creeper",
-    "creepers",
-    "creeping",
-    "creeps",
-    "creepy",
-    "creer",
-    "crees",
-    "cress",
-    "cressey",
-    "cressida",
-    "cresson",
-    "cresswell",
-    "cressy",
-    "crestwood",
-    "crevasse",
-    "crevasses",
-    "crispness",
-    "criss",
-    "crisscross",
-    "crisscrossed",
-    "crisscrossing",
-    "croissance",
-    "croissant",
-    "croissants",
-    "crook",
-    "crooke",
-    "crooked",
-    "crookedly",
-    "crookes",
-    "crooks",
-    "croom",
-    "croon",
-    "crooned",
-    "crooning",
-    "cross",
-    "crossan",
-    "crossbar",
-    "crossborder",
-    "crossbow",
-    "crossbows",
-    "crossbred",
-    "crossbreeding",
-    "crosscountry",
-    "crosscultural",
-    "crosscurrents",
-    "crosscut",
-    "crosscutting",
-    "crosse",
-    "crossed",
-    "crosses",
-    "crossexamination",
-    "crossfire",
-    "crossflow",
-    "crosshairs",
-    "crosshead",
-    "crossing",
-    "crossings",
-    "crossley",
-    "crosslink",
-    "crosslinked",
-    "crosslinking",
-    "crosslinks",
-    "crossly",
-    "crossman",
-    "crossover",
-    "crossovers",
-    "crossroad",
-    "crossroads",
-    "crosssection",
-    "crosssectional",
-    "crosstalk",
-    "crossways",
-    "crosswind",
-    "crosswise",
-    "crossword",
-    "crowfoot",
-    "crr",
-    "crudeness",
-    "css",
-    "cssr",
-    "cuckoo",
-    "cuckoos",
-    "cuddapah",
-    "cuddle",
-    "cuddled",
-    "cuddling",
-    "cuddly",
-    "cuddy",
-    "cues",
-    "cuff",
-    "cuffed",
-    "cuffs",
-    "cuirass",
-    "cunliffe",
-    "cups",
-    "curr",
-    "curragh",
-    "curran",
-    "currant",
-    "currants",
-    "currencies",
-    "currency",
-    "currendy",
-    "current",
-    "currently",
-    "currents",
-    "currey",
-    "curricula",
-    "curricular",
-    "curriculum",
-    "curriculums",
-    "currie",
-    "curried",
-    "currier",
-    "curries",
-    "curry",
-    "curs",
-    "curtiss",
-    "curwen",
-    "cusa",
-    "cusp",
-    "cusps",
-    "cuss",
-    "cussed",
-    "cussing",
-    "cust",
-    "cutlass",
-    "cutlasses",
-    "cutoff",
-    "cutoffs",
-    "cuts",
-    "cyclooxygenase",
-    "cypress",
-    "cypresses",
-    "daa",
-    "daan",
-    "dacha",
-    "dacs",
-    "dada",
-    "daddies",
-    "daddy",
-    "dade",
-    "dado",
-    "dads",
-    "daewoo",
-    "daffodil",
-    "daffodils",
-    "daffy",
-    "dafi",
-    "dafydd",
-    "daguerre",
-    "daguerreotype",
-    "daguerreotypes",
-    "dais",
-    "dama",
-    "damas",
-    "dampness",
-    "dams",
-    "dana",
-    "danda",
-    "dandruff",
-    "dangerousness",
-    "dans",
-    "dara",
-    "darauf",
-    "daraus",
-    "dard",
-    "darf",
-    "dari",
-    "darjeeling",
-    "darkness",
-    "darkroom",
-    "darn",
-    "daro",
-    "darrel",
-    "darrell",
-    "darren",
-    "darrin",
-    "darrow",
-    "darryl",
-    "dartmoor",
-    "daru",
-    "darya",
-    "dasa",
-    "dashwood",
-    "dass",
-    "datasheet",
-    "daud",
-    "dauntless",
-    "davidoff",
-    "dawn",
-    "dawns",
-    "daws",
-    "days",
-    "dbmss",
-    "dd",
-    "dda",
-    "ddd",
-    "dde",
-    "ddi",
-    "ddl",
-    "ddp",
-    "ddr",
-    "dds",
-    "ddt",
-    "deaconess",
-    "deaconesses",
-    "deadness",
-    "deadwood",
-    "deafferentation",
-    "deafness",
-    "dearness",
-    "deathless",
-    "debarred",
-    "debussy",
-    "decaffeinated",
-    "deceitfulness",
-    "decisiveness",
-    "declassified",
-    "decommissioned",
-    "decommissioning",
-    "decompress",
-    "decompressed",
-    "decompression",
-    "decree",
-    "decreed",
-    "decreeing",
-    "decrees",
-    "decussation",
-    "dede",
-    "dedifferentiation",
-    "deduce",
-    "deduces",
-    "dee",
-    "deed",
-    "deeded",
-    "deedee",
-    "deeds",
-    "deegan",
-    "deel",
-    "deem",
-    "deemed",
-    "deeming",
-    "deemphasize",
-    "deemphasized",
-    "deems",
-    "deen",
-    "deena",
-    "deep",
-    "deepak",
-    "deepen",
-    "deepened",
-    "deepening",
-    "deepens",
-    "deeper",
-    "deepest",
-    "deeply",
-    "deeps",
-    "deepseated",
-    "deepwater",
-    "deer",
-    "deere",
-    "deerfield",
-    "deering",
-    "deerskin",
-    "deerslayer",
-    "dees",
-    "defectiveness",
-    "defenceless",
-    "defenseless",
-    "defensiveness",
-    "deferral",
-    "deferrals",
-    "deferred",
-    "deferring",
-    "definiteness",
-    "defmed",
-    "deftness",
-    "defuse",
-    "defused",
-    "degassed",
-    "degassing",
-    "degree",
-    "degrees",
-    "degroot",
-    "delcasse",
-    "delicatessen",
-    "deme",
-    "demers",
-    "demes",
-    "demurrage",
-    "demurred",
-    "demurrer",
-    "dene",
-    "denr",
-    "dens",
-    "dense",
-    "denser",
-    "deportees",
-    "depress",
-    "depressant",
-    "depressants",
-    "depressed",
-    "depresses",
-    "depressing",
-    "depressingly",
-    "depression",
-    "depressions",
-    "depressive",
-    "depressives",
-    "depressor",
-    "dere",
-    "dered",
-    "derm",
-    "dern",
-    "derrick",
-    "derricks",
-    "derrida",
-    "derridean",
-    "derriere",
-    "derringer",
-    "derry",
-    "ders",
-    "desarrollo",
-    "desde",
-    "dese",
-    "designee",
-    "desiree",
-    "dess",
-    "dessa",
-    "dessalines",
-    "dessau",
-    "dessen",
-    "dessert",
-    "desserts",
-    "dessin",
-    "dessins",
-    "dessous",
-    "dessus",
-    "destructiveness",
-    "detainee",
-    "detainees",
-    "determinateness",
-    "deterred",
-    "deterrence",
-    "deterrent",
-    "deterrents",
-    "deterring",
-    "deviousness",
-    "devisee",
-    "devotee",
-    "devotees",
-    "dfee",
-    "dhss",
-    "diana",
-    "diarrhea",
-    "diarrheal",
-    "diarrhoea",
-    "diarrhoeal",
-    "dias",
-    "diddle",
-    "didn",
-    "dido",
-    "didst",
-    "died",
-    "diene",
-    "diener",
-    "dienes",
-    "dies",
-    "diese",
-    "diesem",
-    "diesen",
-    "dieser",
-    "dieses",
-    "diets",
-    "diff",
-    "differ",
-    "differance",
-    "differed",
-    "difference",
-    "differences",
-    "differencing",
-    "differendy",
-    "different",
-    "differentes",
-    "differentia",
-    "differentiable",
-    "differential",
-    "differentially",
-    "differentials",
-    "differentiate",
-    "differentiated",
-    "differentiates",
-    "differentiating",
-    "differentiation",
-    "differentiations",
-    "differentiator",
-    "differently",
-    "differents",
-    "differing",
-    "differs",
-    "diffi",
-    "difficile",
-    "difficult",
-    "difficulties",
-    "difficulty",
-    "diffidence",
-    "diffident",
-    "diffidently",
-    "diffie",
-    "diffracted",
-    "diffraction",
-    "diffractive",
-    "diffractometer",
-    "diffuse",
-    "diffused",
-    "diffusely",
-    "diffuseness",
-    "diffuser",
-    "diffusers",
-    "diffuses",
-    "diffusible",
-    "diffusing",
-    "diffusion",
-    "diffusional",
-    "diffusive",
-    "diffusivities",
-    "diffusivity",
-    "diglossia",
-    "digress",
-    "digression",
-    "digressions",
-    "diisseldorf",
-    "dijk",
-    "dimensionless",
-    "dimness",
-    "diningroom",
-    "dinwiddie",
-    "dips",
-    "directedness",
-    "directionless",
-    "directness",
-    "disaffected",
-    "disaffection",
-    "disagree",
-    "disagreeable",
-    "disagreeably",
-    "disagreed",
-    "disagreeing",
-    "disagreement",
-    "disagreements",
-    "disagrees",
-    "disarranged",
-    "disarray",
-    "disassemble",
-    "disassembled",
-    "disassembling",
-    "disassembly",
-    "disassociate",
-    "disassociated",
-    "disassociation",
-    "disbarred",
-    "disc",
-    "discreet",
-    "discreetly",
-    "discreteness",
-    "discs",
-    "discuss",
-    "discussant",
-    "discussants",
-    "discussed",
-    "discusses",
-    "discussing",
-    "discussion",
-    "discussions",
-    "disinterestedness",
-    "disinterred",
-    "diskussion",
-    "dismiss",
-    "dismissal",
-    "dismissals",
-    "dismissed",
-    "dismisses",
-    "dismissing",
-    "dismissive",
-    "dismissively",
-    "disp",
-    "dispassion",
-    "dispassionate",
-    "dispassionately",
-    "dispossess",
-    "dispossessed",
-    "dispossession",
-    "disproof",
-    "diss",
-    "dissatisfaction",
-    "dissatisfactions",
-    "dissatisfied",
-    "disse",
-    "dissect",
-    "dissected",
-    "dissecting",
-    "dissection",
-    "dissections",
-    "dissects",
-    "dissemble",
-    "dissembling",
-    "disseminate",
-    "disseminated",
-    "disseminates",
-    "disseminating",
-    "dissemination",
-    "dissension",
-    "dissensions",
-    "dissent",
-    "dissented",
-    "dissenter",
-    "dissenters",
-    "dissenting",
-    "dissents",
-    "dissertation",
-    "dissertations",
-    "disservice",
-    "dissidence",
-    "dissident",
-    "dissidents",
-    "dissimilar",
-    "dissimilarities",
-    "dissimilarity",
-    "dissimulation",
-    "dissipate",
-    "dissipated",
-    "dissipates",
-    "dissipating",
-    "dissipation",
-    "dissipative",
-    "dissociate",
-    "dissociated",
-    "dissociates",
-    "dissociating",
-    "dissociation",
-    "dissociations",
-    "dissociative",
-    "dissolute",
-    "dissolution",
-    "dissolutions",
-    "dissolve",
-    "dissolved",
-    "dissolves",
-    "dissolving",
-    "dissonance",
-    "dissonances",
-    "dissonant",
-    "dissuade",
-    "dissuaded",
-    "dissuading",
-    "dist",
-    "distaff",
-    "distinctiveness",
-    "distinctness",
-    "distress",
-    "distressed",
-    "distresses",
-    "distressful",
-    "distressing",
-    "distressingly",
-    "distributee",
-    "divisiveness",
-    "divorcee",
-    "divorcees",
-    "dizziness",
-    "docs",
-    "dodd",
-    "doddridge",
-    "dodds",
-    "dodo",
-    "dodson",
-    "doerr",
-    "doers",
-    "doesn",
-    "doest",
-    "doffed",
-    "dogwood",
-    "dois",
-    "dojo",
-    "dollfuss",
-    "domineering",
-    "domo",
-    "doms",
-    "donee",
-    "doneness",
-    "donnees",
-    "dono",
-    "donor",
-    "donors",
-    "dons",
-    "doo",
-    "doob",
-    "doodle",
-    "doodles",
-    "doodling",
-    "doody",
-    "dooley",
-    "doolittle",
-    "doom",
-    "doomed",
-    "dooms",
-    "doomsday",
-    "doon",
-    "door",
-    "doorbell",
-    "doordarshan",
-    "doorframe",
-    "doorjamb",
-    "doorkeeper",
-    "doorknob",
-    "doorman",
-    "doormat",
-    "doormen",
-    "doors",
-    "doorstep",
-    "doorsteps",
-    "doorway",
-    "doorways",
-    "dooryard",
-    "dora",
-    "dorado",
-    "doran",
-    "doreen",
-    "dorf",
-    "dori",
-    "doris",
-    "dorm",
-    "dorms",
-    "dorn",
-    "dorr",
-    "dorrance",
-    "dorrie",
-    "dorris",
-    "dorrit",
-    "dosa",
-    "dosed",
-    "dosi",
-    "doss",
-    "dossier",
-    "dossiers",
-    "dost",
-    "dots",
-    "doubleness",
-    "doubtless",
-    "doubtlessly",
-    "douglass",
-    "dowd",
-    "down",
-    "downs",
-    "downtrodden",
-    "dowson",
-    "doxa",
-    "drabness",
-    "draftees",
-    "dragoon",
-    "dragoons",
-    "drawee",
-    "drawingroom",
-    "dreamless",
-    "dreariness",
-    "dress",
-    "dressage",
-    "dressed",
-    "dresser",
-    "dressers",
-    "dresses",
-    "dressier",
-    "dressing",
-    "dressings",
-    "dressler",
-    "dressmaker",
-    "dressmakers",
-    "dressmaking",
-    "dressy",
-    "dreyfuss",
-    "driftwood",
-    "drood",
-    "drool",
-    "drooling",
-    "droop",
-    "drooped",
-    "drooping",
-    "droops",
-    "droopy",
-    "dross",
-    "drowsiness",
-    "drunkenness",
-    "dryness",
-    "dss",
-    "duas",
-    "dubuffet",
-    "ducasse",
-    "duchess",
-    "duchesse",
-    "duchesses",
-    "duda",
-    "dude",
-    "dudek",
-    "dudes",
-    "duds",
-    "duer",
-    "dues",
-    "duets",
-    "dufay",
-    "duff",
-    "duffel",
-    "dufferin",
-    "duffield",
-    "duffle",
-    "duffy",
-    "dullness",
-    "dulness",
-    "dundee",
-    "dungarees",
-    "dungeness",
-    "duns",
-    "duos",
-    "duplessis",
-    "dupree",
-    "dura",
-    "duran",
-    "durand",
-    "duras",
-    "duree",
-    "duress",
-    "durfee",
-    "duro",
-    "durr",
-    "durrani",
-    "durrant",
-    "durrell",
-    "durst",
-    "dury",
-    "dusan",
-    "dussel",
-    "dusseldorf",
-    "dust",
-    "dusts",
-    "dutchess",
-    "duveen",
-    "dwarf",
-    "dwarfs",
-    "dword",
-    "dwyer",
-    "dyed",
-    "dyer",
-    "dyers",
-    "dyes",
-    "dyestuff",
-    "dyestuffs",
-    "dysmenorrhea",
-    "eaa",
-    "eagerness",
-    "earnestness",
-    "earring",
-    "earrings",
-    "easiness",
-    "easterbrook",
-    "eastwood",
-    "ebook",
-    "ebooks",
-    "echeverria",
-    "echr",
-    "edd",
-    "edda",
-    "eddie",
-    "eddies",
-    "eddington",
-    "eddy",
-    "eddying",
-    "edessa",
-    "edgewood",
-    "ee",
-    "eea",
-    "eec",
-    "eed",
-    "eee",
-    "eeg",
-    "eegs",
-    "eek",
-    "eel",
-    "eells",
-    "eels",
-    "een",
-    "eeo",
-    "eeoc",
-    "eep",
-    "eeprom",
-    "eer",
-    "eerdmans",
-    "eerie",
-    "eerily",
-    "eero",
-    "eerste",
-    "ees",
-    "eesti",
-    "eet",
-    "eets",
-    "eeuw",
-    "eez",
-    "eff",
-    "efface",
-    "effaced",
-    "effacement",
-    "effacing",
-    "effec",
-    "effect",
-    "effected",
-    "effecting",
-    "effective",
-    "effectively",
-    "effectiveness",
-    "effectives",
-    "effectivity",
-    "effector",
-    "effectors",
-    "effects",
-    "effectual",
-    "effectually",
-    "effectuate",
-    "effectuated",
-    "effectuation",
-    "effeminacy",
-    "effeminate",
-    "effendi",
-    "efferent",
-    "efferents",
-    "effervescence",
-    "effervescent",
-    "effet",
-    "effete",
-    "effets",
-    "effi",
-    "efficacious",
-    "efficacy",
-    "efficiencies",
-    "efficiency",
-    "efficient",
-    "efficiently",
-    "effie",
-    "effigies",
-    "effigy",
-    "effingham",
-    "efflorescence",
-    "effluent",
-    "effluents",
-    "effluvia",
-    "efflux",
-    "effort",
-    "effortful",
-    "effortless",
-    "effortlessly",
-    "efforts",
-    "effrontery",
-    "effulgence",
-    "effusion",
-    "effusions",
-    "effusive",
-    "effusively",
-    "egress",
-    "ehrr",
-    "eichengreen",
-    "eiffel",
-    "eighteen",
-    "eighteenth",
-    "eighteenthcentury",
-    "eileen",
-    "einfluss",
-    "elderberry",
-    "electioneering",
-    "electroless",
-    "elissa",
-    "ellwood",
-    "elmwood",
-    "elusiveness",
-    "elwood",
-    "elysee",
-    "elysees",
-    "embarrass",
-    "embarrassed",
-    "embarrasses",
-    "embarrassing",
-    "embarrassingly",
-    "embarrassment",
-    "embarrassments",
-    "embassies",
-    "embassy",
-    "embedded",
-    "embeddedness",
-    "embedding",
-    "embossed",
-    "embossing",
-    "embree",
-    "emcee",
-    "emer",
-    "emissaries",
-    "emissary",
-    "emission",
-    "emissions",
-    "emissive",
-    "emissivity",
-    "emotionless",
-    "employee",
-    "employees",
-    "empress",
-    "emptiness",
-    "enchantress",
-    "encompass",
-    "encompassed",
-    "encompasses",
-    "encompassing",
-    "endless",
-    "endlessly",
-    "enfeebled",
-    "enfer",
-    "engineer",
-    "engineered",
-    "engineering",
-    "engineers",
-    "englewood",
-    "englishness",
-    "engr",
-    "engross",
-    "engrossed",
-    "engrossing",
-    "engrs",
-    "enrollee",
-    "enrollees",
-    "entree",
-    "entrees",
-    "epizootic",
-    "equerry",
-    "erec",
-    "eres",
-    "ergebnisse",
-    "ericsson",
-    "eriksson",
-    "erne",
-    "errand",
-    "errands",
-    "errant",
-    "errata",
-    "erratic",
-    "erratically",
-    "erratum",
-    "erred",
-    "erreicht",
-    "erreur",
-    "erring",
-    "errington",
-    "errol",
-    "erroneous",
-    "erroneously",
-    "error",
-    "errors",
-    "errs",
-    "erst",
-    "erster",
-    "escapee",
-    "escapees",
-    "esch",
-    "escher",
-    "espresso",
-    "esquisse",
-    "ess",
-    "essa",
-    "essai",
-    "essais",
-    "essay",
-    "essayed",
-    "essayist",
-    "essayists",
-    "essays",
-    "esse",
-    "essen",
-    "essence",
-    "essences",
-    "essene",
-    "essenes",
-    "essent",
-    "essentia",
-    "essential",
-    "essentialism",
-    "essentialist",
-    "essentiality",
-    "essentially",
-    "essentials",
-    "essentiel",
-    "essentiellement",
-    "esser",
-    "essere",
-    "esset",
-    "essex",
-    "essi",
-    "essie",
-    "esso",
-    "estabrook",
-    "esteem",
-    "esteemed",
-    "esteems",
-    "ester",
-    "esters",
-    "estes",
-    "ests",
-    "etfs",
-    "euer",
-    "eure",
-    "eurocurrency",
-    "europeen",
-    "europeenne",
-    "evacuee",
-    "evacuees",
-    "evasiveness",
-    "evenness",
-    "evergreen",
-    "evergreens",
-    "exactness",
-    "examinee",
-    "examinees",
-    "exceed",
-    "exceeded",
-    "exceeding",
-    "exceedingly",
-    "exceeds",
-    "excess",
-    "excesses",
-    "excessive",
-    "excessively",
-    "exclusiveness",
-    "exec",
-    "execs",
-    "exerc",
-    "exert",
-    "exmoor",
-    "exner",
-    "expansiveness",
-    "expellees",
-    "expertness",
-    "explicitness",
-    "express",
-    "expressed",
-    "expresses",
-    "expressible",
-    "expressing",
-    "expression",
-    "expressionism",
-    "expressionist",
-    "expressionistic",
-    "expressionists",
-    "expressionless",
-    "expressions",
-    "expressive",
-    "expressively",
-    "expressiveness",
-    "expressivity",
-    "expressly",
-    "expressway",
-    "expressways",
-    "extensiveness",
-    "extracurricular",
-    "extraterrestrial",
-    "extraterrestrials",
-    "extraterritorial",
-    "extraterritoriality",
-    "exxon",
-    "eyeglass",
-    "eyeglasses",
-    "eyeless",
-    "eyewitness",
-    "eyewitnesses",
-    "faa",
-    "faceless",
-    "factbook",
-    "faintness",
-    "fairness",
-    "faithfulness",
-    "faithless",
-    "faithlessness",
-    "falloff",
-    "falsehood",
-    "falsehoods",
-    "falseness",
-    "falstaff",
-    "farooq",
-    "farr",
-    "farrago",
-    "farragut",
-    "farrakhan",
-    "farrand",
-    "farrar",
-    "farre",
-    "farreaching",
-    "farrell",
-    "farrer",
-    "farrier",
-    "farrington",
-    "farris",
-    "farrow",
-    "farrowing",
-    "farsightedness",
-    "fassbinder",
-    "fastidiousness",
-    "fastness",
-    "fastnesses",
-    "fatherhood",
-    "fatherless",
-    "fathomless",
-    "fatness",
-    "faultless",
-    "fdd",
-    "fddi",
-    "fearfulness",
-    "fearless",
-    "fearlessly",
-    "fearlessness",
-    "featureless",
-    "feckless",
-    "fedayeen",
-    "feddans",
-    "fee",
-    "feeble",
-    "feebleminded",
-    "feebleness",
-    "feebler",
-    "feebly",
-    "feed",
-    "feedback",
-    "feedbacks",
-    "feeder",
-    "feeders",
-    "feedforward",
-    "feeding",
-    "feedings",
-    "feedlot",
-    "feedlots",
-    "feeds",
-    "feedstock",
-    "feedstocks",
-    "feedstuffs",
-    "feedwater",
-    "feel",
-    "feeler",
-    "feelers",
-    "feeley",
-    "feelin",
-    "feeling",
-    "feelingly",
-    "feelings",
-    "feels",
-    "feely",
-    "feeney",
-    "feer",
-    "fees",
-    "feet",
-    "femaleness",
-    "fenugreek",
-    "fergusson",
-    "ferrand",
-    "ferrante",
-    "ferranti",
-    "ferrar",
-    "ferrara",
-    "ferrari",
-    "ferraro",
-    "ferre",
-    "ferred",
-    "ferredoxin",
-    "ferreira",
-    "ferrell",
-    "ferrer",
-    "ferrero",
-    "ferrers",
-    "ferret",
-    "ferreting",
-    "ferrets",
-    "ferretti",
-    "ferri",
-    "ferric",
-    "ferricyanide",
-    "ferried",
-    "ferrier",
-    "ferries",
-    "ferris",
-    "ferrite",
-    "ferrites",
-    "ferritic",
-    "ferritin",
-    "ferro",
-    "ferrocyanide",
-    "ferroelectric",
-    "ferroelectrics",
-    "ferromagnetic",
-    "ferromagnetism",
-    "ferrous",
-    "ferruginous",
-    "ferrule",
-    "ferry",
-    "ferryboat",
-    "ferrying",
-    "ferryman",
-    "fess",
-    "fessenden",
-    "festooned",
-    "festoons",
-    "fetes",
-    "ff",
-    "ffa",
-    "fff",
-    "ffi",
-    "ffl",
-    "ffp",
-    "ffs",
-    "fft",
-    "fiancee",
-    "fiberglass",
-    "fibreglass",
-    "fickleness",
-    "fiddle",
-    "fiddled",
-    "fiddler",
-    "fiddlers",
-    "fiddles",
-    "fiddling",
-    "fierceness",
-    "fierro",
-    "fifteen",
-    "fifteenth",
-    "filigree",
-    "fineness",
-    "finesse",
-    "finiteness",
-    "fireproof",
-    "firewood",
-    "firmness",
-    "firstclass",
-    "fishhook",
-    "fissile",
-    "fission",
-    "fissionable",
-    "fissure",
-    "fissured",
-    "fissures",
-    "fitness",
-    "flagstaff",
-    "flamsteed",
-    "flatness",
-    "flawless",
-    "flawlessly",
-    "flaxseed",
-    "flee",
-    "fleece",
-    "fleeced",
-    "fleeces",
-    "fleecy",
-    "fleeing",
-    "flees",
-    "fleet",
-    "fleeting",
-    "fleetingly",
-    "fleets",
-    "fleetwood",
-    "fliess",
-    "flightless",
-    "flood",
-    "flooded",
-    "floodgates",
-    "flooding",
-    "floodlights",
-    "floodplain",
-    "floodplains",
-    "floods",
-    "floodwater",
-    "floodwaters",
-    "floor",
-    "floorboard",
-    "floorboards",
-    "floored",
-    "flooring",
-    "floors",
-    "florrie",
-    "floss",
-    "flossie",
-    "flowsheet",
-    "fludd",
-    "fluff",
-    "fluffed",
-    "fluffy",
-    "flurries",
-    "flurry",
-    "flywheel",
-    "focussed",
-    "focusses",
-    "focussing",
-    "fodder",
-    "fondness",
-    "foo",
-    "foochow",
-    "food",
-    "foodborne",
-    "foodgrain",
-    "foodgrains",
-    "foods",
-    "foodservice",
-    "foodstuff",
-    "foodstuffs",
-    "fool",
-    "fooled",
-    "foolhardy",
-    "fooling",
-    "foolish",
-    "foolishly",
-    "foolishness",
-    "foolproof",
-    "fools",
-    "foolscap",
-    "foot",
-    "footage",
-    "football",
-    "footballer",
-    "footballers",
-    "footballs",
-    "footbridge",
-    "foote",
-    "footed",
-    "footer",
-    "footers",
-    "footfall",
-    "footfalls",
-    "foothill",
-    "foothills",
-    "foothold",
-    "footholds",
-    "footing",
-    "footings",
-    "footlights",
-    "footloose",
-    "footman",
-    "footmen",
-    "footnote",
-    "footnotes",
-    "footpath",
-    "footpaths",
-    "footplate",
-    "footprint",
-    "footprints",
-    "footstep",
-    "footsteps",
-    "footstool",
-    "footwall",
-    "footwear",
-    "footwork",
-    "forbidden",
-    "forbidding",
-    "forcefulness",
-    "foredoomed",
-    "forefoot",
-    "foreignness",
-    "forenoon",
-    "foresee",
-    "foreseeability",
-    "foreseeable",
-    "foreseeing",
-    "foreseen",
-    "foresees",
-    "forgetfulness",
-    "forgiveness",
-    "formedness",
-    "formless",
-    "formlessness",
-    "forrest",
-    "forrestal",
-    "forrester",
-    "forsook",
-    "forsooth",
-    "forthrightness",
-    "fortissimo",
-    "fortress",
-    "fortresses",
-    "forwardness",
-    "foss",
-    "fossa",
-    "fossae",
-    "fosse",
-    "fossil",
-    "fossiliferous",
-    "fossilized",
-    "fossils",
-    "foulness",
-    "foundress",
-    "fourteen",
-    "fourteenth",
-    "foxx",
-    "fraassen",
-    "franchisee",
-    "franchisees",
-    "frankness",
-    "freddie",
-    "freddy",
-    "free",
-    "freeboard",
-    "freebooters",
-    "freeborn",
-    "freebsd",
-    "freed",
-    "freedman",
-    "freedmen",
-    "freedom",
-    "freedoms",
-    "freeform",
-    "freehand",
-    "freehold",
-    "freeholder",
-    "freeholders",
-    "freeing",
-    "freelance",
-    "freelancers",
-    "freeland",
-    "freely",
-    "freeman",
-    "freemason",
-    "freemasonry",
-    "freemasons",
-    "freemen",
-    "freeport",
-    "freer",
-    "frees",
-    "freese",
-    "freest",
-    "freestanding",
-    "freestone",
-    "freestyle",
-    "freethinker",
-    "freethinkers",
-    "freethinking",
-    "freetown",
-    "freeware",
-    "freeway",
-    "freeways",
-    "freewheeling",
-    "freewill",
-    "freewriting",
-    "freeze",
-    "freezer",
-    "freezers",
-    "freezes",
-    "freezing",
-    "freiherr",
-    "freshness",
-    "frictionless",
-    "friendless",
-    "friendliness",
-    "frisbee",
-    "frisson",
-    "froissart",
-    "fruitfulness",
-    "fruitless",
-    "fruitlessly",
-    "fss",
-    "ftaa",
-    "fuelwood",
-    "fuisse",
-    "fullness",
-    "fulness",
-    "furness",
-    "furniss",
-    "furred",
-    "furring",
-    "furrow",
-    "furrowed",
-    "furrows",
-    "furry",
-    "fuss",
-    "fussed",
-    "fussell",
-    "fussing",
-    "fussy",
-    "fuzziness",
-    "gaa",
-    "gaap",
-    "gaas",
-    "gabaa",
-    "gaddi",
-    "gaddis",
-    "gaff",
-    "gaffe",
-    "gaffer",
-    "gaffney",
-    "galactorrhea",
-    "galilee",
-    "gallbladder",
-    "gama",
-    "gamekeeper",
-    "gams",
-    "gana",
-    "ganga",
-    "gangs",
-    "ganj",
-    "ganja",
-    "gans",
-    "gansevoort",
-    "gaor",
-    "gaps",
-    "gara",
-    "garay",
-    "gari",
-    "garn",
-    "garnishee",
-    "garo",
-    "garrard",
-    "garratt",
-    "garret",
-    "garrett",
-    "garrick",
-    "garrido",
-    "garrison",
-    "garrisoned",
-    "garrisons",
-    "garrity",
-    "garrod",
-    "garrow",
-    "garrulous",
-    "garry",
-    "garwood",
-    "gary",
-    "gasp",
-    "gasps",
-    "gass",
-    "gassed",
-    "gassendi",
-    "gasser",
-    "gasses",
-    "gasset",
-    "gassing",
-    "gassner",
-    "gast",
-    "gatekeeper",
-    "gatekeepers",
-    "gatekeeping",
-    "gatewood",
-    "gats",
-    "gaur",
-    "gauss",
-    "gaussian",
-    "gawk",
-    "gays",
-    "gazetteer",
-    "gazetteers",
-    "geddes",
-    "gee",
-    "geek",
-    "geeks",
-    "geelong",
-    "geen",
-    "geer",
-    "geert",
-    "geertz",
-    "gees",
-    "geese",
-    "geeta",
-    "geez",
-    "geffen",
-    "geissler",
-    "generalissimo",
-    "genesee",
-    "genteel",
-    "gentleness",
-    "genuineness",
-    "geoff",
-    "geoffrey",
-    "geoffroy",
-    "gerrard",
-    "gerrit",
-    "gerry",
-    "gerrymandering",
-    "gessner",
-    "gesso",
-    "gewisse",
-    "gewissen",
-    "ghaffar",
-    "ghee",
-    "giddens",
-    "giddiness",
-    "gidding",
-    "giddings",
-    "giddy",
-    "gierek",
-    "gies",
-    "giese",
-    "giessen",
-    "giffard",
-    "giffen",
-    "gifford",
-    "giftedness",
-    "gins",
-    "giorno",
-    "giraffe",
-    "giraffes",
-    "giri",
-    "girlhood",
-    "gissing",
-    "gist",
-    "gits",
-    "givenness",
-    "gladden",
-    "gladdened",
-    "gladness",
-    "glass",
-    "glasse",
-    "glassed",
-    "glasser",
-    "glasses",
-    "glasshouse",
-    "glassmaking",
-    "glassman",
-    "glassware",
-    "glassworks",
-    "glassy",
-    "glee",
-    "gleeful",
-    "gleefully",
-    "gleeson",
-    "glengarry",
-    "glenwood",
-    "glidden",
-    "glissant",
-    "gloom",
-    "gloomily",
-    "gloomy",
-    "gloss",
-    "glossa",
-    "glossaries",
-    "glossary",
-    "glossed",
-    "glosses",
-    "glossina",
-    "glossing",
-    "glossitis",
-    "glossolalia",
-    "glossopharyngeal",
-    "glossy",
-    "gneiss",
-    "gneisses",
-    "goalkeeper",
-    "goatee",
-    "goats",
-    "goddam",
-    "goddammit",
-    "goddamn",
-    "goddamned",
-    "goddard",
-    "godden",
-    "goddess",
-    "goddesses",
-    "godless",
-    "godliness",
-    "goers",
-    "goest",
-    "goff",
-    "goffman",
-    "gomorrah",
-    "gongs",
-    "gonorrhea",
-    "gonorrhoea",
-    "gonorrhoeae",
-    "goo",
-    "gooch",
-    "good",
-    "goodale",
-    "goodall",
-    "goodby",
-    "goodbye",
-    "goodbyes",
-    "goodchild",
-    "goode",
-    "goodell",
-    "goodenough",
-    "gooders",
-    "goodfellow",
-    "goodhart",
-    "goodhue",
-    "goodies",
-    "goodin",
-    "gooding",
-    "goodlad",
-    "goodlooking",
-    "goodly",
-    "goodman",
-    "goodnatured",
-    "goodness",
-    "goodnight",
-    "goodnow",
-    "goodpasture",
-    "goodrich",
-    "goods",
-    "goodson",
-    "goodspeed",
-    "goodwill",
-    "goodwin",
-    "goodwood",
-    "goody",
-    "goodyear",
-    "gooey",
-    "goof",
-    "goofy",
-    "google",
-    "goold",
-    "goon",
-    "goons",
-    "goose",
-    "gooseberries",
-    "gooseberry",
-    "goossens",
-    "gora",
-    "goran",
-    "goree",
-    "gori",
-    "goro",
-    "gorst",
-    "goss",
-    "gossamer",
-    "gosse",
-    "gosselin",
-    "gossett",
-    "gossip",
-    "gossiped",
-    "gossiping",
-    "gossips",
-    "gossipy",
-    "gossypium",
-    "governess",
-    "governesses",
-    "gowan",
-    "gown",
-    "gowns",
-    "gpss",
-    "graaf",
-    "graaff",
-    "graal",
-    "gracefulness",
-    "graceless",
-    "graciousness",
-    "graff",
-    "graffiti",
-    "grameen",
-    "granddad",
-    "granddaddy",
-    "granddaughter",
-    "granddaughters",
-    "grandee",
-    "grandees",
-    "grantee",
-    "grantees",
-    "grass",
-    "grasse",
-    "grassed",
-    "grasses",
-    "grasset",
-    "grasshopper",
-    "grasshoppers",
-    "grassi",
-    "grassland",
-    "grasslands",
-    "grassmann",
-    "grasso",
-    "grassroots",
-    "grassy",
-    "gratefulness",
-    "grayness",
-    "greatness",
-    "gree",
-    "greece",
-    "greed",
-    "greedily",
-    "greediness",
-    "greedy",
-    "greek",
-    "greeks",
-    "greeley",
-    "greely",
-    "green",
-    "greenacre",
-    "greenaway",
-    "greenback",
-    "greenbacks",
-    "greenbaum",
-    "greenbelt",
-    "greenberg",
-    "greenberger",
-    "greenblatt",
-    "greenbrier",
-    "greene",
-    "greener",
-    "greenery",
-    "greenfield",
-    "greengrocer",
-    "greenhalgh",
-    "greenham",
-    "greenhill",
-    "greenhorn",
-    "greenhouse",
-    "greenhouses",
-    "greening",
-    "greenish",
-    "greenland",
-    "greenlanders",
-    "greenlandic",
-    "greenleaf",
-    "greenlee",
-    "greenness",
-    "greenock",
-    "greenough",
-    "greenpeace",
-    "greens",
-    "greensboro",
-    "greenschist",
-    "greenspan",
-    "greenstein",
-    "greenstone",
-    "greenville",
-    "greenwald",
-    "greenway",
-    "greenwich",
-    "greenwillow",
-    "greenwood",
-    "greer",
-    "greet",
-    "greeted",
-    "greeting",
-    "greetings",
-    "greets",
-    "gregariousness",
-    "gress",
-    "greyness",
-    "gridded",
-    "griddle",
-    "griff",
-    "griffen",
-    "griffin",
-    "griffins",
-    "griffith",
-    "griffiths",
-    "griffon",
-    "grimness",
-    "grissom",
-    "groom",
-    "groomed",
-    "grooming",
-    "grooms",
-    "groot",
-    "groote",
-    "groove",
-    "grooved",
-    "grooves",
-    "grooving",
-    "groovy",
-    "gross",
-    "grossberg",
-    "grosse",
-    "grossed",
-    "grossen",
-    "grosser",
-    "grosses",
-    "grossest",
-    "grosset",
-    "grosseteste",
-    "grossi",
-    "grossing",
-    "grossly",
-    "grossman",
-    "grossmann",
-    "grossness",
-    "grosso",
-    "groundless",
-    "groundmass",
-    "gruff",
-    "gruffly",
-    "grundriss",
-    "grundrisse",
-    "gss",
-    "guar",
-    "guarantee",
-    "guaranteed",
-    "guaranteeing",
-    "guarantees",
-    "guerra",
-    "guerre",
-    "guerrero",
-    "guerres",
-    "guerrilla",
-    "guerrillas",
-    "guess",
-    "guessed",
-    "guesses",
-    "guessing",
-    "guesswork",
-    "guest",
-    "guests",
-    "guffaw",
-    "guffawed",
-    "guffaws",
-    "guidebook",
-    "guidebooks",
-    "guileless",
-    "guiltless",
-    "guinness",
-    "guis",
-    "gujarat",
-    "gums",
-    "guns",
-    "gurdjieff",
-    "gurr",
-    "guru",
-    "gusset",
-    "gussie",
-    "gust",
-    "gustafsson",
-    "gusts",
-    "gutierrez",
-    "guts",
-    "guyer",
-    "guys",
-    "gwynedd",
-    "gyms",
-    "haa",
-    "haack",
-    "haag",
-    "haakon",
-    "haan",
-    "haar",
-    "haarlem",
-    "haart",
-    "haas",
-    "haase",
-    "hackberry",
-    "hadassah",
-    "haddad",
-    "hadde",
-    "hadden",
-    "haddington",
-    "haddock",
-    "haddon",
-    "haemorrhage",
-    "haemorrhages",
-    "haemorrhagic",
-    "haemorrhoids",
-    "haffner",
-    "hafiz",
-    "hair",
-    "hairdresser",
-    "hairdressers",
-    "hairdressing",
-    "hairless",
-    "hairs",
-    "haji",
-    "hajji",
-    "halicarnassus",
-    "halloween",
-    "hama",
-    "hamar",
-    "hamas",
-    "hams",
-    "hana",
-    "handbook",
-    "handbooks",
-    "handcuff",
-    "handcuffed",
-    "handcuffs",
-    "handedness",
-    "handloom",
-    "handoff",
-    "hangar",
-    "hangars",
-    "hangs",
-    "hans",
-    "hansa",
-    "hansberry",
-    "hanssen",
-    "hansson",
-    "hapgood",
-    "hapless",
-    "happiness",
-    "haps",
-    "hara",
-    "harass",
-    "harassed",
-    "harasser",
-    "harassing",
-    "harassment",
-    "hardee",
-    "hardihood",
-    "hardiness",
-    "hardness",
-    "hardwood",
-    "hardwoods",
-    "harewood",
-    "hari",
-    "harkness",
-    "harm",
-    "harmless",
-    "harmlessly",
-    "harms",
-    "harness",
-    "harnessed",
-    "harnesses",
-    "harnessing",
-    "haro",
-    "harpoon",
-    "harpoons",
-    "harrah",
-    "harran",
-    "harrap",
-    "harrassowitz",
-    "harre",
-    "harrell",
-    "harried",
-    "harrier",
-    "harriers",
-    "harries",
-    "harriet",
-    "harriett",
-    "harriette",
-    "harrigan",
-    "harriman",
-    "harrington",
-    "harriot",
-    "harris",
-    "harrisburg",
-    "harrison",
-    "harrisonburg",
-    "harriss",
-    "harrod",
-    "harrods",
-    "harrogate",
-    "harrold",
-    "harrow",
-    "harrowed",
-    "harrowing",
-    "harrows",
-    "harry",
-    "harshness",
-    "hartlepool",
-    "hartree",
-    "harwood",
-    "hasa",
-    "hasp",
-    "hass",
-    "hassall",
-    "hassan",
-    "hasse",
-    "hassell",
-    "hassle",
-    "hassler",
-    "hassles",
-    "hast",
-    "hasta",
-    "hatless",
-    "hats",
-    "haughtiness",
-    "haus",
-    "hausa",
-    "hausdorff",
-    "haussmann",
-    "hawk",
-    "hawks",
-    "hays",
-    "haywood",
-    "hazelwood",
-    "headdress",
-    "headdresses",
-    "headedness",
-    "headless",
-    "headmistress",
-    "headroom",
-    "heartedness",
-    "heartiness",
-    "heartless",
-    "heartwood",
-    "heathcliff",
-    "heaviness",
-    "hedda",
-    "heddle",
-    "hee",
-    "heed",
-    "heeded",
-    "heeding",
-    "heedless",
-    "heedlessly",
-    "heeds",
-    "heeft",
-    "heel",
-    "heeled",
-    "heeling",
-    "heels",
-    "heen",
-    "heer",
-    "heere",
-    "heffer",
-    "heffernan",
-    "hefner",
-    "heiress",
-    "heiresses",
-    "heirloom",
-    "heirlooms",
-    "heiss",
-    "helpee",
-    "helpfulness",
-    "helpless",
-    "helplessly",
-    "helplessness",
-    "heme",
-    "hemorrhage",
-    "hemorrhages",
-    "hemorrhagic",
-    "hemorrhaging",
-    "hemorrhoids",
-    "hennessey",
-    "hennessy",
-    "hens",
-    "hentoff",
-    "hepes",
-    "here",
-    "hereof",
-    "herm",
-    "hermes",
-    "hern",
-    "herne",
-    "hero",
-    "heros",
-    "herr",
-    "herren",
-    "herrera",
-    "herrero",
-    "herrick",
-    "herrin",
-    "herring",
-    "herringbone",
-    "herrings",
-    "herrington",
-    "herriot",
-    "herrmann",
-    "herrn",
-    "herrnstein",
-    "herron",
-    "herrschaft",
-    "hers",
-    "hese",
-    "hess",
-    "hesse",
-    "hessen",
-    "hessian",
-    "hessians",
-    "hest",
-    "hetween",
-    "heuer",
-    "heure",
-    "heures",
-    "heuser",
-    "heywood",
-    "hiaa",
-    "hidden",
-    "hiddenness",
-    "hier",
-    "highness",
-    "highnesses",
-    "highpressure",
-    "highschool",
-    "highspeed",
-    "hindoo",
-    "hindoos",
-    "hipaa",
-    "hips",
-    "hirsch",
-    "hirst",
-    "hiss",
-    "hissar",
-    "hissed",
-    "hisself",
-    "hisses",
-    "hissing",
-    "hist",
-    "hits",
-    "hoar",
-    "hoarseness",
-    "hoary",
-    "hoax",
-    "hodder",
-    "hoff",
-    "hoffa",
-    "hoffer",
-    "hoffman",
-    "hoffmann",
-    "hoffmeister",
-    "hoffnung",
-    "hojo",
-    "holbrook",
-    "holbrooke",
-    "holderness",
-    "holiness",
-    "hollowness",
-    "hollywood",
-    "holyrood",
-    "homeless",
-    "homelessness",
-    "homeroom",
-    "homeschooling",
-    "homesickness",
-    "homewood",
-    "homs",
-    "honeybee",
-    "honeybees",
-    "honeymoon",
-    "honeymooners",
-    "hongo",
-    "honor",
-    "honors",
-    "hons",
-    "hoo",
-    "hooch",
-    "hood",
-    "hooded",
-    "hoodlum",
-    "hoodlums",
-    "hoodoo",
-    "hoods",
-    "hoodwinked",
-    "hoof",
-    "hoofbeats",
-    "hoofed",
-    "hoofs",
-    "hooft",
-    "hooghly",
-    "hook",
-    "hooke",
-    "hooked",
-    "hooker",
-    "hookers",
-    "hooking",
-    "hooks",
-    "hookup",
-    "hookups",
-    "hookworm",
-    "hooligan",
-    "hooliganism",
-    "hooligans",
-    "hoon",
-    "hoop",
-    "hooper",
-    "hoopes",
-    "hoopla",
-    "hoops",
-    "hooray",
-    "hoosier",
-    "hoosiers",
-    "hoot",
-    "hooted",
-    "hooting",
-    "hooton",
-    "hoots",
-    "hoover",
-    "hooves",
-    "hopefulness",
-    "hopeless",
-    "hopelessly",
-    "hopelessness",
-    "hopf",
-    "hops",
-    "hopwood",
-    "hora",
-    "horan",
-    "horas",
-    "horm",
-    "horn",
-    "hornbook",
-    "horns",
-    "horrendous",
-    "horrible",
-    "horribly",
-    "horrid",
-    "horrific",
-    "horrified",
-    "horrifying",
-    "horrocks",
-    "horror",
-    "horrors",
-    "hors",
-    "horseless",
-    "horst",
-    "horwood",
-    "hosp",
-    "hoss",
-    "hossain",
-    "hossein",
-    "host",
-    "hostess",
-    "hostesses",
-    "hosts",
-    "hotchkiss",
-    "hots",
-    "hourglass",
-    "housekeeper",
-    "housekeepers",
-    "housekeeping",
-    "hows",
-    "howson",
-    "hss",
-    "huckleberry",
-    "huddersfield",
-    "huddle",
-    "huddled",
-    "huddleston",
-    "huddling",
-    "hues",
-    "huff",
-    "huffed",
-    "huffing",
-    "huffman",
-    "huis",
-    "hullabaloo",
-    "humaneness",
-    "humanness",
-    "humbleness",
-    "humorless",
-    "hums",
-    "huns",
-    "huntress",
-    "huron",
-    "hurons",
-    "hurrah",
-    "hurrell",
-    "hurricane",
-    "hurricanes",
-    "hurried",
-    "hurriedly",
-    "hurries",
-    "hurry",
-    "hurrying",
-    "hurst",
-    "hurstwood",
-    "husak",
-    "husayn",
-    "huskisson",
-    "huss",
-    "hussain",
-    "hussar",
-    "hussars",
-    "hussein",
-    "husseini",
-    "husserl",
-    "husserlian",
-    "hussey",
-    "hussite",
-    "hussites",
-    "hussy",
-    "huts",
-    "hwnd",
-    "hyperreflexia",
-    "hypoglossal",
-    "hyssop",
-    "iaa",
-    "iarc",
-    "iasc",
-    "ibarra",
-    "iddm",
-    "idee",
-    "ideen",
-    "idees",
-    "idleness",
-    "iee",
-    "ieee",
-    "ietf",
-    "ifrs",
-    "igloo",
-    "ignatieff",
-    "iiss",
-    "illness",
-    "illnesses",
-    "imbedded",
-    "immunoassay",
-    "immunoassays",
-    "immunodiffusion",
-    "immunosuppressed",
-    "immunosuppression",
-    "immunosuppressive",
-    "impassable",
-    "impasse",
-    "impasses",
-    "impassible",
-    "impassioned",
-    "impassive",
-    "impassively",
-    "impermissible",
-    "impermissibly",
-    "impossibilities",
-    "impossibility",
-    "impossible",
-    "impossibly",
-    "impress",
-    "impressed",
-    "impresses",
-    "impressing",
-    "impression",
-    "impressionable",
-    "impressionism",
-    "impressionist",
-    "impressionistic",
-    "impressionists",
-    "impressions",
-    "impressive",
-    "impressively",
-    "impressiveness",
-    "impressment",
-    "impulsiveness",
-    "inaccessibility",
-    "inaccessible",
-    "inadmissibility",
-    "inadmissible",
-    "inappropriateness",
-    "inbetween",
-    "inbreeding",
-    "incessant",
-    "incessantly",
-    "inclusiveness",
-    "incompleteness",
-    "incompressible",
-    "incoordination",
-    "incorrect",
-    "incorrectly",
-    "incorrectness",
-    "incorrigible",
-    "incorruptible",
-    "incurred",
-    "incurring",
-    "indebtedness",
-    "indecisiveness",
-    "indeed",
-    "indefiniteness",
-    "indianness",
-    "indifference",
-    "indifferent",
-    "indifferently",
-    "indirectness",
-    "indiscreet",
-    "indissoluble",
-    "indissolubly",
-    "indoor",
-    "indoors",
-    "industriousness",
-    "ineffable",
-    "ineffective",
-    "ineffectively",
-    "ineffectiveness",
-    "ineffectual",
-    "ineffectually",
-    "inefficacy",
-    "inefficiencies",
-    "inefficiency",
-    "inefficient",
-    "inefficiently",
-    "ineptness",
-    "inerrancy",
-    "inertness",
-    "inessential",
-    "inexpressible",
-    "inexpressibly",
-    "inferred",
-    "inferring",
-    "ingaas",
-    "ingenuousness",
-    "inglewood",
-    "ingress",
-    "innkeeper",
-    "innkeepers",
-    "innovativeness",
-    "inoffensive",
-    "inquisitiveness",
-    "insufferable",
-    "insufficiencies",
-    "insufficiency",
-    "insufficient",
-    "insufficiently",
-    "insufflation",
-    "insurrection",
-    "insurrectionary",
-    "insurrections",
-    "interarrival",
-    "interbedded",
-    "interbreed",
-    "interbreeding",
-    "intercession",
-    "intercessions",
-    "intercessor",
-    "intercessory",
-    "interconnectedness",
-    "intercorrelation",
-    "intercorrelations",
-    "intercurrent",
-    "interdiffusion",
-    "interesse",
-    "intermarriage",
-    "intermarriages",
-    "intermarried",
-    "intermarry",
-    "intermission",
-    "intermissions",
-    "internees",
-    "interoffice",
-    "interossei",
-    "interosseous",
-    "interprocess",
-    "interprofessional",
-    "interracial",
-    "interrater",
-    "interred",
-    "interregional",
-    "interregnum",
-    "interrelate",
-    "interrelated",
-    "interrelatedness",
-    "interrelating",
-    "interrelation",
-    "interrelations",
-    "interrelationship",
-    "interrelationships",
-    "interreligious",
-    "interrogate",
-    "interrogated",
-    "interrogates",
-    "interrogating",
-    "interrogation",
-    "interrogations",
-    "interrogative",
-    "interrogatives",
-    "interrogator",
-    "interrogatories",
-    "interrogators",
-    "interrogatory",
-    "interrupt",
-    "interrupted",
-    "interrupter",
-    "interrupting",
-    "interruption",
-    "interruptions",
-    "interrupts",
-    "interruptus",
-    "interviewee",
-    "interviewees",
-    "intraabdominal",
-    "intraarterial",
-    "intraarticular",
-    "intraclass",
-    "intraosseous",
-    "intrusiveness",
-    "intussusception",
-    "invasiveness",
-    "inventiveness",
-    "inverness",
-    "investee",
-    "invitee",
-    "invitees",
-    "inwardness",
-    "inwood",
-    "ioo",
-    "iooo",
-    "iprs",
-    "irishness",
-    "ironwood",
-    "irr",
-    "irradiance",
-    "irradiate",
-    "irradiated",
-    "irradiating",
-    "irradiation",
-    "irradiations",
-    "irrational",
-    "irrationalism",
-    "irrationality",
-    "irrationally",
-    "irrawaddy",
-    "irreconcilable",
-    "irrecoverable",
-    "irredeemable",
-    "irredeemably",
-    "irredentism",
-    "irredentist",
-    "irreducibility",
-    "irreducible",
-    "irreducibly",
-    "irrefutable",
-    "irregular",
-    "irregularities",
-    "irregularity",
-    "irregularly",
-    "irregulars",
-    "irrelevance",
-    "irrelevancies",
-    "irrelevancy",
-    "irrelevant",
-    "irreligion",
-    "irreligious",
-    "irremediable",
-    "irremediably",
-    "irreparable",
-    "irreparably",
-    "irreplaceable",
-    "irrepressible",
-    "irreproachable",
-    "irresistible",
-    "irresistibly",
-    "irresolute",
-    "irresolution",
-    "irresolvable",
-    "irrespective",
-    "irresponsibility",
-    "irresponsible",
-    "irresponsibly",
-    "irretrievable",
-    "irretrievably",
-    "irreverence",
-    "irreverent",
-    "irreverently",
-    "irreversibility",
-    "irreversible",
-    "irreversibly",
-    "irrevocable",
-    "irrevocably",
-    "irri",
-    "irrigable",
-    "irrigate",
-    "irrigated",
-    "irrigating",
-    "irrigation",
-    "irrigations",
-    "irrigators",
-    "irritability",
-    "irritable",
-    "irritably",
-    "irritant",
-    "irritants",
-    "irritate",
-    "irritated",
-    "irritates",
-    "irritating",
-    "irritation",
-    "irritations",
-    "irritative",
-    "irrotational",
-    "irruption",
-    "irst",
-    "isaac",
-    "isaacs",
-    "isaacson",
-    "isaak",
-    "isee",
-    "isherwood",
-    "isms",
-    "isps",
-    "iss",
-    "issa",
-    "issac",
-    "issachar",
-    "issei",
-    "issn",
-    "issuance",
-    "issue",
-    "issued",
-    "issuer",
-    "issuers",
-    "issues",
-    "issuing",
-    "issus",
-    "ists",
-    "iww",
-    "izaak",
-    "jaap",
-    "jaar",
-    "jackass",
-    "jackendoff",
-    "jaffa",
-    "jaffe",
-    "jaffee",
-    "jaffna",
-    "jamaat",
-    "jamboree",
-    "janissaries",
-    "janssen",
-    "janssens",
-    "jansson",
-    "jarred",
-    "jarrell",
-    "jarrett",
-    "jarring",
-    "jarrow",
-    "jarry",
-    "jassy",
-    "jauss",
-    "jazeera",
-    "jeddah",
-    "jee",
-    "jeep",
-    "jeeps",
-    "jeer",
-    "jeered",
-    "jeering",
-    "jeers",
-    "jeeves",
-    "jeez",
-    "jeff",
-    "jefferies",
-    "jeffers",
-    "jefferson",
-    "jeffersonian",
-    "jeffersonians",
-    "jeffery",
-    "jeffords",
-    "jeffrey",
-    "jeffreys",
-    "jeffries",
-    "jeffs",
-    "jelliffe",
-    "jenness",
-    "jerrold",
-    "jerry",
-    "jess",
-    "jessamine",
-    "jesse",
-    "jessel",
-    "jessen",
-    "jessica",
-    "jessie",
-    "jessop",
-    "jessor",
-    "jessup",
-    "jessy",
-    "jeter",
-    "jeunesse",
-    "jewess",
-    "jewishness",
-    "jezreel",
-    "jiffy",
-    "jj",
-    "jobless",
-    "joblessness",
-    "joffe",
-    "joffre",
-    "johansson",
-    "jolliffe",
-    "jonsson",
-    "joo",
-    "joos",
-    "joost",
-    "joss",
-    "jossey",
-    "jouissance",
-    "joyless",
-    "jubilee",
-    "jubilees",
-    "judd",
-    "jurassic",
-    "justness",
-    "kaa",
-    "kaaba",
-    "kaddish",
-    "kaffir",
-    "kaffirs",
-    "kalamazoo",
-    "kalgoorlie",
-    "kamaraj",
-    "kamloops",
-    "kangaroo",
-    "kangaroos",
-    "kankakee",
-    "kapoor",
-    "kareem",
-    "karloff",
-    "karlsson",
-    "karoo",
-    "karr",
-    "kass",
-    "kassel",
-    "katarina",
-    "kathleen",
-    "kauffman",
-    "kauffmann",
-    "keddie",
-    "kee",
-    "keeble",
-    "keefe",
-    "keefer",
-    "keegan",
-    "keel",
-    "keele",
-    "keeled",
-    "keeler",
-    "keeley",
-    "keeling",
-    "keels",
-    "keely",
-    "keen",
-    "keenan",
-    "keene",
-    "keener",
-    "keenest",
-    "keeney",
-    "keening",
-    "keenly",
-    "keenness",
-    "keep",
-    "keepe",
-    "keeper",
-    "keepers",
-    "keepin",
-    "keeping",
-    "keeps",
-    "keepsake",
-    "keepsakes",
-    "kees",
-    "keesing",
-    "keeton",
-    "kenwood",
-    "kern",
-    "kerner",
-    "kerns",
-    "kerr",
-    "kerrigan",
-    "kerry",
-    "kessel",
-    "kesselring",
-    "kessinger",
-    "kessler",
-    "khaddar",
-    "khama",
-    "khana",
-    "khans",
-    "kharif",
-    "khas",
-    "khasi",
-    "khoo",
-    "khor",
-    "khurasan",
-    "kickapoo",
-    "kickoff",
-    "kidd",
-    "kidded",
-    "kidder",
-    "kiddie",
-    "kiddies",
-    "kidding",
-    "kiddo",
-    "kiddush",
-    "kiefer",
-    "kieffer",
-    "kier",
-    "kierkegaard",
-    "killeen",
-    "kindliness",
-    "kindness",
-    "kindnesses",
-    "kingswood",
-    "kinross",
-    "kins",
-    "kips",
-    "kirchhoff",
-    "kirkwood",
-    "kirsch",
-    "kisch",
-    "kiss",
-    "kissed",
-    "kisses",
-    "kissing",
-    "kissinger",
-    "kits",
-    "klass",
-    "klasse",
-    "klassen",
-    "klassischen",
-    "klee",
-    "kleenex",
-    "kloss",
-    "knee",
-    "kneecap",
-    "kneed",
-    "kneel",
-    "kneeled",
-    "kneeling",
-    "kneels",
-    "knees",
-    "knesset",
-    "knighthood",
-    "knoop",
-    "knorr",
-    "knossos",
-    "koffka",
-    "kommission",
-    "kondratieff",
-    "koo",
-    "kook",
-    "kool",
-    "koon",
-    "kooning",
-    "koontz",
-    "koop",
-    "koopman",
-    "koopmans",
-    "koos",
-    "kootenay",
-    "korsakoff",
-    "koss",
-    "kosslyn",
-    "kossuth",
-    "koussevitzky",
-    "kowloon",
-    "kpss",
-    "kraal",
-    "kraals",
-    "krafft",
-    "kraus",
-    "krauss",
-    "kremer",
-    "kreps",
-    "kress",
-    "kris",
-    "kronor",
-    "kronos",
-    "kupffer",
-    "kurnool",
-    "laa",
-    "laager",
-    "lacrosse",
-    "lacs",
-    "lactoferrin",
-    "ladd",
-    "ladder",
-    "ladders",
-    "laddie",
-    "laffer",
-    "lafferty",
-    "laffont",
-    "lagoon",
-    "lagoonal",
-    "lagoons",
-    "laguerre",
-    "lair",
-    "lairs",
-    "lais",
-    "laisse",
-    "laisser",
-    "laissez",
-    "laissezfaire",
-    "lajos",
-    "lakewood",
-    "lakoff",
-    "lama",
-    "lamar",
-    "lamas",
-    "lameness",
-    "lampoon",
-    "lampooned",
-    "lana",
-    "landless",
-    "landlessness",
-    "landmass",
-    "landseer",
-    "langa",
-    "lans",
-    "laocoon",
-    "laos",
-    "lapierre",
-    "laps",
-    "lara",
-    "largeness",
-    "largess",
-    "largesse",
-    "larissa",
-    "larix",
-    "larousse",
-    "larrabee",
-    "larry",
-    "lars",
-    "larsson",
-    "larus",
-    "lary",
-    "lasciviousness",
-    "lass",
-    "lassa",
-    "lassalle",
-    "lasse",
-    "lassen",
-    "lasses",
-    "lassie",
-    "lassiter",
-    "lassitude",
-    "lasso",
-    "lasswell",
-    "last",
-    "lasts",
-    "lateness",
-    "latissimus",
-    "laundress",
-    "laundresses",
-    "laura",
-    "laus",
-    "lavishness",
-    "lawfulness",
-    "lawless",
-    "lawlessness",
-    "lawn",
-    "lawns",
-    "laws",
-    "laxness",
-    "layoff",
-    "layoffs",
-    "lays",
-    "laziness",
-    "leaa",
-    "leaderless",
-    "leafless",
-    "leanness",
-    "leche",
-    "lecher",
-    "leech",
-    "leeches",
-    "leed",
-    "leeds",
-    "leek",
-    "leeks",
-    "leela",
-    "leeper",
-    "leer",
-    "leered",
-    "leering",
-    "leery",
-    "lees",
-    "leesburg",
-    "leese",
-    "leeson",
-    "leet",
-    "leeuw",
-    "leeuwen",
-    "leeuwenhoek",
-    "leeward",
-    "leeway",
-    "leff",
-    "leffler",
-    "legatee",
-    "legatees",
-    "legless",
-    "lemass",
-    "lemongrass",
-    "lems",
-    "lene",
-    "lens",
-    "lenses",
-    "leos",
-    "lerner",
-    "lesch",
-    "lese",
-    "less",
-    "lessard",
-    "lesse",
-    "lessee",
-    "lessees",
-    "lessen",
-    "lessened",
-    "lessening",
-    "lessens",
-    "lesseps",
-    "lesser",
-    "lessing",
-    "lesson",
-    "lessons",
-    "lessor",
-    "lessors",
-    "lest",
-    "lets",
-    "letterbook",
-    "letterpress",
-    "levasseur",
-    "levee",
-    "levees",
-    "lewdness",
-    "liana",
-    "lianas",
-    "liar",
-    "liars",
-    "lias",
-    "licensee",
-    "licensees",
-    "licentiousness",
-    "lidded",
-    "liddell",
-    "liddle",
-    "liddy",
-    "lief",
-    "liens",
-    "lier",
-    "lies",
-    "lifeblood",
-    "lifeless",
-    "liffey",
-    "liftoff",
-    "lightfoot",
-    "lightheadedness",
-    "lightness",
-    "likelihood",
-    "likelihoods",
-    "likeness",
-    "likenesses",
-    "limitless",
-    "lins",
-    "linseed",
-    "linwood",
-    "lioness",
-    "lips",
-    "lisp",
-    "liss",
-    "lissa",
-    "lisse",
-    "lissitzky",
-    "list",
-    "listless",
-    "listlessly",
-    "listlessness",
-    "lists",
-    "liszt",
-    "literalness",
-    "littleness",
-    "littlewood",
-    "livelihood",
-    "livelihoods",
-    "liveliness",
-    "liveness",
-    "liverpool",
-    "livingroom",
-    "llandaff",
-    "lndeed",
-    "loams",
-    "loans",
-    "lochs",
-    "lockheed",
-    "lockwood",
-    "loco",
-    "locos",
-    "loeffler",
-    "loess",
-    "loftiness",
-    "logbook",
-    "logwood",
-    "lois",
-    "londonderry",
-    "loneliness",
-    "longo",
-    "longs",
-    "longstreet",
-    "longsuffering",
-    "longwood",
-    "loo",
-    "look",
-    "looke",
-    "looked",
-    "looker",
-    "lookers",
-    "lookin",
-    "looking",
-    "lookout",
-    "lookouts",
-    "looks",
-    "lookup",
-    "lookups",
-    "loom",
-    "loomed",
-    "looming",
-    "loomis",
-    "looms",
-    "loon",
-    "looney",
-    "loons",
-    "loony",
-    "loop",
-    "loopback",
-    "looped",
-    "looper",
-    "loophole",
-    "loopholes",
-    "looping",
-    "loops",
-    "loos",
-    "loose",
-    "loosed",
-    "looseleaf",
-    "loosely",
-    "loosen",
-    "loosened",
-    "looseness",
-    "loosening",
-    "loosens",
-    "looser",
-    "looses",
-    "loosing",
-    "loot",
-    "looted",
-    "looters",
-    "looting",
-    "lora",
-    "loran",
-    "lori",
-    "loris",
-    "lorn",
-    "loro",
-    "lorrain",
-    "lorraine",
-    "lorries",
-    "lorry",
-    "lors",
-    "lorsch",
-    "losch",
-    "loss",
-    "losses",
-    "lossing",
-    "lossless",
-    "lossy",
-    "lost",
-    "lotos",
-    "lots",
-    "loudness",
-    "lovaas",
-    "loveless",
-    "loveliness",
-    "lovingkindness",
-    "lowa",
-    "lowi",
-    "lowliness",
-    "lown",
-    "lowness",
-    "lowpass",
-    "lows",
-    "lrrm",
-    "lss",
-    "lssue",
-    "lssues",
-    "luckless",
-    "luddite",
-    "luddites",
-    "ludendorff",
-    "luff",
-    "luftwaffe",
-    "luis",
-    "lujan",
-    "lunchroom",
-    "lungs",
-    "lusaka",
-    "lusk",
-    "luso",
-    "lussac",
-    "lust",
-    "lusts",
-    "luxor",
-    "lxx",
-    "lxxi",
-    "lxxii",
-    "lxxiii",
-    "lxxiv",
-    "lxxix",
-    "lxxv",
-    "lxxvi",
-    "lxxvii",
-    "lxxviii",
-    "lxxx",
-    "lxxxi",
-    "lxxxii",
-    "lxxxiii",
-    "lycee",
-    "lydda",
-    "lynx",
-    "maa",
-    "maanen",
-    "maar",
-    "maarten",
-    "maasai",
-    "maass",
-    "maastricht",
-    "maat",
-    "maatschappij",
-    "macassar",
-    "macaw",
-    "macaws",
-    "maccabees",
-    "macduff",
-    "macfadden",
-    "macha",
-    "macmurray",
-    "macquarrie",
-    "macqueen",
-    "macs",
-    "maddalena",
-    "madden",
-    "maddened",
-    "maddening",
-    "maddeningly",
-    "madder",
-    "maddie",
-    "madding",
-    "maddison",
-    "maddock",
-    "maddox",
-    "maddy",
-    "madness",
-    "maffei",
-    "magee",
-    "magnusson",
-    "mahmood",
-    "mair",
-    "maja",
-    "maji",
-    "major",
-    "majors",
-    "majus",
-    "makassar",
-    "maleness",
-    "mallee",
-    "mamas",
-    "mana",
-    "manas",
-    "manassas",
-    "manasseh",
-    "manatee",
-    "manatees",
-    "manga",
-    "manhood",
-    "manliness",
-    "mans",
-    "mansa",
-    "manteuffel",
-    "mantissa",
-    "manumission",
-    "manx",
-    "maps",
-    "mara",
-    "marais",
-    "marchioness",
-    "mari",
-    "marilee",
-    "maris",
-    "marissa",
-    "markedness",
-    "marketeers",
-    "maro",
-    "maroon",
-    "marooned",
-    "maroons",
-    "marquee",
-    "marquess",
-    "marr",
-    "marra",
-    "marrakech",
-    "marrakesh",
-    "marranos",
-    "marred",
-    "marriage",
-    "marriageable",
-    "marriages",
-    "married",
-    "marries",
-    "marriner",
-    "marring",
-    "marriott",
-    "marris",
-    "marrow",
-    "marry",
-    "marryat",
-    "marrying",
-    "mars",
-    "maru",
-    "marwan",
-    "marwar",
-    "marwari",
-    "marx",
-    "mary",
-    "marya",
-    "marys",
-    "masa",
-    "masai",
-    "masao",
-    "masood",
-    "massa",
-    "massachusetts",
-    "massacre",
-    "massacred",
-    "massacres",
-    "massacring",
-    "massage",
-    "massaged",
-    "massages",
-    "massaging",
-    "massawa",
-    "masse",
-    "massed",
-    "massena",
-    "massenet",
-    "masses",
-    "masseter",
-    "masseur",
-    "massey",
-    "massie",
-    "massif",
-    "massifs",
-    "massimo",
-    "massine",
-    "massing",
-    "massinger",
-    "massive",
-    "massively",
-    "massiveness",
-    "massless",
-    "massoud",
-    "massy",
-    "mast",
-    "mastiff",
-    "masts",
-    "matchless",
-    "matinee",
-    "matinees",
-    "matisse",
-    "mats",
-    "matthiessen",
-    "mattress",
-    "mattresses",
-    "maumee",
-    "maupassant",
-    "maur",
-    "maura",
-    "maureen",
-    "maurras",
-    "maus",
-    "mauss",
-    "mawr",
-    "maxi",
-    "mayas",
-    "mayberry",
-    "maynooth",
-    "mayr",
-    "mays",
-    "mazdoor",
-    "mcadoo",
-    "mcafee",
-    "mcauliffe",
-    "mccaffery",
-    "mccaffrey",
-    "mccandless",
-    "mccarran",
-    "mccook",
-    "mcduffie",
-    "mcfadden",
-    "mcgarry",
-    "mcgee",
-    "mcghee",
-    "mcguffey",
-    "mcguinness",
-    "mckee",
-    "mckeever",
-    "mcmurray",
-    "mcnamee",
-    "mcneely",
-    "mcphee",
-    "mcqueen",
-    "mdd",
-    "meaningfulness",
-    "meaningless",
-    "meaninglessness",
-    "meanness",
-    "measureless",
-    "meatless",
-    "medd",
-    "meddle",
-    "meddled",
-    "meddlesome",
-    "meddling",
-    "mediterranean",
-    "mediterranee",
-    "meech",
-    "meed",
-    "meehan",
-    "meehl",
-    "meek",
-    "meeker",
-    "meekly",
-    "meekness",
-    "meeks",
-    "meena",
-    "meer",
-    "meera",
-    "meerut",
-    "mees",
-    "meese",
-    "meet",
-    "meetin",
-    "meeting",
-    "meetinghouse",
-    "meetings",
-    "meets",
-    "megiddo",
-    "meissen",
-    "meissner",
-    "mejor",
-    "melee",
-    "melissa",
-    "meme",
-    "memes",
-    "mems",
-    "mendeleev",
-    "mendelssohn",
-    "mene",
-    "menezes",
-    "menge",
-    "menger",
-    "menominee",
-    "menorrhagia",
-    "mens",
-    "menses",
-    "mentee",
-    "meow",
-    "meps",
-    "merciless",
-    "mercilessly",
-    "mere",
-    "merimee",
-    "mero",
-    "merrell",
-    "merriam",
-    "merrick",
-    "merrie",
-    "merrier",
-    "merrifield",
-    "merrill",
-    "merrily",
-    "merrimac",
-    "merrimack",
-    "merriman",
-    "merriment",
-    "merritt",
-    "merry",
-    "merrymaking",
-    "mers",
-    "mesmer",
-    "meso",
-    "mess",
-    "message",
-    "messages",
-    "messaging",
-    "messe",
-    "messed",
-    "messenger",
-    "messengers",
-    "messenia",
-    "messer",
-    "messerschmitt",
-    "messes",
-    "messiaen",
-    "messiah",
-    "messiahs",
-    "messiahship",
-    "messianic",
-    "messianism",
-    "messick",
-    "messier",
-    "messieurs",
-    "messina",
-    "messiness",
-    "messing",
-    "messinger",
-    "messner",
-    "messrs",
-    "messy",
-    "mest",
-    "mesure",
-    "mesures",
-    "metaanalysis",
-    "metanarrative",
-    "metes",
-    "mets",
-    "meuse",
-    "meyerbeer",
-    "meyerhoff",
-    "microarray",
-    "microarrays",
-    "microfossils",
-    "microhardness",
-    "microorganism",
-    "microorganisms",
-    "microprocessor",
-    "microprocessors",
-    "mics",
-    "midafternoon",
-    "midday",
-    "middelburg",
-    "midden",
-    "middens",
-    "middle",
-    "middleaged",
-    "middlebrook",
-    "middlebrow",
-    "middlebury",
-    "middleclass",
-    "middleman",
-    "middlemarch",
-    "middlemen",
-    "middles",
-    "middlesbrough",
-    "middlesex",
-    "middleton",
-    "middletown",
-    "middleware",
-    "middling",
-    "midnineteenth",
-    "midriff",
-    "midweek",
-    "mier",
-    "miers",
-    "mies",
-    "miffed",
-    "mifflin",
-    "mildness",
-    "milkweed",
-    "millbrook",
-    "milwaukee",
-    "mims",
-    "mindedness",
-    "mindfulness",
-    "mindless",
-    "mindlessly",
-    "minesweepers",
-    "mins",
-    "minuteness",
-    "minx",
-    "mips",
-    "mirra",
-    "mirror",
-    "mirrored",
-    "mirroring",
-    "mirrors",
-    "misc",
-    "miscarriage",
-    "miscarriages",
-    "miscarried",
-    "miscarry",
-    "misclassification",
-    "misclassified",
-    "misdeed",
-    "misdeeds",
-    "misr",
-    "miss",
-    "missa",
-    "missal",
-    "missed",
-    "missense",
-    "misses",
-    "misshapen",
-    "missile",
-    "missiles",
-    "missing",
-    "mission",
-    "missionaries",
-    "missionary",
-    "missions",
-    "missis",
-    "mississauga",
-    "mississippi",
-    "mississippian",
-    "mississippians",
-    "missive",
-    "missives",
-    "missoula",
-    "missouri",
-    "missourians",
-    "misspelled",
-    "misspelling",
-    "misspellings",
-    "misstatement",
-    "misstatements",
-    "misstep",
-    "missteps",
-    "missus",
-    "missy",
-    "mist",
-    "mistook",
-    "mistress",
-    "mistresses",
-    "mists",
-    "misunderstood",
-    "mittee",
-    "mitterrand",
-    "moana",
-    "moans",
-    "moats",
-    "moffat",
-    "moffatt",
-    "moffett",
-    "moffitt",
-    "moir",
-    "mois",
-    "mojo",
-    "molasses",
-    "momo",
-    "moms",
-    "moncrieff",
-    "mongo",
-    "mongoose",
-    "mono",
-    "monogr",
-    "monooxygenase",
-    "monro",
-    "mons",
-    "monsoon",
-    "monsoonal",
-    "monsoons",
-    "monterrey",
-    "montessori",
-    "montparnasse",
-    "montserrat",
-    "moo",
-    "mood",
-    "moodie",
-    "moodily",
-    "moodiness",
-    "moods",
-    "moody",
-    "moog",
-    "mook",
-    "mookerjee",
-    "moon",
-    "moonbeams",
-    "mooney",
-    "mooning",
-    "moonless",
-    "moonlight",
-    "moonlighting",
-    "moonlit",
-    "moons",
-    "moonshine",
-    "moonstone",
-    "moor",
-    "moore",
-    "moored",
-    "moorehead",
-    "moores",
-    "moorhead",
-    "moorhouse",
-    "mooring",
-    "moorings",
-    "moorish",
-    "moorland",
-    "moorman",
-    "moors",
-    "moos",
-    "moose",
-    "moot",
-    "mooted",
-    "mops",
-    "mora",
-    "morais",
-    "moran",
-    "morass",
-    "moray",
-    "mori",
-    "morn",
-    "moro",
-    "morrell",
-    "morrie",
-    "morrill",
-    "morris",
-    "morrison",
-    "morrissey",
-    "morristown",
-    "morro",
-    "morrow",
-    "mors",
-    "morss",
-    "mortgagee",
-    "mortgagees",
-    "moscow",
-    "mosm",
-    "moss",
-    "mossad",
-    "mossadegh",
-    "mossbauer",
-    "mosse",
-    "mosses",
-    "mossi",
-    "mossman",
-    "mossy",
-    "most",
-    "motherhood",
-    "motherless",
-    "motionless",
-    "mots",
-    "mouffe",
-    "mountaineer",
-    "mountaineering",
-    "mountaineers",
-    "moussa",
-    "mousse",
-    "mowat",
-    "mown",
-    "mss",
-    "mudd",
-    "muddied",
-    "muddle",
-    "muddled",
-    "muddling",
-    "muddy",
-    "muff",
-    "muffin",
-    "muffins",
-    "muffle",
-    "muffled",
-    "muffler",
-    "mufflers",
-    "muffling",
-    "muharram",
-    "muir",
-    "mujahideen",
-    "mukerjee",
-    "mukherjee",
-    "mulberries",
-    "mulberry",
-    "muldoon",
-    "multiprocessing",
-    "multiprocessor",
-    "multiprocessors",
-    "mums",
-    "munksgaard",
-    "muons",
-    "mura",
-    "murat",
-    "murata",
-    "murderess",
-    "murfreesboro",
-    "muro",
-    "murray",
-    "murrell",
-    "murrow",
-    "murry",
-    "musee",
-    "museen",
-    "musees",
-    "musharraf",
-    "mushroom",
-    "mushroomed",
-    "mushrooming",
-    "mushrooms",
-    "musketeer",
-    "musketeers",
-    "muskogee",
-    "muss",
-    "mussalmans",
-    "mussed",
-    "mussel",
-    "mussels",
-    "mussen",
-    "musser",
-    "musset",
-    "mussolini",
-    "mussoorie",
-    "mussorgsky",
-    "mussulman",
-    "mussulmans",
-    "must",
-    "musts",
-    "muteness",
-    "mutineers",
-    "muzaffar",
-    "mycorrhiza",
-    "mycorrhizae",
-    "mycorrhizal",
-    "myelosuppression",
-    "myer",
-    "myers",
-    "myrrh",
-    "naa",
-    "naacp",
-    "naaman",
-    "naar",
-    "naca",
-    "nachlass",
-    "nachr",
-    "naess",
-    "naif",
-    "nair",
-    "naissance",
-    "naja",
-    "nakedness",
-    "nama",
-    "nameless",
-    "nana",
-    "nanga",
-    "naps",
-    "nara",
-    "narcissa",
-    "narcisse",
-    "narcissism",
-    "narcissist",
-    "narcissistic",
-    "narcissus",
-    "nari",
-    "narr",
-    "narragansett",
-    "narrate",
-    "narrated",
-    "narrates",
-    "narrating",
-    "narration",
-    "narrations",
-    "narrative",
-    "narratively",
-    "narratives",
-    "narrativity",
-    "narratology",
-    "narrator",
-    "narrators",
-    "narrow",
-    "narrowband",
-    "narrowed",
-    "narrower",
-    "narrowest",
-    "narrowing",
-    "narrowly",
-    "narrowness",
-    "narrows",
-    "nars",
-    "nary",
-    "nasa",
-    "nasca",
-    "nasi",
-    "nasir",
-    "naso",
-    "nasr",
-    "nass",
-    "nassau",
-    "nasser",
-    "nast",
-    "nastiness",
-    "nasw",
-    "nationhood",
-    "nats",
-    "naturalness",
-    "naturwissenschaften",
-    "naughtiness",
-    "nauvoo",
-    "navarra",
-    "navarre",
-    "navarrete",
-    "navarro",
-    "nawaz",
-    "naxos",
-    "nayar",
-    "nays",
-    "ncaa",
-    "nearness",
-    "neatness",
-    "neces",
-    "necessaries",
-    "necessarily",
-    "necessario",
-    "necessary",
-    "necesse",
-    "necessitate",
-    "necessitated",
-    "necessitates",
-    "necessitating",
-    "necessities",
-    "necessity",
-    "nee",
-    "need",
-    "needed",
-    "needful",
-    "needham",
-    "neediness",
-    "needing",
-    "needle",
-    "needled",
-    "needleman",
-    "needlepoint",
-    "needles",
-    "needless",
-    "needlessly",
-    "needlework",
-    "needling",
-    "needs",
-    "needy",
-    "neel",
-    "neely",
-    "neem",
-    "neer",
-    "neere",
-    "nees",
-    "neff",
-    "negligee",
-    "negress",
-    "neighborhood",
-    "neighborhoods",
-    "neighborliness",
-    "neighbourhood",
-    "neighbourhoods",
-    "neighbourliness",
-    "neisse",
-    "neisser",
-    "neisseria",
-    "nene",
-    "neoclassic",
-    "neoclassical",
-    "neoclassicism",
-    "nere",
-    "nero",
-    "nerveless",
-    "nervousness",
-    "ness",
-    "nessa",
-    "nessus",
-    "nest",
-    "nests",
-    "nets",
-    "neurotransmission",
-    "neusner",
-    "nevertheless",
-    "newberry",
-    "newness",
-    "newsreel",
-    "newsreels",
-    "newsroom",
-    "newsrooms",
-    "newsstand",
-    "newsstands",
-    "newsweek",
-    "ngos",
-    "nias",
-    "niceness",
-    "nics",
-    "niddm",
-    "niece",
-    "nieces",
-    "nier",
-    "nies",
-    "nightdress",
-    "nijhoff",
-    "nilsson",
-    "nineteen",
-    "nineteenth",
-    "nineteenthcentury",
-    "nips",
-    "nissan",
-    "nissen",
-    "nissim",
-    "nissl",
-    "nist",
-    "nitroprusside",
-    "nits",
-    "noaa",
-    "nobleness",
-    "noblesse",
-    "nodded",
-    "nodding",
-    "noddings",
-    "noir",
-    "noirs",
-    "noiseless",
-    "noiselessly",
-    "nominee",
-    "nominees",
-    "nomos",
-    "noms",
-    "nonaggression",
-    "nonaggressive",
-    "nonbusiness",
-    "nonclassical",
-    "noncommissioned",
-    "noncooperation",
-    "noncooperative",
-    "noncurrent",
-    "nondepressed",
-    "nonessential",
-    "nonetheless",
-    "nonferrous",
-    "nonfood",
-    "nono",
-    "nonofficial",
-    "nonplussed",
-    "nonpoor",
-    "nonprofessional",
-    "nonprofessionals",
-    "nonrecurring",
-    "nontariff",
-    "noo",
-    "noodle",
-    "noodles",
-    "nook",
-    "nooks",
-    "noon",
-    "noonan",
-    "noonday",
-    "noone",
-    "noontime",
-    "noor",
-    "noord",
-    "noose",
-    "nootka",
-    "nora",
-    "norcross",
-    "nordhoff",
-    "noreen",
-    "nori",
-    "norm",
-    "norms",
-    "norris",
-    "norsk",
-    "northbrook",
-    "northcliffe",
-    "northwood",
-    "norway",
-    "norwood",
-    "nosebleed",
-    "nosebleeds",
-    "noss",
-    "nossa",
-    "notebook",
-    "notebooks",
-    "nothingness",
-    "nots",
-    "nowak",
-    "nss",
-    "nueces",
-    "nuer",
-    "nuffield",
-    "numberless",
-    "numbness",
-    "nuns",
-    "nurs",
-    "nusa",
-    "nussbaum",
-    "nusselt",
-    "nuts",
-    "nuys",
-    "nwfp",
-    "nyssa",
-    "oaa",
-    "oakwood",
-    "oars",
-    "oasis",
-    "oats",
-    "obligee",
-    "obsess",
-    "obsessed",
-    "obsessing",
-    "obsession",
-    "obsessional",
-    "obsessions",
-    "obsessive",
-    "obsessively",
-    "obtuseness",
-    "obviousness",
-    "occurred",
-    "occurrence",
-    "occurrences",
-    "occurring",
-    "ocho",
-    "ochs",
-    "odd",
-    "oddball",
-    "odder",
-    "oddest",
-    "oddi",
-    "oddities",
-    "oddity",
-    "oddly",
-    "oddness",
-    "odds",
-    "odessa",
-    "odorless",
-    "odysseus",
-    "odyssey",
-    "oeec",
-    "oems",
-    "offa",
-    "offal",
-    "offbeat",
-    "offe",
-    "offen",
-    "offenbach",
-    "offenbar",
-    "offence",
-    "offences",
-    "offend",
-    "offended",
-    "offender",
-    "offenders",
-    "offending",
-    "offends",
-    "offense",
-    "offenses",
-    "offensive",
-    "offensively",
-    "offensiveness",
-    "offensives",
-    "offer",
-    "offered",
-    "offeree",
-    "offering",
-    "offerings",
-    "offeror",
-    "offers",
-    "offertory",
-    "offhand",
-    "offhandedly",
-    "offi",
-    "office",
-    "officeholder",
-    "officeholders",
-    "officer",
-    "officered",
-    "officers",
-    "offices",
-    "official",
-    "officialdom",
-    "officially",
-    "officials",
-    "officiant",
-    "officiate",
-    "officiated",
-    "officiating",
-    "officiel",
-    "officiis",
-    "officinale",
-    "officinalis",
-    "officio",
-    "officious",
-    "officium",
-    "offing",
-    "offish",
-    "offline",
-    "offre",
-    "offs",
-    "offscreen",
-    "offset",
-    "offsets",
-    "offsetting",
-    "offshoot",
-    "offshoots",
-    "offshore",
-    "offside",
-    "offsite",
-    "offspring",
-    "offsprings",
-    "offstage",
-    "ogee",
-    "oilseed",
-    "oilseeds",
-    "ojos",
-    "okeechobee",
-    "olsson",
-    "omission",
-    "omissions",
-    "onassis",
-    "ondaatje",
-    "oneness",
-    "onlooker",
-    "onlookers",
-    "onscreen",
-    "oo",
-    "oocysts",
-    "oocyte",
-    "oocytes",
-    "ood",
-    "oogenesis",
-    "ooh",
-    "ook",
-    "ool",
-    "oolite",
-    "oolitic",
-    "oom",
-    "oon",
-    "ooo",
-    "oooh",
-    "oooo",
-    "ooooo",
-    "oooooo",
-    "oooooooo",
-    "oop",
-    "oophorectomy",
-    "oops",
-    "oor",
-    "oort",
-    "oost",
-    "oot",
-    "ooze",
-    "oozed",
-    "oozes",
-    "oozing",
-    "openness",
-    "opossum",
-    "opossums",
-    "oppress",
-    "oppressed",
-    "oppresses",
-    "oppressing",
-    "oppression",
-    "oppressions",
-    "oppressive",
-    "oppressively",
-    "oppressor",
-    "oppressors",
-    "orderliness",
-    "ordinariness",
-    "orissa",
-    "orloff",
-    "oroonoko",
-    "orr",
-    "orrery",
-    "orrin",
-    "osgood",
-    "ospf",
-    "oss",
-    "ossa",
-    "osseous",
-    "osservatore",
-    "osservazioni",
-    "ossian",
-    "ossicles",
-    "ossicular",
-    "ossie",
-    "ossificans",
-    "ossification",
-    "ossified",
-    "ossuary",
-    "osterreich",
-    "osterreichische",
-    "osterreichischen",
-    "otherness",
-    "otros",
-    "outclassed",
-    "outdoor",
-    "outdoors",
-    "outgassing",
-    "outlook",
-    "outlooks",
-    "outspokenness",
-    "ovenproof",
-    "overcooked",
-    "overcorrection",
-    "overcurrent",
-    "overdressed",
-    "overexpressed",
-    "overexpression",
-    "overfeeding",
-    "overlook",
-    "overlooked",
-    "overlooking",
-    "overlooks",
-    "overpass",
-    "overpressure",
-    "overran",
-    "overrated",
-    "overreach",
-    "overreached",
-    "overreaching",
-    "overreact",
-    "overreacted",
-    "overreacting",
-    "overreaction",
-    "overreliance",
-    "overrepresentation",
-    "overrepresented",
-    "overridden",
-    "override",
-    "overrides",
-    "overriding",
-    "overripe",
-    "overrode",
-    "overrule",
-    "overruled",
-    "overruling",
-    "overrun",
-    "overrunning",
-    "overruns",
-    "oversee",
-    "overseeing",
-    "overseen",
-    "overseer",
-    "overseers",
-    "oversees",
-    "overshoot",
-    "overshooting",
-    "overstreet",
-    "overstress",
-    "overstressed",
-    "overstuffed",
-    "overtook",
-    "overweening",
-    "owerri",
-    "owns",
-    "oxon",
-    "paa",
-    "paar",
-    "paca",
-    "pacha",
-    "packwood",
-    "padded",
-    "paddies",
-    "padding",
-    "paddington",
-    "paddle",
-    "paddled",
-    "paddlers",
-    "paddles",
-    "paddling",
-    "paddock",
-    "paddocks",
-    "paddy",
-    "painless",
-    "painlessly",
-    "pairs",
-    "paleness",
-    "pamphleteer",
-    "pamphleteers",
-    "pana",
-    "panas",
-    "pantaloons",
-    "papas",
-    "papeete",
-    "paperless",
-    "para",
-    "paraffin",
-    "paraffins",
-    "parakeet",
-    "parakeets",
-    "paraprofessional",
-    "paraprofessionals",
-    "paratroop",
-    "paratrooper",
-    "paratroopers",
-    "paratroops",
-    "pardee",
-    "parenthood",
-    "pari",
-    "paris",
-    "parma",
-    "parmar",
-    "parmelee",
-    "parnas",
-    "parnassus",
-    "paro",
-    "parolee",
-    "parolees",
-    "paros",
-    "parr",
-    "parra",
-    "parramatta",
-    "parricide",
-    "parried",
-    "parries",
-    "parrington",
-    "parris",
-    "parrish",
-    "parrot",
-    "parrots",
-    "parrott",
-    "parry",
-    "parsee",
-    "parsees",
-    "parterre",
-    "partook",
-    "paru",
-    "parus",
-    "pasa",
-    "paso",
-    "pass",
-    "passable",
-    "passacaglia",
-    "passage",
-    "passages",
-    "passageway",
-    "passageways",
-    "passaic",
-    "passant",
-    "passau",
-    "passband",
-    "passbook",
-    "passchendaele",
-    "passe",
-    "passed",
-    "passenger",
-    "passengers",
-    "passer",
-    "passerby",
-    "passerine",
-    "passers",
-    "passersby",
-    "passes",
-    "passeth",
-    "passim",
-    "passing",
-    "passio",
-    "passion",
-    "passionate",
-    "passionately",
-    "passionless",
-    "passions",
-    "passivation",
-    "passive",
-    "passively",
-    "passives",
-    "passivity",
-    "passmore",
-    "passo",
-    "passos",
-    "passover",
-    "passport",
-    "passports",
-    "passu",
-    "passus",
-    "passwd",
-    "password",
-    "passwords",
-    "passy",
-    "past",
-    "pasta",
-    "pastas",
-    "patchiness",
-    "patentee",
-    "patentees",
-    "pathless",
-    "patroness",
-    "pattee",
-    "pauw",
-    "pawn",
-    "pawnee",
-    "pawnees",
-    "payee",
-    "payees",
-    "payoff",
-    "payoffs",
-    "pays",
-    "pdd",
-    "peacefulness",
-    "peacekeepers",
-    "peacekeeping",
-    "peachtree",
-    "peche",
-    "peddle",
-    "peddled",
-    "peddler",
-    "peddlers",
-    "peddling",
-    "pedigree",
-    "pedigrees",
-    "pee",
-    "peebles",
-    "peed",
-    "peek",
-    "peeked",
-    "peeking",
-    "peeks",
-    "peel",
-    "peele",
-    "peeled",
-    "peeler",
-    "peeling",
-    "peels",
-    "peening",
-    "peep",
-    "peeped",
-    "peephole",
-    "peeping",
-    "peeps",
-    "peer",
-    "peerage",
-    "peered",
-    "peering",
-    "peerless",
-    "peers",
-    "peet",
-    "peeters",
-    "peeved",
-    "peevish",
-    "peevishly",
-    "pelee",
-    "pemex",
-    "pene",
-    "penniless",
-    "pense",
-    "pensee",
-    "pensees",
-    "penser",
-    "peoplehood",
-    "perceptiveness",
-    "percussion",
-    "percussive",
-    "pere",
-    "perigee",
-    "perloff",
-    "perm",
-    "permissibility",
-    "permissible",
-    "permission",
-    "permissions",
-    "permissive",
-    "permissiveness",
-    "pero",
-    "perrault",
-    "perret",
-    "perrier",
-    "perrin",
-    "perron",
-    "perrone",
-    "perrot",
-    "perrow",
-    "perry",
-    "personhood",
-    "perspex",
-    "persson",
-    "persuasiveness",
-    "pertussis",
-    "peruse",
-    "pervasiveness",
-    "pesce",
-    "peso",
-    "pesos",
-    "pessary",
-    "pessimism",
-    "pessimist",
-    "pessimistic",
-    "pessimists",
-    "pessoa",
-    "pest",
-    "peste",
-    "pettersson",
-    "pettiness",
-    "pfaff",
-    "pfeffer",
-    "pfeiffer",
-    "pharisee",
-    "pharisees",
-    "pharm",
-    "pharma",
-    "pharos",
-    "phasor",
-    "phasors",
-    "phips",
-    "phono",
-    "phos",
-    "photocurrent",
-    "photodissociation",
-    "photoemission",
-    "photos",
-    "phys",
-    "pianissimo",
-    "picasso",
-    "picturesqueness",
-    "piece",
-    "pieces",
-    "pier",
-    "pierre",
-    "pierres",
-    "pierrot",
-    "piers",
-    "pies",
-    "pinewood",
-    "pingree",
-    "pinwheel",
-    "pioneer",
-    "pioneered",
-    "pioneering",
-    "pioneers",
-    "piss",
-    "pissarro",
-    "pissed",
-    "pissing",
-    "pitiless",
-    "pitilessly",
-    "pius",
-    "pizarro",
-    "plainness",
-    "plaintiff",
-    "plaintiffs",
-    "plassey",
-    "platoon",
-    "platoons",
-    "playfulness",
-    "playoff",
-    "playoffs",
-    "playroom",
-    "pleasantness",
-    "pledgee",
-    "plessis",
-    "plessy",
-    "plexiglass",
-    "plodded",
-    "plodding",
-    "plywood",
-    "pocketbook",
-    "pocketbooks",
-    "poco",
-    "poems",
-    "poetess",
-    "poets",
-    "pointless",
-    "pois",
-    "poisson",
-    "poissons",
-    "politeness",
-    "pomo",
-    "pondicherry",
-    "pongo",
-    "pontiff",
-    "pontiffs",
-    "pontoon",
-    "pontoons",
-    "poo",
-    "pooch",
-    "poodle",
-    "poodles",
-    "poof",
-    "pooh",
-    "pool",
-    "poole",
-    "pooled",
-    "pooley",
-    "pooling",
-    "pools",
-    "poolside",
-    "poon",
-    "poona",
-    "poop",
-    "poor",
-    "poore",
-    "poorer",
-    "poorest",
-    "poorhouse",
-    "poorly",
-    "porn",
-    "porno",
-    "poro",
-    "porras",
-    "porridge",
-    "porro",
-    "posi",
-    "positiveness",
-    "posix",
-    "poss",
-    "posse",
-    "posses",
-    "possess",
-    "possessed",
-    "possesses",
-    "possessing",
-    "possession",
-    "possessions",
-    "possessive",
-    "possessively",
-    "possessiveness",
-    "possessives",
-    "possessor",
-    "possessors",
-    "possessory",
-    "posset",
-    "possi",
-    "possibile",
-    "possibilities",
-    "possibility",
-    "possible",
-    "possibles",
-    "possibly",
-    "possihle",
-    "possit",
-    "possono",
-    "possum",
-    "possums",
-    "possunt",
-    "post",
-    "postclassic",
-    "posto",
-    "postoffice",
-    "postprocessing",
-    "potassium",
-    "potchefstroom",
-    "potpourri",
-    "poughkeepsie",
-    "pourra",
-    "pourrait",
-    "poussin",
-    "powerless",
-    "powerlessness",
-    "powis",
-    "powwow",
-    "prearranged",
-    "precariousness",
-    "preceeding",
-    "precession",
-    "preciousness",
-    "preclassic",
-    "predecessor",
-    "predecessors",
-    "preece",
-    "preeclampsia",
-    "preeminence",
-    "preeminent",
-    "preeminently",
-    "preemployment",
-    "preempt",
-    "preempted",
-    "preempting",
-    "preemption",
-    "preemptive",
-    "preempts",
-    "preen",
-    "preening",
-    "preestablished",
-    "preexistent",
-    "preexisting",
-    "preferred",
-    "preferring",
-    "premiss",
-    "premisses",
-    "prentiss",
-    "preparedness",
-    "prepossessing",
-    "preprocessing",
-    "preprocessor",
-    "preschool",
-    "preschooler",
-    "preschoolers",
-    "preschools",
-    "press",
-    "presse",
-    "pressed",
-    "presser",
-    "presses",
-    "pressing",
-    "pression",
-    "pressley",
-    "pressman",
-    "pressmen",
-    "presso",
-    "pressor",
-    "pressure",
-    "pressured",
-    "pressures",
-    "pressuring",
-    "pressurised",
-    "pressurization",
-    "pressurized",
-    "prestress",
-    "prestressed",
-    "prestressing",
-    "pretentiousness",
-    "prettiness",
-    "preuss",
-    "preussen",
-    "preussischen",
-    "priceless",
-    "pricewaterhousecoopers",
-    "priestess",
-    "priestesses",
-    "priesthood",
-    "priesthoods",
-    "primitiveness",
-    "princess",
-    "princesse",
-    "princesses",
-    "prioress",
-    "prissy",
-    "prithee",
-    "privateer",
-    "privateering",
-    "privateers",
-    "proceed",
-    "proceeded",
-    "proceeding",
-    "proceedings",
-    "proceeds",
-    "process",
-    "processed",
-    "processes",
-    "processing",
-    "procession",
-    "processional",
-    "processions",
-    "processo",
-    "processor",
-    "processors",
-    "processual",
-    "processus",
-    "prodded",
-    "prodding",
-    "productiveness",
-    "profess",
-    "professed",
-    "professedly",
-    "professes",
-    "professeur",
-    "professing",
-    "profession",
-    "professional",
-    "professionalisation",
-    "professionalism",
-    "professionalization",
-    "professionalized",
-    "professionally",
-    "professionals",
-    "professionnelle",
-    "professions",
-    "professor",
-    "professorial",
-    "professors",
-    "professorship",
-    "professorships",
-    "proffer",
-    "proffered",
-    "proffering",
-    "proffers",
-    "profiteering",
-    "profiteers",
-    "profitless",
-    "progress",
-    "progressed",
-    "progresses",
-    "progressing",
-    "progression",
-    "progressions",
-    "progressive",
-    "progressively",
-    "progressiveness",
-    "progressives",
-    "progressivism",
-    "progressivity",
-    "promisee",
-    "promissory",
-    "promptness",
-    "proneness",
-    "proof",
-    "proofed",
-    "proofing",
-    "proofread",
-    "proofreader",
-    "proofreading",
-    "proofs",
-    "propertyless",
-    "prophetess",
-    "prophethood",
-    "proprietress",
-    "prosser",
-    "protectiveness",
-    "protectress",
-    "proudfoot",
-    "prowess",
-    "prozess",
-    "prr",
-    "prussia",
-    "prussian",
-    "prussians",
-    "pss",
-    "pudding",
-    "puddings",
-    "puddle",
-    "puddled",
-    "puddles",
-    "puddling",
-    "puer",
-    "pues",
-    "pufa",
-    "puff",
-    "puffed",
-    "puffer",
-    "puffin",
-    "puffing",
-    "puffins",
-    "puffs",
-    "puffy",
-    "puis",
-    "puissance",
-    "puissances",
-    "puissant",
-    "puisse",
-    "puja",
-    "pulpwood",
-    "puppeteer",
-    "puppeteers",
-    "pura",
-    "puran",
-    "purana",
-    "puranas",
-    "puree",
-    "pureed",
-    "puro",
-    "purposefulness",
-    "purposeless",
-    "purposiveness",
-    "purr",
-    "purred",
-    "purring",
-    "pusan",
-    "pusat",
-    "puss",
-    "pussy",
-    "pussycat",
-    "pyrenees",
-    "pyrrhic",
-    "pyrrhotite",
-    "pyrrhus",
-    "pyrrole",
-    "qaddafi",
-    "qasr",
-    "quaintness",
-    "quanah",
-    "quar",
-    "quark",
-    "quarkxpress",
-    "quarrel",
-    "quarreled",
-    "quarreling",
-    "quarrelled",
-    "quarrelling",
-    "quarrels",
-    "quarrelsome",
-    "quarried",
-    "quarries",
-    "quarry",
-    "quarrying",
-    "quart",
-    "quas",
-    "quasar",
-    "quasars",
-    "quasi",
-    "quays",
-    "queen",
-    "queene",
-    "queenie",
-    "queenly",
-    "queensberry",
-    "queensland",
-    "queenstown",
-    "queequeg",
-    "queer",
-    "queerly",
-    "queerness",
-    "queers",
-    "quern",
-    "ques",
-    "quest",
-    "queste",
-    "quests",
-    "quickbooks",
-    "quickness",
-    "quiddity",
-    "quiere",
-    "quietness",
-    "quintessence",
-    "quintessential",
-    "quintessentially",
-    "quips",
-    "quire",
-    "quires",
-    "quirk",
-    "quis",
-    "quist",
-    "quits",
-    "quos",
-    "quran",
-    "raa",
-    "raab",
-    "raabe",
-    "raad",
-    "raaf",
-    "raccoon",
-    "raccoons",
-    "rachmaninoff",
-    "racketeer",
-    "racketeering",
-    "racketeers",
-    "radcliffe",
-    "radioimmunoassay",
-    "radisson",
-    "raff",
-    "raffaele",
-    "raffaello",
-    "rafferty",
-    "raffia",
-    "raffle",
-    "raffles",
-    "rafi",
-    "ragweed",
-    "raiffa",
-    "raintree",
-    "rais",
-    "raja",
-    "rajneesh",
-    "raju",
-    "rajya",
-    "rama",
-    "ramesses",
-    "rams",
-    "rana",
-    "randomness",
-    "ranee",
-    "ranga",
-    "rangoon",
-    "rans",
-    "rapeseed",
-    "raps",
-    "rara",
-    "rasa",
-    "rasch",
-    "rasheed",
-    "rashness",
-    "rasmussen",
-    "rasp",
-    "raspberries",
-    "raspberry",
-    "rassegna",
-    "rasselas",
-    "rassemblement",
-    "rast",
-    "rasta",
-    "ratcliff",
-    "ratcliffe",
-    "ratliff",
-    "rats",
-    "rauf",
-    "ravenswood",
-    "rawness",
-    "rays",
-    "readiness",
-    "readmission",
-    "reaffirm",
-    "reaffirmation",
-    "reaffirmed",
-    "reaffirming",
-    "reaffirms",
-    "rearrange",
-    "rearranged",
-    "rearrangement",
-    "rearrangements",
-    "rearranges",
-    "rearranging",
-    "reasonableness",
-    "reassemble",
-    "reassembled",
-    "reassembling",
-    "reassembly",
-    "reassert",
-    "reasserted",
-    "reasserting",
-    "reassertion",
-    "reasserts",
-    "reassess",
-    "reassessed",
-    "reassessing",
-    "reassessment",
-    "reassign",
-    "reassigned",
-    "reassignment",
-    "reassurance",
-    "reassurances",
-    "reassure",
-    "reassured",
-    "reassures",
-    "reassuring",
-    "reassuringly",
-    "rebelliousness",
-    "reboot",
-    "rebuff",
-    "rebuffed",
-    "rebuffs",
-    "receptiveness",
-    "recess",
-    "recessed",
-    "recesses",
-    "recession",
-    "recessional",
-    "recessionary",
-    "recessions",
-    "recessive",
-    "reckless",
-    "recklessly",
-    "recklessness",
-    "reclassification",
-    "reclassified",
-    "reconnaissance",
-    "recordkeeping",
-    "recross",
-    "recrossed",
-    "recs",
-    "recurred",
-    "recurrence",
-    "recurrences",
-    "recurrent",
-    "recurrently",
-    "recurring",
-    "redbook",
-    "redcliffe",
-    "redd",
-    "redden",
-    "reddened",
-    "reddening",
-    "redder",
-    "reddi",
-    "redding",
-    "reddish",
-    "reddy",
-    "redeem",
-    "redeemable",
-    "redeemed",
-    "redeemer",
-    "redeeming",
-    "redeems",
-    "redness",
-    "redress",
-    "redressed",
-    "redressing",
-    "redwood",
-    "redwoods",
-    "ree",
-    "reebok",
-    "reece",
-    "reed",
-    "reeder",
-    "reeds",
-    "reeducation",
-    "reedy",
-    "reef",
-    "reefed",
-    "reefer",
-    "reefs",
-    "reek",
-    "reeked",
-    "reeking",
-    "reeks",
-    "reel",
-    "reelected",
-    "reelection",
-    "reeled",
-    "reeling",
-    "reels",
-    "reemerge",
-    "reemerged",
-    "reemergence",
-    "reemphasized",
-    "reemployment",
-    "reenact",
-    "reenacted",
-    "reenacting",
-    "reenactment",
-    "reengineering",
-    "reenter",
-    "reentered",
-    "reentering",
-    "reentrant",
-    "reentry",
-    "rees",
-    "reese",
-    "reestablish",
-    "reestablished",
-    "reestablishing",
-    "reestablishment",
-    "reevaluate",
-    "reevaluated",
-    "reevaluating",
-    "reevaluation",
-    "reeve",
-    "reeves",
-    "reexamination",
-    "reexamine",
-    "reexamined",
-    "reexamining",
-    "referee",
-    "refereed",
-    "referees",
-    "referral",
-    "referrals",
-    "referred",
-    "referring",
-    "refractoriness",
-    "refs",
-    "refugee",
-    "refugees",
-    "refuse",
-    "refuses",
-    "regardless",
-    "regress",
-    "regressed",
-    "regresses",
-    "regressing",
-    "regression",
-    "regressions",
-    "regressive",
-    "regressor",
-    "regressors",
-    "reindeer",
-    "reiss",
-    "reissue",
-    "reissued",
-    "relatedness",
-    "relentless",
-    "relentlessly",
-    "religiousness",
-    "remarriage",
-    "remarried",
-    "remarry",
-    "remarrying",
-    "remiss",
-    "remission",
-    "remissions",
-    "remorseless",
-    "remorselessly",
-    "remoteness",
-    "renaissance",
-    "rene",
-    "renee",
-    "rensselaer",
-    "repartee",
-    "repercussion",
-    "repercussions",
-    "repetitiveness",
-    "repossess",
-    "repossessed",
-    "repossession",
-    "repousse",
-    "representativeness",
-    "repress",
-    "repressed",
-    "represses",
-    "repressing",
-    "repression",
-    "repressions",
-    "repressive",
-    "repressor",
-    "repressors",
-    "reprocessed",
-    "reprocessing",
-    "reproof",
-    "reps",
-    "rescher",
-    "rescission",
-    "reshuffle",
-    "reshuffled",
-    "reshuffling",
-    "resistless",
-    "resoluteness",
-    "resourcefulness",
-    "resp",
-    "responsiveness",
-    "ress",
-    "ressentiment",
-    "ressources",
-    "rest",
-    "reste",
-    "restless",
-    "restlessly",
-    "restlessness",
-    "restrictiveness",
-    "restroom",
-    "restrooms",
-    "rests",
-    "resurrect",
-    "resurrected",
-    "resurrecting",
-    "resurrection",
-    "retiree",
-    "retirees",
-    "retook",
-    "retooling",
-    "retransmission",
-    "retrogression",
-    "retrogressive",
-    "returnees",
-    "reuse",
-    "reuss",
-    "rexx",
-    "rhee",
-    "rhinorrhea",
-    "rhondda",
-    "rias",
-    "ribicoff",
-    "richesse",
-    "richness",
-    "riddance",
-    "riddell",
-    "ridden",
-    "ridder",
-    "ridding",
-    "riddle",
-    "riddled",
-    "riddles",
-    "riddling",
-    "riebeeck",
-    "rieff",
-    "ries",
-    "riff",
-    "riffle",
-    "riffles",
-    "riffraff",
-    "riffs",
-    "righteousness",
-    "rightness",
-    "rijn",
-    "rimless",
-    "ringwood",
-    "ripeness",
-    "rips",
-    "risc",
-    "riskiness",
-    "riskless",
-    "riss",
-    "risse",
-    "rist",
-    "roadless",
-    "roams",
-    "roar",
-    "roark",
-    "roars",
-    "roast",
-    "roasts",
-    "robespierre",
-    "robustness",
-    "rockwood",
-    "rodd",
-    "roddy",
-    "roff",
-    "rogoff",
-    "rois",
-    "rojas",
-    "rojo",
-    "romanoff",
-    "romo",
-    "roms",
-    "roo",
-    "rood",
-    "roof",
-    "roofed",
-    "roofing",
-    "roofless",
-    "roofs",
-    "rooftop",
-    "rooftops",
-    "rook",
-    "rooke",
-    "rookeries",
-    "rookery",
-    "rookie",
-    "rookies",
-    "rooks",
-    "room",
-    "roomed",
-    "roomful",
-    "rooming",
-    "roommate",
-    "roommates",
-    "rooms",
-    "roomy",
-    "roon",
-    "rooney",
-    "roop",
-    "roos",
-    "roosevelt",
-    "roosevelts",
-    "roost",
-    "rooster",
-    "roosters",
-    "roosting",
-    "roosts",
-    "root",
-    "rooted",
-    "rootedness",
-    "rooting",
-    "rootless",
-    "rootlessness",
-    "rootlets",
-    "roots",
-    "rootstock",
-    "rootstocks",
-    "rosa",
-    "rosary",
-    "rosas",
-    "rosch",
-    "rosenbloom",
-    "rosewood",
-    "rosi",
-    "ross",
-    "rossa",
-    "rossby",
-    "rosse",
-    "rosselli",
-    "rossellini",
-    "rosser",
-    "rossetti",
-    "rossi",
-    "rossignol",
-    "rossii",
-    "rossini",
-    "rossiter",
-    "rosslyn",
-    "rossman",
-    "rosso",
-    "rost",
-    "rostow",
-    "roszak",
-    "rots",
-    "rottenness",
-    "roughness",
-    "roundness",
-    "roundwood",
-    "rousseau",
-    "roussel",
-    "rousset",
-    "roussillon",
-    "rowan",
-    "rowntree",
-    "rows",
-    "roxas",
-    "rr",
-    "rra",
-    "rras",
-    "rrc",
-    "rrna",
-    "rrr",
-    "rrs",
-    "rss",
-    "rudd",
-    "rudder",
-    "rudders",
-    "ruddick",
-    "ruddy",
-    "rudeness",
-    "rues",
-    "ruff",
-    "ruffed",
-    "ruffian",
-    "ruffians",
-    "ruffin",
-    "ruffle",
-    "ruffled",
-    "ruffles",
-    "ruffling",
-    "ruggedness",
-    "rungs",
-    "runoff",
-    "runs",
-    "rupee",
-    "rupees",
-    "rusch",
-    "russ",
-    "russe",
-    "russel",
-    "russell",
-    "russes",
-    "russet",
-    "russett",
-    "russia",
-    "russian",
-    "russians",
-    "russie",
-    "russification",
-    "russischen",
-    "russkaia",
-    "russkogo",
-    "russkoi",
-    "russland",
-    "russo",
-    "rust",
-    "rusts",
-    "ruthless",
-    "ruthlessly",
-    "ruthlessness",
-    "ruts",
-    "ryegrass",
-    "saa",
-    "saab",
-    "saad",
-    "saadia",
-    "saal",
-    "saale",
-    "saami",
-    "saar",
-    "saarc",
-    "saarinen",
-    "saarland",
-    "saatchi",
-    "saavedra",
-    "sacha",
-    "sachar",
-    "sachs",
-    "sacredness",
-    "sacs",
-    "saddam",
-    "saddened",
-    "saddening",
-    "sadder",
-    "saddest",
-    "saddle",
-    "saddlebag",
-    "saddlebags",
-    "saddled",
-    "saddler",
-    "saddles",
-    "saddling",
-    "sadducees",
-    "sadness",
-    "saeed",
-    "safa",
-    "safekeeping",
-    "safflower",
-    "safford",
-    "saffron",
-    "safi",
-    "sagesse",
-    "saif",
-    "sainthood",
-    "saintliness",
-    "sais",
-    "saks",
-    "salaam",
-    "saleem",
-    "saloon",
-    "saloons",
-    "sama",
-    "samaj",
-    "samar",
-    "samarra",
-    "sambrook",
-    "sameness",
-    "sams",
-    "sana",
-    "sandalwood",
-    "sanga",
-    "sans",
-    "santee",
-    "saps",
-    "sapwood",
-    "sara",
-    "saragossa",
-    "sarai",
-    "sargasso",
-    "sari",
-    "saris",
-    "sarma",
-    "sarna",
-    "sarnoff",
-    "saro",
-    "sarris",
-    "sars",
-    "sary",
-    "sasa",
-    "saskatoon",
-    "sass",
-    "sassafras",
-    "sassanian",
-    "sassen",
-    "sasso",
-    "sasson",
-    "sassoon",
-    "sassy",
-    "sats",
-    "sauf",
-    "saur",
-    "saussure",
-    "sauteed",
-    "sawn",
-    "saws",
-    "sawtooth",
-    "saxo",
-    "saybrook",
-    "says",
-    "scaffold",
-    "scaffolding",
-    "scaffolds",
-    "scarr",
-    "scarred",
-    "scarring",
-    "scarry",
-    "schaeffer",
-    "schaff",
-    "schaffer",
-    "schaffner",
-    "scharff",
-    "scheel",
-    "scheele",
-    "scheer",
-    "scheff",
-    "scheffe",
-    "scheffer",
-    "scheffler",
-    "schegloff",
-    "scherrer",
-    "schieffelin",
-    "schiff",
-    "schiffer",
-    "schiffman",
-    "schillebeeckx",
-    "schizoaffective",
-    "schlieffen",
-    "schloss",
-    "schlosser",
-    "school",
-    "schoolbooks",
-    "schoolboy",
-    "schoolboys",
-    "schoolchildren",
-    "schoolcraft",
-    "schooldays",
-    "schooled",
-    "schooler",
-    "schoolers",
-    "schoolgirl",
-    "schoolgirls",
-    "schoolhouse",
-    "schoolhouses",
-    "schooling",
-    "schoolmaster",
-    "schoolmasters",
-    "schoolmate",
-    "schoolmates",
-    "schoolmen",
-    "schoolmistress",
-    "schoolroom",
-    "schools",
-    "schoolteacher",
-    "schoolteachers",
-    "schoolwide",
-    "schoolwork",
-    "schoolyard",
-    "schooner",
-    "schooners",
-    "schorr",
-    "scission",
-    "scissor",
-    "scissors",
-    "scoff",
-    "scoffed",
-    "scoffing",
-    "scoffs",
-    "scoop",
-    "scooped",
-    "scooping",
-    "scoops",
-    "scoot",
-    "scooted",
-    "scooter",
-    "scooters",
-    "scrapbook",
-    "scrapbooks",
-    "scree",
-    "screech",
-    "screeched",
-    "screeching",
-    "screed",
-    "screen",
-    "screened",
-    "screener",
-    "screening",
-    "screenings",
-    "screenplay",
-    "screenplays",
-    "screens",
-    "screenshot",
-    "screenwriter",
-    "screenwriters",
-    "screenwriting",
-    "scrooge",
-    "scruff",
-    "scruffy",
-    "scudder",
-    "scudding",
-    "scuffed",
-    "scuffing",
-    "scuffle",
-    "scuffling",
-    "scurried",
-    "scurrilous",
-    "scurry",
-    "scurrying",
-    "seabrook",
-    "seafloor",
-    "seafood",
-    "seafoods",
-    "seagrass",
-    "seamless",
-    "seamlessly",
-    "seamstress",
-    "seamstresses",
-    "seasickness",
-    "seaweed",
-    "seaweeds",
-    "seaworthiness",
-    "seborrheic",
-    "secession",
-    "secessionist",
-    "secessionists",
-    "sechs",
-    "secretiveness",
-    "secs",
-    "seddon",
-    "seductiveness",
-    "seductress",
-    "see",
-    "seealso",
-    "seebeck",
-    "seebohm",
-    "seeckt",
-    "seed",
-    "seedbed",
-    "seeded",
-    "seeding",
-    "seedless",
-    "seedling",
-    "seedlings",
-    "seeds",
-    "seedy",
-    "seeger",
-    "seein",
-    "seeing",
-    "seek",
-    "seeker",
-    "seekers",
-    "seeking",
-    "seeks",
-    "seel",
-    "seele",
-    "seeley",
-    "seely",
-    "seem",
-    "seeman",
-    "seeme",
-    "seemed",
-    "seemeth",
-    "seeming",
-    "seemingly",
-    "seemly",
-    "seems",
-    "seen",
-    "seene",
-    "seep",
-    "seepage",
-    "seeped",
-    "seeping",
-    "seeps",
-    "seer",
-    "seers",
-    "sees",
-    "seesaw",
-    "seest",
-    "seeth",
-    "seethe",
-    "seethed",
-    "seething",
-    "sejm",
-    "selassie",
-    "selfawareness",
-    "selfconsciousness",
-    "selfesteem",
-    "selfexpression",
-    "selfhood",
-    "selfishness",
-    "selfless",
-    "selflessly",
-    "selflessness",
-    "selfsufficiency",
-    "selfsufficient",
-    "seme",
-    "semiclassical",
-    "semiofficial",
-    "semiprofessional",
-    "senge",
-    "sens",
-    "sense",
-    "senseless",
-    "senselessness",
-    "senses",
-    "sensitiveness",
-    "sensuousness",
-    "separateness",
-    "seps",
-    "sere",
-    "serf",
-    "serfs",
-    "seriousness",
-    "sero",
-    "serra",
-    "serrano",
-    "serrata",
-    "serrate",
-    "serrated",
-    "serratia",
-    "serratus",
-    "serre",
-    "serres",
-    "serried",
-    "sers",
-    "servanthood",
-    "sese",
-    "sess",
-    "sessile",
-    "session",
-    "sessional",
-    "sessions",
-    "setoff",
-    "sets",
-    "settee",
-    "seuss",
-    "seventeen",
-    "seventeenth",
-    "seventeenthcentury",
-    "sewanee",
-    "sexe",
-    "sexless",
-    "shaanxi",
-    "shabbiness",
-    "shaffer",
-    "shaheen",
-    "shallowness",
-    "shameless",
-    "shamelessly",
-    "shamelessness",
-    "shampoo",
-    "shampoos",
-    "shapeless",
-    "sharpless",
-    "sharpness",
-    "sharpshooter",
-    "sharpshooters",
-    "shaughnessy",
-    "shawcross",
-    "shawnee",
-    "shawnees",
-    "shedd",
-    "shedding",
-    "shee",
-    "sheed",
-    "sheehan",
-    "sheehy",
-    "sheeler",
-    "sheen",
-    "sheena",
-    "sheep",
-    "sheepdog",
-    "sheepish",
-    "sheepishly",
-    "sheepskin",
-    "sheer",
-    "sheet",
-    "sheeted",
-    "sheeting",
-    "sheets",
-    "sheffield",
-    "shepherdess",
-    "sherbrooke",
-    "sheriff",
-    "sheriffs",
-    "sherri",
-    "sherrill",
-    "sherrington",
-    "sherrod",
-    "sherry",
-    "sherwood",
-    "shiffrin",
-    "shiftless",
-    "shirtless",
-    "shirtsleeves",
-    "shoddy",
-    "shoeless",
-    "shoo",
-    "shooed",
-    "shook",
-    "shoot",
-    "shooter",
-    "shooters",
-    "shootin",
-    "shooting",
-    "shootings",
-    "shootout",
-    "shoots",
-    "shopfloor",
-    "shopkeeper",
-    "shopkeepers",
-    "shortness",
-    "shortsightedness",
-    "showroom",
-    "showrooms",
-    "shredded",
-    "shredder",
-    "shredding",
-    "shree",
-    "shrewdness",
-    "shudder",
-    "shuddered",
-    "shuddering",
-    "shudders",
-    "shuffle",
-    "shuffleboard",
-    "shuffled",
-    "shuffles",
-    "shuffling",
-    "shutoff",
-    "shyness",
-    "sickness",
-    "sicknesses",
-    "sickroom",
-    "siddha",
-    "siddhanta",
-    "siddhartha",
-    "siddhas",
-    "siddhi",
-    "siddiqi",
-    "siddiqui",
-    "siddons",
-    "sidedness",
-    "sierra",
-    "sierras",
-    "siete",
-    "sightedness",
-    "sightless",
-    "sightseeing",
-    "sightseers",
-    "silliness",
-    "sinfulness",
-    "singleness",
-    "sinless",
-    "sins",
-    "sips",
-    "sirs",
-    "sisson",
-    "sissy",
-    "sisterhood",
-    "sits",
-    "sixteen",
-    "sixteenth",
-    "sixteenthcentury",
-    "sixteenths",
-    "skeet",
-    "skeeter",
-    "skeffington",
-    "sketchbook",
-    "sketchbooks",
-    "skewness",
-    "skidded",
-    "skidding",
-    "skiff",
-    "skiffs",
-    "skinless",
-    "skoog",
-    "slackness",
-    "sledding",
-    "sleek",
-    "sleeman",
-    "sleep",
-    "sleeper",
-    "sleepers",
-    "sleepily",
-    "sleepiness",
-    "sleeping",
-    "sleepless",
-    "sleeplessness",
-    "sleeps",
-    "sleepwalker",
-    "sleepwalking",
-    "sleepy",
-    "sleet",
-    "sleeve",
-    "sleeved",
-    "sleeveless",
-    "sleeves",
-    "slenderness",
-    "slessor",
-    "sloop",
-    "sloops",
-    "sloppiness",
-    "sloss",
-    "slowness",
-    "sluggishness",
-    "slurred",
-    "slurries",
-    "slurring",
-    "slurry",
-    "smallness",
-    "smallwood",
-    "smartness",
-    "smithereens",
-    "smokeless",
-    "smokescreen",
-    "smoot",
-    "smooth",
-    "smoothed",
-    "smoother",
-    "smoothest",
-    "smoothie",
-    "smoothing",
-    "smoothly",
-    "smoothness",
-    "smooths",
-    "smugness",
-    "sneed",
-    "sneer",
-    "sneered",
-    "sneering",
-    "sneers",
-    "sneeze",
-    "sneezed",
-    "sneezes",
-    "sneezing",
-    "sniff",
-    "sniffed",
-    "sniffer",
-    "sniffing",
-    "sniffled",
-    "sniffling",
-    "sniffs",
-    "snobbishness",
-    "snodgrass",
-    "snook",
-    "snooker",
-    "snoop",
-    "snooping",
-    "snoopy",
-    "snooty",
-    "snooze",
-    "snorri",
-    "snuff",
-    "snuffed",
-    "snuffing",
-    "snuffling",
-    "soaks",
-    "soaps",
-    "soar",
-    "soars",
-    "soas",
-    "socorro",
-    "sodden",
-    "soddy",
-    "soest",
-    "sofa",
-    "sofas",
-    "soffit",
-    "softness",
-    "softwood",
-    "softwoods",
-    "soir",
-    "soiree",
-    "soirees",
-    "soissons",
-    "soja",
-    "sokoloff",
-    "somos",
-    "songbook",
-    "songs",
-    "sono",
-    "sons",
-    "soo",
-    "soochow",
-    "soon",
-    "soone",
-    "sooner",
-    "soonest",
-    "soong",
-    "soot",
-    "sooth",
-    "soothe",
-    "soothed",
-    "soothes",
-    "soothing",
-    "soothingly",
-    "soothsayer",
-    "soothsayers",
-    "sooty",
-    "sops",
-    "sora",
-    "sorceress",
-    "soreness",
-    "sori",
-    "sorrel",
-    "sorrell",
-    "sorrentino",
-    "sorrento",
-    "sorrow",
-    "sorrowful",
-    "sorrowfully",
-    "sorrowing",
-    "sorrows",
-    "sorry",
-    "sosa",
-    "souffle",
-    "soulless",
-    "soundless",
-    "soundlessly",
-    "soundness",
-    "soundproof",
-    "sourcebook",
-    "sourness",
-    "southwood",
-    "sown",
-    "sows",
-    "spaak",
-    "spaatz",
-    "spaciousness",
-    "sparring",
-    "sparrow",
-    "sparrows",
-    "sparseness",
-    "specialness",
-    "spedding",
-    "spee",
-    "speech",
-    "speeches",
-    "speechless",
-    "speechwriter",
-    "speed",
-    "speedboat",
-    "speeded",
-    "speedier",
-    "speedily",
-    "speeding",
-    "speedometer",
-    "speeds",
-    "speedup",
-    "speedway",
-    "speedwell",
-    "speedy",
-    "speer",
-    "spermatozoon",
-    "sperry",
-    "spesso",
-    "spiess",
-    "spineless",
-    "spinoff",
-    "spiritless",
-    "spleen",
-    "spleens",
-    "spofford",
-    "spoof",
-    "spoofing",
-    "spook",
-    "spooked",
-    "spooks",
-    "spooky",
-    "spool",
-    "spooler",
-    "spooling",
-    "spools",
-    "spoon",
-    "spooned",
-    "spooner",
-    "spoonful",
-    "spoonfuls",
-    "spooning",
-    "spoons",
-    "spoor",
-    "spotless",
-    "spotlessly",
-    "spotswood",
-    "spottiswoode",
-    "sprachwissenschaft",
-    "spreadsheet",
-    "spreadsheets",
-    "spree",
-    "sprees",
-    "springsteen",
-    "spss",
-    "spurr",
-    "spurred",
-    "spurring",
-    "spyglass",
-    "squareness",
-    "squeegee",
-    "squeeze",
-    "squeezed",
-    "squeezes",
-    "squeezing",
-    "squirrel",
-    "squirrels",
-    "sraddha",
-    "sraffa",
-    "sree",
-    "srr",
-    "ss",
-    "ssa",
-    "ssb",
-    "ssc",
-    "sscp",
-    "ssd",
-    "sse",
-    "ssf",
-    "ssh",
-    "ssi",
-    "ssl",
-    "ssm",
-    "ssn",
-    "sso",
-    "ssp",
-    "ssr",
-    "ssrc",
-    "ssri",
-    "ssris",
-    "sss",
-    "sssr",
-    "sst",
-    "ssu",
-    "ssw",
-    "staal",
-    "staat",
-    "staaten",
-    "staates",
-    "staatliche",
-    "staats",
-    "staatsbibliothek",
-    "staff",
-    "staffed",
-    "staffer",
-    "staffers",
-    "staffing",
-    "stafford",
-    "staffordshire",
-    "staffs",
-    "stainless",
-    "stallybrass",
-    "standoff",
-    "starfleet",
-    "starkness",
-    "starr",
-    "starred",
-    "starrett",
-    "starring",
-    "starry",
-    "stassen",
-    "statehood",
-    "stateless",
-    "stateroom",
-    "staterooms",
-    "stauffenberg",
-    "stauffer",
-    "steadfastness",
-    "steadiness",
-    "steatorrhea",
-    "steed",
-    "steedman",
-    "steeds",
-    "steel",
-    "steele",
-    "steeled",
-    "steelers",
-    "steelhead",
-    "steeling",
-    "steelmaking",
-    "steels",
-    "steelwork",
-    "steelworkers",
-    "steelworks",
-    "steely",
-    "steen",
-    "steep",
-    "steeped",
-    "steepening",
-    "steeper",
-    "steepest",
-    "steeping",
-    "steeple",
-    "steeplechase",
-    "steeples",
-    "steeply",
-    "steepness",
-    "steer",
-    "steerage",
-    "steere",
-    "steered",
-    "steering",
-    "steers",
-    "steersman",
-    "steevens",
-    "stefansson",
-    "steffen",
-    "steffens",
-    "stepladder",
-    "sternness",
-    "stessa",
-    "stesso",
-    "stewardess",
-    "stewardesses",
-    "stickiness",
-    "stiff",
-    "stiffen",
-    "stiffened",
-    "stiffener",
-    "stiffeners",
-    "stiffening",
-    "stiffens",
-    "stiffer",
-    "stiffly",
-    "stiffness",
-    "stiffnesses",
-    "stillness",
-    "stinginess",
-    "stirred",
-    "stirrer",
-    "stirring",
-    "stirrings",
-    "stirrup",
-    "stirrups",
-    "stockroom",
-    "stoddard",
-    "stoddart",
-    "stoff",
-    "stood",
-    "stooge",
-    "stooges",
-    "stool",
-    "stools",
-    "stoop",
-    "stooped",
-    "stooping",
-    "stoops",
-    "storekeeper",
-    "storekeepers",
-    "storeroom",
-    "storerooms",
-    "storr",
-    "storrs",
-    "storybook",
-    "storybooks",
-    "stouffer",
-    "straddle",
-    "straddled",
-    "straddles",
-    "straddling",
-    "strafford",
-    "straightforwardness",
-    "straightness",
-    "strangeness",
-    "strassburg",
-    "strasse",
-    "strasser",
-    "strauss",
-    "strawberries",
-    "strawberry",
-    "streeck",
-    "street",
-    "streetcar",
-    "streetcars",
-    "streeten",
-    "streeter",
-    "streetlight",
-    "streetlights",
-    "streets",
-    "streetwise",
-    "stress",
-    "stressed",
-    "stresses",
-    "stressful",
-    "stressing",
-    "stressor",
-    "stressors",
-    "strictness",
-    "stroessner",
-    "stroop",
-    "structureless",
-    "stubbornness",
-    "studded",
-    "studding",
-    "stuff",
-    "stuffed",
-    "stuffiness",
-    "stuffing",
-    "stuffs",
-    "stuffy",
-    "stylesheet",
-    "suas",
-    "subassemblies",
-    "subassembly",
-    "subcarrier",
-    "subclass",
-    "subclasses",
-    "subcommittee",
-    "subcommittees",
-    "subfloor",
-    "submission",
-    "submissions",
-    "submissive",
-    "submissively",
-    "submissiveness",
-    "subprocesses",
-    "subterranean",
-    "subtree",
-    "subtrees",
-    "succeed",
-    "succeeded",
-    "succeeding",
-    "succeeds",
-    "success",
-    "successes",
-    "successful",
-    "successfully",
-    "succession",
-    "successional",
-    "successions",
-    "successive",
-    "successively",
-    "successor",
-    "successors",
-    "suchness",
-    "sudden",
-    "suddenly",
-    "suddenness",
-    "sues",
-    "suess",
-    "suffer",
-    "sufferance",
-    "suffered",
-    "sufferer",
-    "sufferers",
-    "suffering",
-    "sufferings",
-    "suffers",
-    "suffice",
-    "sufficed",
-    "suffices",
-    "sufficiency",
-    "sufficiendy",
-    "sufficient",
-    "sufficiently",
-    "sufficing",
-    "suffix",
-    "suffixed",
-    "suffixes",
-    "suffocate",
-    "suffocated",
-    "suffocating",
-    "suffocation",
-    "suffolk",
-    "suffragan",
-    "suffrage",
-    "suffrages",
-    "suffragette",
-    "suffragettes",
-    "suffragist",
-    "suffragists",
-    "suffused",
-    "suggestiveness",
-    "suis",
-    "suisse",
-    "sums",
-    "sunglasses",
-    "sunless",
-    "suns",
-    "sunscreen",
-    "sunscreens",
-    "superadded",
-    "superclass",
-    "supercooled",
-    "supercooling",
-    "supersession",
-    "supervisee",
-    "supervisees",
-    "suppleness",
-    "suppress",
-    "suppressant",
-    "suppressed",
-    "suppresses",
-    "suppressing",
-    "suppression",
-    "suppressive",
-    "suppressor",
-    "suppressors",
-    "sura",
-    "suraj",
-    "surat",
-    "sureness",
-    "surf",
-    "surpass",
-    "surpassed",
-    "surpasses",
-    "surpassing",
-    "surratt",
-    "surreal",
-    "surrealism",
-    "surrealist",
-    "surrealistic",
-    "surrealists",
-    "surrender",
-    "surrendered",
-    "surrendering",
-    "surrenders",
-    "surreptitious",
-    "surreptitiously",
-    "surrey",
-    "surrogacy",
-    "surrogate",
-    "surrogates",
-    "surround",
-    "surrounded",
-    "surrounding",
-    "surroundings",
-    "surrounds",
-    "surry",
-    "surtees",
-    "susa",
-    "susan",
-    "susana",
-    "suso",
-    "suspiciousness",
-    "sussex",
-    "susskind",
-    "sussman",
-    "susy",
-    "sutcliffe",
-    "suttee",
-    "svensson",
-    "swaddled",
-    "swaddling",
-    "swans",
-    "swaps",
-    "swarm",
-    "swarms",
-    "sways",
-    "sweeney",
-    "sweeny",
-    "sweep",
-    "sweeper",
-    "sweepers",
-    "sweeping",
-    "sweeps",
-    "sweepstakes",
-    "sweet",
-    "sweetbreads",
-    "sweeten",
-    "sweetened",
-    "sweetener",
-    "sweeteners",
-    "sweetening",
-    "sweeter",
-    "sweetest",
-    "sweetheart",
-    "sweethearts",
-    "sweetie",
-    "sweetly",
-    "sweetman",
-    "sweetmeats",
-    "sweetness",
-    "sweets",
-    "sweetwater",
-    "sweezy",
-    "swets",
-    "swidden",
-    "swiftness",
-    "swiss",
-    "swoon",
-    "swooned",
-    "swooning",
-    "swoop",
-    "swooped",
-    "swooping",
-    "swoops",
-    "sworn",
-    "symptomless",
-    "syst",
-    "taa",
-    "taal",
-    "tablespoon",
-    "tablespoonful",
-    "tablespoons",
-    "taboo",
-    "tabooed",
-    "taboos",
-    "tachyarrhythmias",
-    "tactless",
-    "taddeo",
-    "taff",
-    "taffeta",
-    "taffy",
-    "takeoff",
-    "takeoffs",
-    "taliaferro",
-    "tallahassee",
-    "tama",
-    "tamar",
-    "tana",
-    "tanah",
-    "tanga",
-    "tanglewood",
-    "tans",
-    "taos",
-    "tapas",
-    "taproot",
-    "taps",
-    "tara",
-    "tarai",
-    "tardiness",
-    "tari",
-    "tariff",
-    "tariffs",
-    "tarn",
-    "taro",
-    "tarr",
-    "tarragon",
-    "tarragona",
-    "tarrant",
-    "tarred",
-    "tarried",
-    "tarrow",
-    "tarry",
-    "tarrytown",
-    "tars",
-    "tartuffe",
-    "tary",
-    "tass",
-    "tassel",
-    "tassels",
-    "tasso",
-    "tasteless",
-    "tattoo",
-    "tattooed",
-    "tattooing",
-    "tattoos",
-    "taussig",
-    "taux",
-    "taxa",
-    "taxi",
-    "taxis",
-    "taxus",
-    "tcdd",
-    "tdd",
-    "tearoom",
-    "teaspoon",
-    "teaspoonful",
-    "teaspoons",
-    "techs",
-    "tedder",
-    "teddington",
-    "teddy",
-    "tee",
-    "teece",
-    "teed",
-    "teel",
-    "teem",
-    "teemed",
-    "teeming",
-    "teems",
-    "teen",
-    "teenage",
-    "teenaged",
-    "teenager",
-    "teenagers",
-    "teens",
-    "teenth",
-    "teeny",
-    "teepee",
-    "tees",
-    "teeter",
-    "teetered",
-    "teetering",
-    "teeth",
-    "teething",
-    "temptress",
-    "tems",
-    "tenderness",
-    "tene",
-    "tener",
-    "tennessean",
-    "tennessee",
-    "tens",
-    "tense",
-    "tenseness",
-    "tenses",
-    "tentativeness",
-    "teos",
-    "tepee",
-    "tepees",
-    "term",
-    "terme",
-    "termes",
-    "terms",
-    "tern",
-    "terns",
-    "terr",
-    "terra",
-    "terrace",
-    "terraced",
-    "terraces",
-    "terracing",
-    "terracotta",
-    "terracottas",
-    "terrae",
-    "terrain",
-    "terrains",
-    "terram",
-    "terran",
-    "terrance",
-    "terrane",
-    "terranes",
-    "terrapin",
-    "terrarium",
-    "terras",
-    "terrazzo",
-    "terre",
-    "terrell",
-    "terrence",
-    "terreno",
-    "terres",
-    "terrestre",
-    "terrestrial",
-    "terrestris",
-    "terri",
-    "terrible",
-    "terribly",
-    "terrier",
-    "terriers",
-    "terrific",
-    "terrifically",
-    "terrified",
-    "terrifies",
-    "terrify",
-    "terrifying",
-    "terrifyingly",
-    "terrigenous",
-    "terrill",
-    "terrine",
-    "terris",
-    "territoire",
-    "territoires",
-    "territorial",
-    "territoriality",
-    "territorially",
-    "territories",
-    "territorio",
-    "territory",
-    "terror",
-    "terrorism",
-    "terrorist",
-    "terroristic",
-    "terrorists",
-    "terrorize",
-    "terrorized",
-    "terrorizing",
-    "terrors",
-    "terry",
-    "ters",
-    "tess",
-    "tessa",
-    "tessellation",
-    "tesserae",
-    "tessie",
-    "tessier",
-    "test",
-    "teste",
-    "testes",
-    "tests",
-    "textbook",
-    "textbooks",
-    "thaddeus",
-    "thaf",
-    "thair",
-    "thais",
-    "thalassaemia",
-    "thalassemia",
-    "thana",
-    "thankfulness",
-    "thankless",
-    "thar",
-    "thasos",
-    "thats",
-    "thaws",
-    "thee",
-    "thefe",
-    "thej",
-    "theme",
-    "themes",
-    "theor",
-    "theos",
-    "ther",
-    "there",
-    "thereof",
-    "therm",
-    "thes",
-    "these",
-    "theseus",
-    "thess",
-    "thessalian",
-    "thessalonians",
-    "thessalonica",
-    "thessaloniki",
-    "thessaly",
-    "thickness",
-    "thicknesses",
-    "thief",
-    "thieme",
-    "thier",
-    "thierry",
-    "thiers",
-    "thiessen",
-    "thinness",
-    "thins",
-    "thir",
-    "thirsk",
-    "thirst",
-    "thirsts",
-    "thirteen",
-    "thirteenth",
-    "thirtythree",
-    "this",
-    "thongs",
-    "thor",
-    "thorac",
-    "thorax",
-    "thorn",
-    "thornberry",
-    "thorns",
-    "thoroughness",
-    "thos",
-    "thoughtfulness",
-    "thoughtless",
-    "thoughtlessly",
-    "thoughtlessness",
-    "three",
-    "threedimensional",
-    "threefold",
-    "threefourths",
-    "threepence",
-    "threepenny",
-    "threequarters",
-    "threes",
-    "threescore",
-    "threesome",
-    "threeyear",
-    "thudded",
-    "thudding",
-    "thuja",
-    "thur",
-    "thurgood",
-    "thurow",
-    "thurs",
-    "thus",
-    "thys",
-    "thyssen",
-    "tias",
-    "tics",
-    "tidiness",
-    "tidsskrift",
-    "tiefe",
-    "tiene",
-    "tier",
-    "tiere",
-    "tierra",
-    "tierras",
-    "tiers",
-    "ties",
-    "tiff",
-    "tiffany",
-    "tiffin",
-    "tightness",
-    "tigress",
-    "tillamook",
-    "timekeeper",
-    "timekeeping",
-    "timeless",
-    "timelessness",
-    "timeliness",
-    "tims",
-    "tins",
-    "tips",
-    "tiredness",
-    "tireless",
-    "tirelessly",
-    "tisch",
-    "tiss",
-    "tissot",
-    "tissue",
-    "tissues",
-    "tisza",
-    "titmuss",
-    "tits",
-    "toast",
-    "toasts",
-    "todd",
-    "toddler",
-    "toddlers",
-    "toddy",
-    "toffee",
-    "toffler",
-    "togetherness",
-    "tojo",
-    "tomo",
-    "tomorrow",
-    "tomorrows",
-    "toms",
-    "tongs",
-    "tono",
-    "tons",
-    "too",
-    "took",
-    "tooke",
-    "tool",
-    "toolbar",
-    "toolbars",
-    "toolbox",
-    "toole",
-    "tooled",
-    "tooley",
-    "tooling",
-    "toolkit",
-    "toolkits",
-    "tools",
-    "toombs",
-    "toomer",
-    "toomey",
-    "toone",
-    "toot",
-    "tooth",
-    "toothache",
-    "toothbrush",
-    "toothbrushes",
-    "toothed",
-    "toothless",
-    "toothpaste",
-    "toothpick",
-    "toothpicks",
-    "toothy",
-    "tooting",
-    "tootsie",
-    "topless",
-    "topos",
-    "tops",
-    "tora",
-    "torchbooks",
-    "tori",
-    "torn",
-    "torno",
-    "toro",
-    "torr",
-    "torrance",
-    "torre",
-    "torrence",
-    "torrens",
-    "torrent",
-    "torrential",
-    "torrents",
-    "torres",
-    "torrey",
-    "torricelli",
-    "torrid",
-    "torrijos",
-    "torrington",
-    "tors",
-    "tosa",
-    "tosi",
-    "toss",
-    "tossed",
-    "tosses",
-    "tossing",
-    "tost",
-    "tots",
-    "toughness",
-    "toussaint",
-    "town",
-    "towns",
-    "tows",
-    "towson",
-    "toynbee",
-    "trackless",
-    "tradeoff",
-    "tradeoffs",
-    "traffic",
-    "trafficked",
-    "traffickers",
-    "trafficking",
-    "trafford",
-    "trainee",
-    "trainees",
-    "tralee",
-    "trams",
-    "trans",
-    "transf",
-    "transferee",
-    "transferees",
-    "transferred",
-    "transferrin",
-    "transferring",
-    "transgress",
-    "transgressed",
-    "transgresses",
-    "transgressing",
-    "transgression",
-    "transgressions",
-    "transgressive",
-    "transgressor",
-    "transgressors",
-    "transitoriness",
-    "transmissibility",
-    "transmissible",
-    "transmission",
-    "transmissions",
-    "transmissivity",
-    "transsexual",
-    "transsexualism",
-    "transsexuals",
-    "transshipment",
-    "transvaal",
-    "trapdoor",
-    "traps",
-    "tras",
-    "trays",
-    "tree",
-    "treed",
-    "treeless",
-    "treeline",
-    "trees",
-    "treetop",
-    "treetops",
-    "treks",
-    "tres",
-    "trespass",
-    "trespassed",
-    "trespasser",
-    "trespassers",
-    "trespasses",
-    "trespassing",
-    "tress",
-    "tresses",
-    "trias",
-    "triassic",
-    "trier",
-    "tries",
-    "trieste",
-    "trims",
-    "trincomalee",
-    "trips",
-    "tris",
-    "trist",
-    "trodden",
-    "trois",
-    "trojan",
-    "trojans",
-    "troop",
-    "trooped",
-    "trooper",
-    "troopers",
-    "trooping",
-    "troops",
-    "trost",
-    "trots",
-    "troubleshoot",
-    "troubleshooter",
-    "troubleshooting",
-    "trousseau",
-    "trow",
-    "truax",
-    "truckee",
-    "trueblood",
-    "truer",
-    "truest",
-    "truffaut",
-    "truffle",
-    "truffles",
-    "truro",
-    "truss",
-    "trussed",
-    "trusses",
-    "trust",
-    "trustee",
-    "trustees",
-    "trusteeship",
-    "trusts",
-    "trustworthiness",
-    "truthfulness",
-    "tryst",
-    "tsee",
-    "tss",
-    "tues",
-    "tufa",
-    "tuff",
-    "tuffs",
-    "tums",
-    "tuns",
-    "tura",
-    "turan",
-    "tureen",
-    "turf",
-    "turfgrass",
-    "turn",
-    "turnoff",
-    "turns",
-    "turret",
-    "turreted",
-    "turrets",
-    "tury",
-    "tuscaloosa",
-    "tuskegee",
-    "tussen",
-    "tussle",
-    "tussock",
-    "twaddle",
-    "twas",
-    "twee",
-    "tweed",
-    "tweeds",
-    "tweedy",
-    "tween",
-    "tweezers",
-    "twentythree",
-    "twiss",
-    "twos",
-    "tycoon",
-    "tycoons",
-    "typee",
-    "typhoon",
-    "typhoons",
-    "tyree",
-    "tyrrell",
-    "tyrrhenian",
-    "uart",
-    "udder",
-    "udders",
-    "uddi",
-    "uffizi",
-    "ugliness",
-    "ujamaa",
-    "ujjain",
-    "ulysses",
-    "umpteenth",
-    "unaddressed",
-    "unaffected",
-    "unaffiliated",
-    "unaffordable",
-    "unassailable",
-    "unassigned",
-    "unassimilated",
-    "unassisted",
-    "unassociated",
-    "unassuming",
-    "unawareness",
-    "unbidden",
-    "unclassified",
-    "uncleanness",
-    "uncompressed",
-    "unconsciousness",
-    "uncooked",
-    "uncooperative",
-    "uncoordinated",
-    "uncorrected",
-    "uncorrelated",
-    "uncorrupted",
-    "uncrossed",
-    "undercarriage",
-    "underclass",
-    "undercooling",
-    "undercurrent",
-    "undercurrents",
-    "underfoot",
-    "underpass",
-    "underrate",
-    "underrated",
-    "underreported",
-    "underreporting",
-    "underrepresentation",
-    "underrepresented",
-    "understaffed",
-    "understood",
-    "undertook",
-    "underwood",
-    "undeterred",
-    "undifferentiated",
-    "undissociated",
-    "undissolved",
-    "undress",
-    "undressed",
-    "undressing",
-    "uneasiness",
-    "unembarrassed",
-    "unerring",
-    "unerringly",
-    "unessential",
-    "unevenness",
-    "unexpectedness",
-    "unexpressed",
-    "unfairness",
-    "unfaithfulness",
-    "unfeeling",
-    "unfitness",
-    "unforeseeable",
-    "unforeseen",
-    "unfree",
-    "unfreedom",
-    "unhappiness",
-    "unheeded",
-    "unhooked",
-    "unhurried",
-    "unhurriedly",
-    "unimpressed",
-    "unimpressive",
-    "uninterrupted",
-    "uninterruptedly",
-    "uniqueness",
-    "unirradiated",
-    "unirrigated",
-    "unissued",
-    "unkindness",
-    "unless",
-    "unlikelihood",
-    "unlikeness",
-    "unlooked",
-    "unmarried",
-    "unnecessarily",
-    "unnecessary",
-    "unneeded",
-    "unofficial",
-    "unofficially",
-    "unpeeled",
-    "unpleasantness",
-    "unpreparedness",
-    "unprepossessing",
-    "unprocessed",
-    "unprofessional",
-    "unreasonableness",
-    "unredeemed",
-    "unresponsiveness",
-    "unrighteousness",
-    "unrra",
-    "unruffled",
-    "unschooled",
-    "unseeing",
-    "unseemly",
-    "unseen",
-    "unselfishness",
-    "unsoundness",
-    "unstarred",
-    "unsteadiness",
-    "unstressed",
-    "unsuccessful",
-    "unsuccessfully",
-    "unsurpassable",
-    "unsurpassed",
-    "unsweetened",
-    "unterricht",
-    "untidiness",
-    "untrodden",
-    "unwarrantable",
-    "unwarranted",
-    "unwillingness",
-    "unworthiness",
-    "upkeep",
-    "upperclass",
-    "upperclassmen",
-    "uprightness",
-    "uproot",
-    "uprooted",
-    "uprooting",
-    "urns",
-    "urrutia",
-    "urry",
-    "usaaf",
-    "usaf",
-    "uscs",
-    "usee",
-    "usefulness",
-    "useless",
-    "uselessly",
-    "uselessness",
-    "usfs",
-    "usps",
-    "uss",
-    "ussher",
-    "ussr",
-    "ustr",
-    "usus",
-    "vaal",
-    "vaca",
-    "vagueness",
-    "vais",
-    "vajpayee",
-    "vallee",
-    "valueless",
-    "vana",
-    "vanessa",
-    "vans",
-    "vara",
-    "vari",
-    "varma",
-    "varna",
-    "varnas",
-    "varro",
-    "vars",
-    "varus",
-    "vary",
-    "vasa",
-    "vasc",
-    "vaso",
-    "vasopressin",
-    "vasopressor",
-    "vassal",
-    "vassalage",
-    "vassals",
-    "vassar",
-    "vassily",
-    "vast",
-    "vastness",
-    "vasu",
-    "vats",
-    "vaux",
-    "vdd",
-    "veces",
-    "vedder",
-    "vee",
-    "veeck",
-    "veen",
-    "veena",
-    "veer",
-    "veered",
-    "veering",
-    "veers",
-    "velveteen",
-    "vendee",
-    "veneer",
-    "veneered",
-    "veneers",
-    "vere",
-    "vereeniging",
-    "verfasser",
-    "verfassung",
-    "vermeer",
-    "vermes",
-    "vern",
-    "verne",
-    "verner",
-    "vero",
-    "veroff",
-    "verre",
-    "verres",
-    "verrier",
-    "verrocchio",
-    "verrucous",
-    "verry",
-    "vers",
-    "versts",
-    "vessel",
-    "vessels",
-    "vest",
-    "vests",
-    "vets",
-    "viana",
-    "vias",
-    "viciousness",
-    "vicissitudes",
-    "victimhood",
-    "victimless",
-    "videocassette",
-    "videocassettes",
-    "viene",
-    "viens",
-    "vier",
-    "vies",
-    "vileness",
-    "villarreal",
-    "vindictiveness",
-    "vins",
-    "vipassana",
-    "vips",
-    "virus",
-    "viscountess",
-    "visscher",
-    "visser",
-    "vitesse",
-    "vividness",
-    "vocs",
-    "voiceless",
-    "voir",
-    "vois",
-    "voix",
-    "volksraad",
-    "volterra",
-    "voluntariness",
-    "volunteer",
-    "volunteered",
-    "volunteering",
-    "volunteerism",
-    "volunteers",
-    "voluptuousness",
-    "voodoo",
-    "voor",
-    "voorhees",
-    "voorhis",
-    "voraussetzungen",
-    "voss",
-    "vows",
-    "vreeland",
-    "vroom",
-    "vss",
-    "vues",
-    "waa",
-    "waal",
-    "waals",
-    "wadded",
-    "waddell",
-    "wadden",
-    "wadding",
-    "waddington",
-    "waddle",
-    "waddled",
-    "waddling",
-    "waffen",
-    "waffle",
-    "waffles",
-    "wagstaff",
-    "wahoo",
-    "waitress",
-    "waitresses",
-    "wakefulness",
-    "walloon",
-    "wantonness",
-    "wardroom",
-    "wariness",
-    "warr",
-    "warrant",
-    "warranted",
-    "warranties",
-    "warranting",
-    "warrantless",
-    "warranto",
-    "warrants",
-    "warranty",
-    "warre",
-    "warred",
-    "warren",
-    "warrendale",
-    "warrens",
-    "warrenton",
-    "warri",
-    "warrick",
-    "warring",
-    "warrington",
-    "warrior",
-    "warriors",
-    "wars",
-    "washroom",
-    "washrooms",
-    "wass",
-    "wasser",
-    "wasserman",
-    "wassermann",
-    "wasserstein",
-    "wassily",
-    "wasson",
-    "wastefulness",
-    "watchfulness",
-    "watercress",
-    "waterless",
-    "waterloo",
-    "waterproof",
-    "waterproofing",
-    "waterwheel",
-    "waywardness",
-    "weakness",
-    "weaknesses",
-    "weariness",
-    "weatherproof",
-    "wedded",
-    "weddell",
-    "wedderburn",
-    "wedding",
-    "weddings",
-    "wedgwood",
-    "wee",
-    "weed",
-    "weeded",
-    "weeden",
-    "weeding",
-    "weedon",
-    "weeds",
-    "weedy",
-    "week",
-    "weekday",
-    "weekdays",
-    "weekend",
-    "weekends",
-    "weekes",
-    "weeklies",
-    "weeklong",
-    "weekly",
-    "weeks",
-    "weel",
-    "weems",
-    "ween",
-    "weep",
-    "weeping",
-    "weeps",
-    "weepy",
-    "weevil",
-    "weevils",
-    "weierstrass",
-    "weightless",
-    "weightlessness",
-    "weirdness",
-    "weiss",
-    "weisskopf",
-    "weissman",
-    "weissmann",
-    "wellness",
-    "wemyss",
-    "wenatchee",
-    "wessel",
-    "wessels",
-    "wessex",
-    "wesson",
-    "westbrook",
-    "westergaard",
-    "westwood",
-    "wetness",
-    "wff",
-    "wheatgrass",
-    "wheedle",
-    "wheedling",
-    "wheel",
-    "wheelbarrow",
-    "wheelbarrows",
-    "wheelbase",
-    "wheelchair",
-    "wheelchairs",
-    "wheeled",
-    "wheeler",
-    "wheelers",
-    "wheelhouse",
-    "wheeling",
-    "wheelock",
-    "wheels",
-    "wheelwright",
-    "wheeze",
-    "wheezed",
-    "wheezes",
-    "wheezing",
-    "wherry",
-    "whiff",
-    "whirlpool",
-    "whirlpools",
-    "whirr",
-    "whirred",
-    "whirring",
-    "whiteness",
-    "wholeness",
-    "wholesomeness",
-    "whoop",
-    "whooped",
-    "whooping",
-    "whoops",
-    "whoosh",
-    "wickedness",
-    "widdowson",
-    "widowhood",
-    "wildebeest",
-    "wilderness",
-    "wildernesses",
-    "wildness",
-    "wildwood",
-    "wilfulness",
-    "wilhelmstrasse",
-    "willfulness",
-    "willingness",
-    "windlass",
-    "windless",
-    "windowless",
-    "windscreen",
-    "wineglass",
-    "wingless",
-    "wintergreen",
-    "wireless",
-    "wiss",
-    "wisse",
-    "wissen",
-    "wissenschaft",
-    "wissenschaften",
-    "wissenschaftliche",
-    "wissenschaftlichen",
-    "wissler",
-    "wistfulness",
-    "witherspoon",
-    "withstood",
-    "witless",
-    "witness",
-    "witnessed",
-    "witnesses",
-    "witnessing",
-    "wofford",
-    "wolff",
-    "wolffian",
-    "womanhood",
-    "womanliness",
-    "woo",
-    "wood",
-    "woodall",
-    "woodard",
-    "woodbine",
-    "woodblock",
-    "woodbridge",
-    "woodburn",
-    "woodbury",
-    "woodcarving",
-    "woodchuck",
-    "woodcock",
-    "woodcraft",
-    "woodcut",
-    "woodcuts",
-    "woodcutter",
-    "woodcutters",
-    "wooded",
-    "wooden",
-    "woodford",
-    "woodhead",
-    "woodhouse",
-    "woodhull",
-    "woodland",
-    "woodlands",
-    "woodlawn",
-    "woodley",
-    "woodlot",
-    "woodlots",
-    "woodman",
-    "woodpecker",
-    "woodpeckers",
-    "woodpile",
-    "woodring",
-    "woodrow",
-    "woodruff",
-    "woods",
-    "woodshed",
-    "woodside",
-    "woodsman",
-    "woodsmen",
-    "woodson",
-    "woodstock",
-    "woodsworth",
-    "woodville",
-    "woodward",
-    "woodwind",
-    "woodwinds",
-    "woodwork",
-    "woodworkers",
-    "woodworking",
-    "woodworth",
-    "woody",
-    "wooed",
-    "woof",
-    "woogie",
-    "wooing",
-    "wool",
-    "wooldridge",
-    "woolen",
-    "woolens",
-    "wooley",
-    "woolf",
-    "woolfolk",
-    "woolfs",
-    "woolgar",
-    "woollcott",
-    "woollen",
-    "woollens",
-    "woolley",
-    "woolly",
-    "woolman",
-    "wools",
-    "woolsey",
-    "woolwich",
-    "woolworth",
-    "wooly",
-    "wooster",
-    "wooten",
-    "wootton",
-    "woozy",
-    "wordiness",
-    "wordless",
-    "wordlessly",
-    "worf",
-    "workbook",
-    "workbooks",
-    "workingclass",
-    "workroom",
-    "worksheet",
-    "worksheets",
-    "workweek",
-    "worldliness",
-    "worm",
-    "worms",
-    "wormwood",
-    "worn",
-    "worrall",
-    "worrell",
-    "worried",
-    "worriedly",
-    "worries",
-    "worrisome",
-    "worry",
-    "worrying",
-    "worst",
-    "wort",
-    "worthiness",
-    "worthless",
-    "worthlessness",
-    "wretchedness",
-    "wrongness",
-    "wrst",
-    "wschr",
-    "wss",
-    "wulff",
-    "ww",
-    "wwf",
-    "wwi",
-    "wwii",
-    "wyckoff",
-    "wycliffe",
-    "wyss",
-    "xanax",
-    "xerox",
-    "xx",
-    "xxe",
-    "xxi",
-    "xxii",
-    "xxiii",
-    "xxiv",
-    "xxix",
-    "xxv",
-    "xxvi",
-    "xxvii",
-    "xxviii",
-    "xxx",
-    "xxxi",
-    "xxxii",
-    "xxxiii",
-    "xxxiv",
-    "xxxix",
-    "xxxv",
-    "xxxvi",
-    "xxxvii",
-    "xxxviii",
-    "xxxx",
-    "xxxxx",
-    "xxy",
-    "yaakov",
-    "yahoo",
-    "yahoos",
-    "yankee",
-    "yankees",
-    "yarra",
-    "yarrow",
-    "yasser",
-    "yassin",
-    "yazoo",
-    "yearbook",
-    "yearbooks",
-    "yearround",
-    "yee",
-    "yere",
-    "yessir",
-    "yiddish",
-    "yoo",
-    "yoon",
-    "yossi",
-    "youngblood",
-    "youssef",
-    "youthfulness",
-    "zasshi",
-    "zebedee",
-    "zee",
-    "zeeb",
-    "zeeland",
-    "zeeman",
-    "zeiss",
-    "ziff",
-    "zinsser",
-    "zoo",
-    "zoogeography",
-    "zook",
-    "zool",
-    "zoological",
-    "zoologie",
-    "zoologist",
-    "zoologists",
-    "zoology",
-    "zoom",
-    "zoomed",
-    "zooming",
-    "zoomorphic",
-    "zooms",
-    "zooplankton",
-    "zoos",
-    "zoospores",
-    "zoot",
-    "zorro",
-    "zusammenfassung",
-];
+use super::dawg::Dawg;
+
+static WORDS: Dawg = Dawg::new(include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/telex_doubles.dawg"
+)));
 
 /// Check if word contains Telex patterns that should auto-restore
 pub fn contains(word: &str) -> bool {
-    WORDS.binary_search(&word).is_ok()
+    WORDS.contains(word)
 }
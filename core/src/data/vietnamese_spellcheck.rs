@@ -1,31 +1,98 @@
 //! Vietnamese Spell Checking Module
 //!
-//! Uses HashSet-based word lookup for efficient Vietnamese word validation.
-//! Memory-efficient: ~0.5MB vs ~5.5MB with full Hunspell implementation.
+//! Uses a build-time compiled word graph (see `dawg`) for Vietnamese word validation.
+//! No startup parsing and no resident HashSet, vs ~5.5MB with full Hunspell implementation.
 //!
-//! Supports both orthography styles:
+//! Supports both orthography styles from a single DauMoi dictionary:
 //! - DauMoi (modern): hoà, thuý
 //! - DauCu (traditional): hòa, thúy
+//!
+//! The two styles only differ in tone placement on oa/oe/uy, so a query word is
+//! normalized to modern placement (via `Phonology::find_tone_position`) before lookup.
+//! Mixed house styles (`TonePlacement`, e.g. modern oa/oe with traditional uy) are
+//! checked per rhyme the same way.
+//!
+//! `suggest` ranks dictionary words near a misspelling, with Vietnamese slips
+//! (tone, tone position, horn/circumflex, ch/tr, s/x, d/gi/r, n/ng, i/y) ranked
+//! before plain keystroke typos.
 
-use std::collections::HashSet;
-use std::sync::LazyLock;
+use std::borrow::Cow;
 
-// Embed dictionary files into binary
-const DIC_DAUMOI: &str = include_str!("dictionaries/vi_daumoi.dic");
-const DIC_DAUCU: &str = include_str!("dictionaries/vi_daucu.dic");
+use super::chars::{self, mark, ParsedChar};
+use super::dawg::Dawg;
+use super::edits::{deletions, swaps};
+use super::keys;
+use super::vowel::{Modifier, Phonology, TonePlacement, Vowel};
 
-/// Parse .dic file into HashSet (skip first line which is word count)
-fn parse_dic_to_hashset(dic_content: &'static str) -> HashSet<&'static str> {
-    dic_content.lines().skip(1).collect()
-}
+/// DauMoi (modern) dictionary, compiled by build.rs (DauCu is derived on lookup)
+static DICT_DAUMOI: Dawg = Dawg::new(include_bytes!(concat!(env!("OUT_DIR"), "/vi_daumoi.dawg")));
+
+/// Normalize a word's tone mark to modern (DauMoi) placement
+///
+/// - modern rhyme in `style`: word must already use modern placement (hoà)
+/// - traditional rhyme in `style`: word must use traditional placement (hòa), which
+///   is moved to the modern position so it can be looked up in the DauMoi dictionary
+///
+/// Returns `None` when the mark sits where the requested style would not put it
+/// (e.g. "hoà" in traditional style), so the word is rejected like a dictionary miss.
+/// Words without a mark or with unparseable characters are returned unchanged.
+pub(crate) fn normalize_tone_position(word: &str, style: TonePlacement) -> Option<Cow<'_, str>> {
+    let Some(mut letters) = chars::parse_word(word) else {
+        return Some(Cow::Borrowed(word));
+    };
 
-/// Lazy-loaded DauMoi (modern) dictionary - ~0.5MB memory
-static DICT_DAUMOI: LazyLock<HashSet<&'static str>> =
-    LazyLock::new(|| parse_dic_to_hashset(DIC_DAUMOI));
+    let Some((mark_pos, mark_value)) = letters
+        .iter()
+        .enumerate()
+        .find(|(_, c)| c.mark != mark::NONE && keys::is_vowel(c.key))
+        .map(|(i, c)| (i, c.mark))
+    else {
+        return Some(Cow::Borrowed(word));
+    };
 
-/// Lazy-loaded DauCu (traditional) dictionary - ~0.5MB memory
-static DICT_DAUCU: LazyLock<HashSet<&'static str>> =
-    LazyLock::new(|| parse_dic_to_hashset(DIC_DAUCU));
+    let vowels: Vec<Vowel> = letters
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| keys::is_vowel(c.key))
+        .map(|(pos, c)| {
+            let modifier = match c.tone {
+                chars::tone::CIRCUMFLEX => Modifier::Circumflex,
+                chars::tone::HORN => Modifier::Horn,
+                _ => Modifier::None,
+            };
+            Vowel::new(c.key, modifier, pos)
+        })
+        .collect();
+    let last_vowel_pos = vowels.last().map(|v| v.pos).unwrap_or(0);
+    let has_final = letters[last_vowel_pos + 1..]
+        .iter()
+        .any(|c| keys::is_consonant(c.key));
+    let has_qu = letters
+        .iter()
+        .position(|c| c.key == keys::U)
+        .is_some_and(|i| i > 0 && letters[i - 1].key == keys::Q);
+    let has_gi = letters.len() >= 3
+        && letters[0].key == keys::G
+        && letters[1].key == keys::I
+        && keys::is_vowel(letters[2].key);
+    let modern_pos = Phonology::find_tone_position(&vowels, has_final, true, has_qu, has_gi);
+    let style_pos = Phonology::find_tone_position(&vowels, has_final, style, has_qu, has_gi);
+
+    // Styles agree on this syllable: nothing to normalize
+    if modern_pos == style_pos {
+        return Some(Cow::Borrowed(word));
+    }
+    // Styles disagree: mark must be where the requested style puts it
+    if mark_pos != style_pos {
+        return None;
+    }
+
+    letters[mark_pos].mark = mark::NONE;
+    if let Some(c) = letters.get_mut(modern_pos) {
+        c.mark = mark_value;
+    }
+    Some(Cow::Owned(chars::compose_word(&letters)))
+}
 
 /// Check if word starts with foreign consonant (z, w, j, f)
 /// These consonants are not part of standard Vietnamese alphabet
@@ -38,11 +105,16 @@ fn starts_with_foreign_consonant(word: &str) -> bool {
 
 /// Check if a word is valid Vietnamese with style and foreign consonants option
 ///
-/// - `use_modern = true`: Use DauMoi dictionary (modern style: oà, uý)
-/// - `use_modern = false`: Use DauCu dictionary (traditional style: òa, úy)
+/// - `placement = true`: Modern style (oà, uý)
+/// - `placement = false`: Traditional style (òa, úy), normalized to modern before lookup
+/// - `placement` as a `TonePlacement`: style chosen per rhyme (oa, oe, uy)
 /// - `allow_foreign = true`: Allow words starting with z/w/j/f
 /// - `allow_foreign = false`: Reject words starting with z/w/j/f
-pub fn check_with_style_and_foreign(word: &str, use_modern: bool, allow_foreign: bool) -> bool {
+pub fn check_with_style_and_foreign(
+    word: &str,
+    placement: impl Into<TonePlacement>,
+    allow_foreign: bool,
+) -> bool {
     if word.is_empty() {
         return false;
     }
@@ -52,20 +124,280 @@ pub fn check_with_style_and_foreign(word: &str, use_modern: bool, allow_foreign:
         return false;
     }
 
-    let dict = if use_modern {
-        &*DICT_DAUMOI
-    } else {
-        &*DICT_DAUCU
+    // Case-insensitive lookup (dictionary stores lowercase)
+    match normalize_tone_position(word, placement.into()) {
+        Some(normalized) => DICT_DAUMOI.contains_ignore_case(&normalized),
+        None => false,
+    }
+}
+
+/// Vietnamese words (modern style) starting with `prefix`, up to `max`
+pub fn words_with_prefix(prefix: &str, max: usize) -> Vec<String> {
+    DICT_DAUMOI.words_with_prefix(prefix, max)
+}
+
+/// Cost of a tone mark on the wrong vowel ("tòan" → "toàn")
+const COST_MARK_MOVED: u8 = 1;
+/// Cost of a missing or wrong diacritic: tone mark, horn/circumflex/breve, đ
+const COST_DIACRITIC: u8 = 2;
+/// Cost of dropping a diacritic the user typed
+const COST_DIACRITIC_DROPPED: u8 = 3;
+/// Cost of a spelling confusion: ch/tr, s/x, d/gi/r, n/ng, i/y
+const COST_CONFUSION: u8 = 4;
+/// Cost of a keystroke typo: adjacent letters swapped, extra letter
+const COST_KEYSTROKE: u8 = 5;
+/// Suggestions further away than this are dropped
+/// (e.g. "nguoi" → "người" is three missing diacritics)
+const MAX_SUGGEST_COST: u8 = 8;
+
+/// Replace `len` chars at `start` by plain `keys`, keeping the case of the replaced chars
+fn replace_keys(
+    letters: &[ParsedChar],
+    start: usize,
+    len: usize,
+    new_keys: &[u16],
+) -> Vec<ParsedChar> {
+    let caps = letters[start].caps;
+    let mut out = letters[..start].to_vec();
+    for (i, &key) in new_keys.iter().enumerate() {
+        let mut c = letters
+            .get(start + i.min(len.saturating_sub(1)))
+            .copied()
+            .unwrap_or_default();
+        c.key = key;
+        c.caps = if i < len {
+            letters[start + i].caps
+        } else {
+            caps
+        };
+        c.stroke = false;
+        if !keys::is_vowel(key) {
+            c.tone = 0;
+            c.mark = mark::NONE;
+        }
+        out.push(c);
+    }
+    out.extend_from_slice(&letters[start + len..]);
+    out
+}
+
+/// Same word with one confusable spelling swapped (ch/tr, s/x, d/gi/r, n/ng, i/y)
+fn confusions(letters: &[ParsedChar]) -> Vec<Vec<ParsedChar>> {
+    let keys_of: Vec<u16> = letters.iter().map(|c| c.key).collect();
+    let plain_d = letters
+        .first()
+        .is_some_and(|c| c.key == keys::D && !c.stroke);
+    let gi = keys_of.starts_with(&[keys::G, keys::I])
+        && keys_of.get(2).is_some_and(|&k| keys::is_vowel(k));
+    let mut out = Vec::new();
+
+    // Initial consonants
+    let initial: &[(&[u16], &[&[u16]])] = &[
+        (&[keys::C, keys::H], &[&[keys::T, keys::R]]),
+        (&[keys::T, keys::R], &[&[keys::C, keys::H]]),
+        (&[keys::S], &[&[keys::X]]),
+        (&[keys::X], &[&[keys::S]]),
+        (&[keys::R], &[&[keys::D], &[keys::G, keys::I]]),
+    ];
+    for (from, tos) in initial {
+        if keys_of.starts_with(from) {
+            for to in *tos {
+                out.push(replace_keys(letters, 0, from.len(), to));
+            }
+        }
+    }
+    if plain_d {
+        out.push(replace_keys(letters, 0, 1, &[keys::G, keys::I]));
+        out.push(replace_keys(letters, 0, 1, &[keys::R]));
+    }
+    if gi {
+        out.push(replace_keys(letters, 0, 2, &[keys::D]));
+        out.push(replace_keys(letters, 0, 2, &[keys::R]));
+    }
+
+    // Final n/ng
+    let n = letters.len();
+    if keys_of.ends_with(&[keys::N, keys::G]) {
+        out.push(replace_keys(letters, n - 2, 2, &[keys::N]));
+    } else if keys_of.last() == Some(&keys::N) {
+        out.push(replace_keys(letters, n - 1, 1, &[keys::N, keys::G]));
+    }
+
+    // i/y (keeps the tone mark)
+    for (i, c) in letters.iter().enumerate() {
+        let other = match c.key {
+            keys::I => keys::Y,
+            keys::Y => keys::I,
+            _ => continue,
+        };
+        let mut swapped = letters.to_vec();
+        swapped[i].key = other;
+        out.push(swapped);
+    }
+    out
+}
+
+/// Cost of turning one diacritic slot from `had` into `has` (`same`: unchanged)
+fn diacritic_cost(had: bool, has: bool, same: bool) -> u8 {
+    match (had, has) {
+        _ if same => 0,
+        (false, false) => 0,
+        (true, false) => COST_DIACRITIC_DROPPED,
+        _ => COST_DIACRITIC,
+    }
+}
+
+/// Dictionary words that differ from `base` only in diacritics, within `budget`
+///
+/// Tries every vowel tone, one tone mark on any vowel and đ/d.
+fn diacritic_variants(
+    base: &[ParsedChar],
+    budget: u8,
+    is_valid: &dyn Fn(&str) -> bool,
+) -> Vec<(u8, String)> {
+    let vowels: Vec<usize> = (0..base.len())
+        .filter(|&i| keys::is_vowel(base[i].key))
+        .collect();
+    let strokes: Vec<usize> = (0..base.len())
+        .filter(|&i| base[i].key == keys::D)
+        .collect();
+    // Longer syllables do not exist; bail out before the product explodes
+    if vowels.len() > 3 || strokes.len() > 1 {
+        return Vec::new();
+    }
+    let tone_options: Vec<Vec<u8>> = vowels
+        .iter()
+        .map(|&i| {
+            [
+                chars::tone::NONE,
+                chars::tone::CIRCUMFLEX,
+                chars::tone::HORN,
+            ]
+            .into_iter()
+            .filter(|&t| chars::to_char(base[i].key, false, t, 0).is_some())
+            .collect()
+        })
+        .collect();
+    let old_mark = vowels
+        .iter()
+        .find(|&&i| base[i].mark != mark::NONE)
+        .map(|&i| (i, base[i].mark));
+    let mut marks: Vec<Option<(usize, u8)>> = vec![None];
+    for &i in &vowels {
+        for value in mark::SAC..=mark::NANG {
+            marks.push(Some((i, value)));
+        }
+    }
+
+    let mut out = Vec::new();
+    let combos: usize = tone_options.iter().map(Vec::len).product();
+    for combo in 0..combos {
+        let mut letters = base.to_vec();
+        let mut cost = 0;
+        let mut rest = combo;
+        for (v, options) in vowels.iter().zip(&tone_options) {
+            let tone = options[rest % options.len()];
+            rest /= options.len();
+            cost += diacritic_cost(letters[*v].tone != 0, tone != 0, letters[*v].tone == tone);
+            letters[*v].tone = tone;
+        }
+        for &new_mark in &marks {
+            let mark_cost = match (old_mark, new_mark) {
+                (Some((pos, value)), Some((new_pos, new_value))) => {
+                    u8::from(pos != new_pos) * COST_MARK_MOVED
+                        + u8::from(value != new_value) * COST_DIACRITIC
+                }
+                (old, new) => diacritic_cost(old.is_some(), new.is_some(), false),
+            };
+            for stroke in [false, true] {
+                if stroke && strokes.is_empty() {
+                    continue;
+                }
+                let stroke_cost = strokes.first().map_or(0, |&i| {
+                    diacritic_cost(base[i].stroke, stroke, base[i].stroke == stroke)
+                });
+                let total = cost + mark_cost + stroke_cost;
+                if total > budget {
+                    continue;
+                }
+                let mut candidate = letters.clone();
+                for &i in &vowels {
+                    candidate[i].mark = mark::NONE;
+                }
+                if let Some((i, value)) = new_mark {
+                    candidate[i].mark = value;
+                }
+                if let Some(&i) = strokes.first() {
+                    candidate[i].stroke = stroke;
+                }
+                let text = chars::compose_word(&candidate);
+                if is_valid(&text) {
+                    out.push((total, text));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Ranked dictionary suggestions for a misspelled word, best first, up to `max`
+///
+/// - `placement`: suggestions use modern (hoà) or traditional (hòa) tone placement,
+///   or a per-rhyme `TonePlacement`
+///
+/// Candidates are the word with its diacritics changed, one spelling confusion
+/// (ch/tr, s/x, d/gi/r, n/ng, i/y) or one keystroke typo undone. Diacritic
+/// slips rank first, then confusions, then typos; ties keep generation order.
+/// Case follows the input. The word itself is never suggested.
+pub fn suggest(word: &str, placement: impl Into<TonePlacement>, max: usize) -> Vec<String> {
+    let placement = placement.into();
+    let Some(letters) = chars::parse_word(word) else {
+        return Vec::new();
     };
+    if letters.is_empty() || max == 0 {
+        return Vec::new();
+    }
+    let is_valid = |w: &str| check_with_style_and_foreign(w, placement, true);
 
-    // Case-insensitive lookup (dictionary stores lowercase)
-    let word_lower = word.to_lowercase();
-    dict.contains(word_lower.as_str())
+    let mut bases = vec![(0, letters.clone())];
+    bases.extend(
+        confusions(&letters)
+            .into_iter()
+            .map(|l| (COST_CONFUSION, l)),
+    );
+    // Keystroke typos, same edits as autocorrect: adjacent letters swapped, one removed
+    bases.extend(
+        swaps(&letters, |c| c.key)
+            .into_iter()
+            .chain(deletions(&letters))
+            .map(|l| (COST_KEYSTROKE, l)),
+    );
+
+    let mut ranked: Vec<(u8, String)> = Vec::new();
+    for (base_cost, base) in bases {
+        for (cost, text) in diacritic_variants(&base, MAX_SUGGEST_COST - base_cost, &is_valid) {
+            ranked.push((base_cost + cost, text));
+        }
+    }
+    // Stable: equal costs keep generation order
+    ranked.sort_by_key(|(cost, _)| *cost);
+
+    let mut out: Vec<String> = Vec::new();
+    for (_, text) in ranked {
+        if text != word && !out.contains(&text) {
+            out.push(text);
+            if out.len() == max {
+                break;
+            }
+        }
+    }
+    out
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::StyledRhyme;
 
     #[test]
     fn test_common_vietnamese_words() {
@@ -117,4 +449,84 @@ mod tests {
         // Just verify they don't get rejected by the foreign consonant check
         assert!(!check_with_style_and_foreign("zá", false, true)); // Not in dict, but passes foreign check
     }
+
+    #[test]
+    fn test_tone_style_normalization() {
+        // Traditional placement accepted only in traditional style
+        assert!(check_with_style_and_foreign("hòa", false, false));
+        assert!(check_with_style_and_foreign("thúy", false, false));
+        assert!(!check_with_style_and_foreign("hòa", true, false));
+        // Modern placement accepted only in modern style
+        assert!(check_with_style_and_foreign("hoà", true, false));
+        assert!(check_with_style_and_foreign("thuý", true, false));
+        assert!(!check_with_style_and_foreign("hoà", false, false));
+        // Syllables where both styles agree work in either style
+        assert!(check_with_style_and_foreign("hoàn", true, false));
+        assert!(check_with_style_and_foreign("hoàn", false, false));
+        assert!(check_with_style_and_foreign("quý", true, false));
+        assert!(check_with_style_and_foreign("quý", false, false));
+    }
+
+    #[test]
+    fn test_mixed_tone_placement() {
+        // Modern oa/oe, traditional uy
+        let style = TonePlacement::MODERN.with(StyledRhyme::Uy, false);
+        assert!(check_with_style_and_foreign("hoà", style, false));
+        assert!(check_with_style_and_foreign("khoẻ", style, false));
+        assert!(check_with_style_and_foreign("thúy", style, false));
+        assert!(!check_with_style_and_foreign("hòa", style, false));
+        assert!(!check_with_style_and_foreign("thuý", style, false));
+    }
+
+    #[test]
+    fn test_suggest_diacritic_slips() {
+        // Missing circumflex, misplaced mark, wrong mark
+        assert_eq!(suggest("tiéng", false, 1), ["tiếng"]);
+        assert_eq!(suggest("tòan", false, 1), ["toàn"]);
+        assert!(suggest("bàng", false, 5).contains(&"bằng".to_string()));
+        // Typed without any diacritics
+        assert!(suggest("nguoi", false, 5).contains(&"người".to_string()));
+    }
+
+    #[test]
+    fn test_suggest_follows_style() {
+        assert_eq!(suggest("hòa", true, 1), ["hoà"]);
+        assert_eq!(suggest("hoà", false, 1), ["hòa"]);
+    }
+
+    #[test]
+    fn test_suggest_confusions() {
+        assert!(suggest("chường", false, 6).contains(&"trường".to_string()));
+        assert!(suggest("xáng", false, 6).contains(&"sáng".to_string()));
+        assert!(suggest("kĩ", false, 6).contains(&"kỹ".to_string()));
+    }
+
+    #[test]
+    fn test_suggest_d_gi_r_ranking() {
+        // d/gi/r confusions rank after diacritic slips, before keystroke typos
+        assert_eq!(suggest("giừng", false, 2), ["dừng", "rừng"]);
+        let s = suggest("dúp", false, 6);
+        let pos = |w: &str| s.iter().position(|x| x == w).unwrap();
+        assert_eq!(s[0], "đúp");
+        assert!(pos("giúp") < pos("úp"));
+        assert!(pos("rúp") < pos("úp"));
+    }
+
+    #[test]
+    fn test_suggest_ranking_and_case() {
+        // Diacritic slips before keystroke typos
+        let s = suggest("tôio", false, 6);
+        assert_eq!(s[0], "tôi");
+        assert_eq!(suggest("hcào", false, 1), ["chào"]);
+        assert_eq!(suggest("Viêt", false, 2), ["Viết", "Việt"]);
+    }
+
+    #[test]
+    fn test_suggest_edge_cases() {
+        assert!(suggest("", false, 5).is_empty());
+        assert!(suggest("tiéng", false, 0).is_empty());
+        assert!(suggest("a1", false, 5).is_empty());
+        assert_eq!(suggest("tiéng", false, 3).len(), 3);
+        assert!(!suggest("việt", false, 10).contains(&"việt".to_string()));
+    }
 }
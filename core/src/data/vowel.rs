@@ -251,6 +251,82 @@ pub const TRIPHTHONG_PATTERNS: &[TriphthongTonePattern] = &[
     }, // uyê: khuyến, quyền
 ];
 
+/// Rhymes whose tone placement differs between orthography styles
+///
+/// Modern (DauMoi) puts the tone on the main vowel (hoà, khoẻ, thuý), traditional
+/// (DauCu) on the medial (hòa, khỏe, thúy). Closed syllables and qu- agree (hoàn, quý).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StyledRhyme {
+    Oa = 0,
+    Oe = 1,
+    Uy = 2,
+}
+
+impl StyledRhyme {
+    pub const ALL: [StyledRhyme; 3] = [StyledRhyme::Oa, StyledRhyme::Oe, StyledRhyme::Uy];
+
+    /// Rhyme of a vowel key pair, if its placement is style-dependent
+    pub fn from_keys(v1: u16, v2: u16) -> Option<Self> {
+        match (v1, v2) {
+            (keys::O, keys::A) => Some(StyledRhyme::Oa),
+            (keys::O, keys::E) => Some(StyledRhyme::Oe),
+            (keys::U, keys::Y) => Some(StyledRhyme::Uy),
+            _ => None,
+        }
+    }
+
+    /// Convert FFI value (0=oa, 1=oe, 2=uy)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
+/// Tone placement per style-dependent rhyme (house style)
+///
+/// e.g., modern for oa/oe but traditional for uy: hoà, khoẻ, thúy.
+/// A `bool` converts to the same style for every rhyme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TonePlacement {
+    /// Indexed by `StyledRhyme`: true = modern (tone on the main vowel)
+    modern: [bool; 3],
+}
+
+impl TonePlacement {
+    pub const MODERN: Self = Self { modern: [true; 3] };
+    pub const TRADITIONAL: Self = Self { modern: [false; 3] };
+
+    /// Whether `rhyme` uses modern placement
+    pub fn is_modern(self, rhyme: StyledRhyme) -> bool {
+        self.modern[rhyme as usize]
+    }
+
+    /// Set the placement of one rhyme
+    pub fn set(&mut self, rhyme: StyledRhyme, modern: bool) {
+        self.modern[rhyme as usize] = modern;
+    }
+
+    /// Copy with the placement of one rhyme changed
+    pub fn with(mut self, rhyme: StyledRhyme, modern: bool) -> Self {
+        self.set(rhyme, modern);
+        self
+    }
+}
+
+impl Default for TonePlacement {
+    fn default() -> Self {
+        Self::MODERN
+    }
+}
+
+impl From<bool> for TonePlacement {
+    fn from(modern: bool) -> Self {
+        Self {
+            modern: [modern; 3],
+        }
+    }
+}
+
 /// Vietnamese vowel phonology analyzer
 pub struct Phonology;
 
@@ -266,13 +342,16 @@ impl Phonology {
     /// 3. With final consonant: mark on 2nd vowel
     /// 4. Open syllable: use pattern tables
     /// 5. Triphthong: use TRIPHTHONG_PATTERNS (middle, except uyê → last)
+    ///
+    /// `placement` decides oa/oe/uy in open syllables (`true`/`false` for all of them).
     pub fn find_tone_position(
         vowels: &[Vowel],
         has_final_consonant: bool,
-        modern: bool,
+        placement: impl Into<TonePlacement>,
         has_qu_initial: bool,
         has_gi_initial: bool,
     ) -> usize {
+        let placement = placement.into();
         // Handle gi-initial: first vowel 'i' is part of consonant, use remaining vowels
         // Example: "giàu" → vowels [i, a, u], but with gi-initial, treat as [a, u] diphthong
         if has_gi_initial && vowels.len() >= 2 && vowels[0].key == keys::I {
@@ -283,7 +362,7 @@ impl Phonology {
                 2 => Self::find_diphthong_position(
                     remaining,
                     has_final_consonant,
-                    modern,
+                    placement,
                     false,
                     false,
                 ),
@@ -301,7 +380,7 @@ impl Phonology {
                 2 => Self::find_diphthong_position(
                     remaining,
                     has_final_consonant,
-                    modern,
+                    placement,
                     false, // No longer qu-initial for remaining vowels
                     false,
                 ),
@@ -315,7 +394,7 @@ impl Phonology {
             2 => Self::find_diphthong_position(
                 vowels,
                 has_final_consonant,
-                modern,
+                placement,
                 has_qu_initial,
                 has_gi_initial,
             ),
@@ -343,7 +422,7 @@ impl Phonology {
     fn find_diphthong_position(
         vowels: &[Vowel],
         has_final_consonant: bool,
-        modern: bool,
+        placement: TonePlacement,
         has_qu_initial: bool,
         has_gi_initial: bool,
     ) -> usize {
@@ -402,18 +481,18 @@ impl Phonology {
         }
 
         // Rule 4: TONE_SECOND_PATTERNS (medial + main, compound)
-        // Modern setting only affects: oa, oe, uy (without qu-initial)
+        // Placement setting only affects: oa, oe, uy (without qu-initial), per rhyme
         if TONE_SECOND_PATTERNS
             .iter()
             .any(|p| p[0] == pair[0] && p[1] == pair[1])
         {
             // Only oa, oe, uy are affected by modern/traditional debate
-            let is_modern_pattern = matches!(
-                (v1.key, v2.key),
-                (keys::O, keys::A) | (keys::O, keys::E) | (keys::U, keys::Y)
-            );
-            if is_modern_pattern {
-                return if modern { v2.pos } else { v1.pos };
+            if let Some(rhyme) = StyledRhyme::from_keys(v1.key, v2.key) {
+                return if placement.is_modern(rhyme) {
+                    v2.pos
+                } else {
+                    v1.pos
+                };
             }
             // Other patterns (uê, iê, uô): always 2nd vowel
             return v2.pos;
@@ -0,0 +1,182 @@
+//! Autocorrect - nearest valid Vietnamese word for an invalid committed word
+//!
+//! Candidates are single edits of the composed word, tried in tiers:
+//! 1. Missing diacritic: circumflex, horn or breve on a plain vowel, đ stroke
+//! 2. Wrong diacritic: other vowel tone, tone mark value or position, no stroke
+//! 3. Adjacent letters swapped
+//! 4. One extra letter
+//!
+//! The first tier with candidates decides, and only if it has exactly one valid
+//! word: ambiguous typos are left alone.
+//!
+//! The keystroke tiers come from `data::edits`, shared with the ranked
+//! suggestions of `vietnamese_spellcheck::suggest`.
+
+use super::buffer::{Buffer, Char};
+use crate::data::edits::{deletions, swaps};
+use crate::data::{chars, keys};
+
+/// Nearest valid word for `word`, or None if no unique correction exists
+///
+/// `is_valid` decides dictionary membership (style and user words included).
+pub fn correct(word: &Buffer, is_valid: impl Fn(&str) -> bool) -> Option<Buffer> {
+    let (missing, wrong) = diacritic_edits(word);
+    let letters: Vec<Char> = word.iter().copied().collect();
+    let keystrokes = |edits: Vec<Vec<Char>>| edits.iter().map(|l| to_buffer(l)).collect();
+    for tier in [
+        missing,
+        wrong,
+        keystrokes(swaps(&letters, |c| c.key)),
+        keystrokes(deletions(&letters)),
+    ] {
+        let mut found: Option<(String, Buffer)> = None;
+        for candidate in tier {
+            let text = candidate.to_full_string();
+            if !is_valid(&text) || found.as_ref().is_some_and(|(t, _)| *t == text) {
+                continue;
+            }
+            if found.is_some() {
+                return None;
+            }
+            found = Some((text, candidate));
+        }
+        if let Some((_, buf)) = found {
+            return Some(buf);
+        }
+    }
+    None
+}
+
+/// Check that a vowel with these diacritics exists ("ê" yes, "ơ" on 'e' no)
+fn is_valid_vowel(c: &Char) -> bool {
+    chars::to_char(c.key, c.caps, c.tone, c.mark).is_some()
+}
+
+/// Copy of `word` with char `i` changed by `f`
+fn with_char(word: &Buffer, i: usize, f: impl FnOnce(&mut Char)) -> Buffer {
+    let mut copy = word.clone();
+    if let Some(c) = copy.get_mut(i) {
+        f(c);
+    }
+    copy
+}
+
+/// Tiers 1 and 2: one diacritic added, or one diacritic changed
+fn diacritic_edits(word: &Buffer) -> (Vec<Buffer>, Vec<Buffer>) {
+    let mut missing = Vec::new();
+    let mut out = Vec::new();
+    let marked = word.iter().position(|c| c.has_mark());
+
+    for (i, c) in word.iter().enumerate() {
+        if c.key == keys::D {
+            let copy = with_char(word, i, |c| c.stroke = !c.stroke);
+            if c.stroke {
+                out.push(copy);
+            } else {
+                missing.push(copy);
+            }
+            continue;
+        }
+        if !keys::is_vowel(c.key) {
+            continue;
+        }
+        // Vowel tone: "tiên" for "tien", "người" for "nguời"
+        for tone in [
+            chars::tone::NONE,
+            chars::tone::CIRCUMFLEX,
+            chars::tone::HORN,
+        ] {
+            if tone != c.tone {
+                let copy = with_char(word, i, |c| c.tone = tone);
+                if !copy.get(i).is_some_and(is_valid_vowel) {
+                    continue;
+                }
+                if c.tone == chars::tone::NONE {
+                    missing.push(copy);
+                } else {
+                    out.push(copy);
+                }
+            }
+        }
+        // Tone mark moved here from another vowel
+        if let Some(from) = marked.filter(|&from| from != i) {
+            let value = word.get(from).map_or(0, |c| c.mark);
+            let mut copy = with_char(word, from, |c| c.mark = chars::mark::NONE);
+            if let Some(c) = copy.get_mut(i) {
+                c.mark = value;
+            }
+            out.push(copy);
+        }
+    }
+
+    // Other tone mark on the same vowel: hỏi ↔ ngã, sắc ↔ nặng…
+    if let Some(i) = marked {
+        let current = word.get(i).map_or(0, |c| c.mark);
+        for value in chars::mark::SAC..=chars::mark::NANG {
+            if value != current {
+                out.push(with_char(word, i, |c| c.mark = value));
+            }
+        }
+    }
+    (missing, out)
+}
+
+fn to_buffer(chars: &[Char]) -> Buffer {
+    let mut buf = Buffer::new();
+    for &c in chars {
+        buf.push(c);
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::vietnamese_spellcheck;
+
+    fn buffer(word: &str) -> Buffer {
+        let letters: Vec<Char> = chars::parse_word(word)
+            .unwrap()
+            .into_iter()
+            .map(Char::from)
+            .collect();
+        to_buffer(&letters)
+    }
+
+    fn fix(word: &str) -> Option<String> {
+        correct(&buffer(word), |w| {
+            vietnamese_spellcheck::check_with_style_and_foreign(w, false, false)
+        })
+        .map(|b| b.to_full_string())
+    }
+
+    #[test]
+    fn test_missing_circumflex() {
+        assert_eq!(fix("nguời").as_deref(), Some("người"));
+        assert_eq!(fix("tiéng").as_deref(), Some("tiếng"));
+    }
+
+    #[test]
+    fn test_misplaced_mark() {
+        assert_eq!(fix("tòan").as_deref(), Some("toàn"));
+    }
+
+    #[test]
+    fn test_swapped_keys() {
+        assert_eq!(fix("hcào").as_deref(), Some("chào"));
+    }
+
+    #[test]
+    fn test_extra_letter() {
+        assert_eq!(fix("khôngg").as_deref(), Some("không"));
+        assert_eq!(fix("tôio").as_deref(), Some("tôi"));
+    }
+
+    #[test]
+    fn test_no_correction() {
+        // Gibberish without a valid neighbour stays as typed
+        assert_eq!(fix("xyzẫ"), None);
+        // Needs two edits
+        assert_eq!(fix("ngùơi"), None);
+    }
+}
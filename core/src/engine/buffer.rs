@@ -2,6 +2,7 @@
 
 pub const MAX: usize = 256;
 
+use crate::data::chars::ParsedChar;
 use crate::utils;
 
 /// Single character in buffer
@@ -39,6 +40,18 @@ impl Char {
     }
 }
 
+impl From<ParsedChar> for Char {
+    fn from(p: ParsedChar) -> Self {
+        Self {
+            key: p.key,
+            caps: p.caps,
+            tone: p.tone,
+            mark: p.mark,
+            stroke: p.stroke,
+        }
+    }
+}
+
 /// Typing buffer
 #[derive(Clone)]
 pub struct Buffer {
@@ -0,0 +1,167 @@
+//! Line Model - text of the current line around the caret
+//!
+//! Mirrors what the host shows on screen by applying each key result
+//! (backspaces, inserted chars, passed-through keys) and Left/Right moves.
+//! The engine uses it to re-enter a committed word when the caret lands
+//! right after it, so tones can be edited in place without re-reading the screen.
+//!
+//! Only text typed since the last reset is known: moving past either end,
+//! Ctrl/Cmd shortcuts and other navigation keys reset the model.
+
+/// Maximum chars kept; older text at the start of the line is dropped
+pub const MAX_LINE_LEN: usize = 1024;
+
+/// Current line as seen on screen, with the caret position
+#[derive(Debug, Default, Clone)]
+pub struct Line {
+    text: Vec<char>,
+    cursor: usize,
+}
+
+impl Line {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget everything (new line, caret moved somewhere unknown)
+    pub fn reset(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Line text
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Caret position in chars from the start of the known text
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Insert a char at the caret
+    pub fn insert(&mut self, ch: char) {
+        self.text.insert(self.cursor, ch);
+        self.cursor += 1;
+        if self.text.len() > MAX_LINE_LEN {
+            let excess = self.text.len() - MAX_LINE_LEN;
+            self.text.drain(..excess);
+            self.cursor = self.cursor.saturating_sub(excess);
+        }
+    }
+
+    /// Delete the char before the caret (Backspace)
+    ///
+    /// Deleting before the known text means the model is out of date: reset.
+    pub fn delete_back(&mut self) {
+        if self.cursor == 0 {
+            self.reset();
+            return;
+        }
+        self.cursor -= 1;
+        self.text.remove(self.cursor);
+    }
+
+    /// Apply a key result: `backspace` deletions, then `chars` inserted
+    pub fn apply(&mut self, backspace: usize, chars: &[char]) {
+        for _ in 0..backspace {
+            self.delete_back();
+        }
+        for &ch in chars {
+            self.insert(ch);
+        }
+    }
+
+    /// Move the caret left (reset when leaving the known text)
+    pub fn move_left(&mut self, count: usize) {
+        if count > self.cursor {
+            self.reset();
+        } else {
+            self.cursor -= count;
+        }
+    }
+
+    /// Move the caret right (reset when leaving the known text)
+    pub fn move_right(&mut self) {
+        if self.cursor == self.text.len() {
+            self.reset();
+        } else {
+            self.cursor += 1;
+        }
+    }
+
+    /// Word ending exactly at the caret: "việt| nam" → "việt", "vi|ệt" → None
+    pub fn word_before_cursor(&self) -> Option<String> {
+        if self
+            .text
+            .get(self.cursor)
+            .is_some_and(|c| c.is_alphabetic())
+        {
+            return None;
+        }
+        let start = self.text[..self.cursor]
+            .iter()
+            .rposition(|c| !c.is_alphabetic())
+            .map_or(0, |i| i + 1);
+        if start == self.cursor {
+            return None;
+        }
+        Some(self.text[start..self.cursor].iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line {
+        let mut line = Line::new();
+        line.apply(0, &text.chars().collect::<Vec<_>>());
+        line
+    }
+
+    #[test]
+    fn test_apply_and_delete() {
+        let mut l = line("xin chao");
+        l.apply(4, &['c', 'h', 'à', 'o']);
+        assert_eq!(l.text(), "xin chào");
+        l.delete_back();
+        assert_eq!((l.text().as_str(), l.cursor()), ("xin chà", 7));
+    }
+
+    #[test]
+    fn test_edit_in_middle() {
+        let mut l = line("viet nam");
+        l.move_left(4);
+        l.apply(2, &['ệ', 't']);
+        assert_eq!(l.text(), "việt nam");
+        assert_eq!(l.cursor(), 4);
+    }
+
+    #[test]
+    fn test_word_before_cursor() {
+        let mut l = line("việt, nam");
+        assert_eq!(l.word_before_cursor().as_deref(), Some("nam"));
+        l.move_left(5);
+        assert_eq!(l.word_before_cursor().as_deref(), Some("việt"));
+        l.move_left(1);
+        assert_eq!(l.word_before_cursor(), None);
+        l.move_right();
+        l.move_right();
+        assert_eq!(l.word_before_cursor(), None);
+    }
+
+    #[test]
+    fn test_leaving_known_text_resets() {
+        let mut l = line("ab");
+        l.move_right();
+        assert_eq!(l.text(), "");
+        let mut l = line("ab");
+        l.move_left(3);
+        assert_eq!(l.text(), "");
+        let mut l = line("ab");
+        l.move_left(2);
+        l.delete_back();
+        assert_eq!(l.text(), "");
+    }
+}
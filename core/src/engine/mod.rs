@@ -10,25 +10,41 @@
 //! 3. **Shortcut Support**: User-defined abbreviations with priority
 //! 4. **Longest-Match-First**: For diacritic placement
 
+pub mod autocorrect;
 pub mod buffer;
+pub mod line;
+pub mod proper_nouns;
 pub mod shortcut;
 pub mod syllable;
+pub mod template;
+pub mod token_class;
 pub mod transform;
+pub mod typography;
+pub mod user_dict;
 pub mod validation;
+pub mod word_lists;
 
 use crate::data::{
+    bigrams::Bigrams,
     chars::{self, mark, tone},
     constants, english_dict, keys, telex_doubles, vietnamese_spellcheck,
-    vowel::{Phonology, Vowel},
+    vowel::{Phonology, TonePlacement, Vowel},
 };
 use crate::input::{self, ToneType};
 use crate::utils;
 use buffer::{Buffer, Char, MAX};
-use shortcut::{InputMethod, ShortcutTable};
+use line::Line;
+use proper_nouns::{ProperNouns, MAX_PHRASE_WORDS};
+use shortcut::{InputMethod, MatchSource, ShortcutTable, MAX_REPLACEMENT_LEN};
+use std::collections::VecDeque;
+use token_class::TokenClass;
+use typography::Typography;
+use user_dict::{Language, UserDictionary};
 use validation::{
     is_foreign_word_pattern, is_valid, is_valid_for_transform_with_foreign, is_valid_with_foreign,
     is_valid_with_tones, is_valid_with_tones_and_foreign,
 };
+use word_lists::WordLists;
 
 /// Engine action result
 #[repr(u8)]
@@ -49,12 +65,78 @@ pub struct Result {
     /// Flags byte:
     /// - bit 0 (0x01): key_consumed - if set, the trigger key should NOT be passed through
     ///   Used for shortcuts where the trigger key is part of the replacement
+    /// - bit 1 (0x02): more_output - more chars follow, see `FLAG_MORE_OUTPUT`
+    /// - bit 2 (0x04): misspelled - committed word failed strict spelling, see
+    ///   `FLAG_MISSPELLED` (may be set with action None)
+    ///
+    /// `chars` may contain '\n' (type as Return) and '\t' (type as Tab).
     pub flags: u8,
+    /// Number of Left-arrow presses the host must send after injecting `chars`
+    /// (shortcut replacement with a `$|` cursor marker), 0 = leave caret at end.
+    /// Hosts that ignore it leave the caret after the replacement.
+    /// For chunked output, set on the last chunk only.
+    pub cursor_left: u16,
 }
 
 /// Flag: key was consumed by shortcut, don't pass through
 pub const FLAG_KEY_CONSUMED: u8 = 0x01;
 
+/// Flag: output continues, fetch the next chunk with `Engine::next_output_chunk`
+/// (long shortcut expansions, sent in chunks of `MAX_REPLACEMENT_LEN` chars;
+/// only set after the host opts in with `Engine::set_long_output`)
+pub const FLAG_MORE_OUTPUT: u8 = 0x02;
+
+/// Flag: the word committed by this key is neither valid Vietnamese nor English
+/// (`StrictSpelling::Flag`); the host may underline it
+pub const FLAG_MISSPELLED: u8 = 0x04;
+
+/// Strict spelling: what happens to a committed word missing from the dictionaries
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StrictSpelling {
+    /// Keep the word as composed
+    #[default]
+    Off = 0,
+    /// Replace the word with the raw keys as typed ("tiéng " → "tiengs ")
+    Restore = 1,
+    /// Keep the word and set `FLAG_MISSPELLED` on the result
+    Flag = 2,
+}
+
+impl StrictSpelling {
+    /// Convert FFI value (0=off, 1=restore, 2=flag)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(StrictSpelling::Off),
+            1 => Some(StrictSpelling::Restore),
+            2 => Some(StrictSpelling::Flag),
+            _ => None,
+        }
+    }
+}
+
+/// Key that forces the language of one word (see `Engine::set_word_escape`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordEscape {
+    /// Pressed before the word (consumed); the word is forced when committed.
+    /// Pressing it twice types the key itself.
+    Prefix(u16),
+    /// Pressed twice right after the word: the word and the first tap are
+    /// replaced by the forced word
+    DoubleSuffix(u16),
+}
+
+impl WordEscape {
+    /// Convert FFI value (0=off, 1=prefix, 2=double-tapped suffix)
+    pub fn from_u8(mode: u8, key: u16) -> Option<Self> {
+        match mode {
+            1 => Some(WordEscape::Prefix(key)),
+            2 => Some(WordEscape::DoubleSuffix(key)),
+            _ => None,
+        }
+    }
+}
+
 impl Result {
     pub fn none() -> Self {
         Self {
@@ -63,6 +145,7 @@ impl Result {
             backspace: 0,
             count: 0,
             flags: 0,
+            cursor_left: 0,
         }
     }
 
@@ -73,6 +156,7 @@ impl Result {
             backspace,
             count: chars.len().min(MAX) as u8,
             flags: 0,
+            cursor_left: 0,
         };
         for (i, &c) in chars.iter().take(MAX).enumerate() {
             result.chars[i] = c as u32;
@@ -87,12 +171,92 @@ impl Result {
         result
     }
 
+    /// Set caret offset from the end of the output (clamped to u16::MAX)
+    pub fn with_cursor_left(mut self, cursor_left: usize) -> Self {
+        self.cursor_left = cursor_left.min(u16::MAX as usize) as u16;
+        self
+    }
+
+    /// Check if more output chunks follow
+    pub fn has_more_output(&self) -> bool {
+        self.flags & FLAG_MORE_OUTPUT != 0
+    }
+
     /// Check if key was consumed (should not be passed through)
     pub fn key_consumed(&self) -> bool {
         self.flags & FLAG_KEY_CONSUMED != 0
     }
 }
 
+/// Last shortcut expansion or autocorrection, undone by an immediate Backspace
+#[derive(Debug, Clone)]
+struct ExpansionUndo {
+    /// Chars on screen produced by the expansion (plus a passed-through boundary key)
+    screen_len: usize,
+    /// Text to put back: trigger or word as typed plus the boundary key
+    typed: Vec<char>,
+}
+
+/// Maximum transforms remembered per word for step-by-step undo
+const MAX_TRANSFORM_UNDO: usize = 32;
+
+/// Buffer state before one transform of the current word
+#[derive(Clone)]
+struct TransformStep {
+    before: Buffer,
+    /// Key that caused the transform, restored if it created a char ("w" → "ư")
+    key: u16,
+    caps: bool,
+    created: bool,
+}
+
+/// Word before the first tap of a `WordEscape::DoubleSuffix` key
+struct EscapeTap {
+    language: Language,
+    buf: Buffer,
+    raw_input: Vec<(u16, bool, bool)>,
+    /// Word as typed (raw keys)
+    raw: Vec<char>,
+    sentence_token: String,
+    /// Chars on screen for the word and the first tap
+    screen_len: usize,
+}
+
+impl EscapeTap {
+    /// Add the chars the first tap left on screen; None if they are unknown
+    fn after(mut self, result: &Result, key: u16, shift: bool) -> Option<Self> {
+        if result.has_more_output() {
+            return None;
+        }
+        let passes_through = if result.action == Action::Send as u8 {
+            self.screen_len =
+                (self.screen_len + result.count as usize).checked_sub(result.backspace as usize)?;
+            keys::is_break_ext(key, shift) && !result.key_consumed()
+        } else {
+            true
+        };
+        if passes_through {
+            self.screen_len += 1;
+        }
+        Some(self)
+    }
+}
+
+/// Number of diacritics (tone, mark, stroke) in a buffer
+fn diacritic_count(buf: &Buffer) -> usize {
+    buf.iter()
+        .map(|c| usize::from(c.has_tone()) + usize::from(c.has_mark()) + usize::from(c.stroke))
+        .sum()
+}
+
+/// Error for one line of an imported text file (user words, shortcuts)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
 /// Transform type for revert tracking
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Transform {
@@ -156,6 +320,14 @@ impl WordHistory {
         Some(self.data[self.head].clone())
     }
 
+    /// Most recent buffer without removing it
+    fn last(&self) -> Option<&Buffer> {
+        if self.len == 0 {
+            return None;
+        }
+        Some(&self.data[(self.head + HISTORY_CAPACITY - 1) % HISTORY_CAPACITY])
+    }
+
     fn clear(&mut self) {
         self.len = 0;
         self.head = 0;
@@ -171,6 +343,34 @@ fn is_sentence_ending_punctuation(key: u16, shift: bool) -> bool {
         || (shift && key == keys::SLASH) // ?
 }
 
+/// Abbreviations whose final dot does not end a sentence ("TP. Hồ Chí Minh", "v.v. và")
+const ABBREVIATIONS: &[&str] = &[
+    "TP.", "Tp.", "TX.", "TT.", "Q.", "P.", "TS.", "ThS.", "PGS.", "GS.", "BS.", "KS.", "NXB.",
+    "v.v.", "vv.", "tr.",
+];
+
+/// Check if the text since the last whitespace is a known abbreviation
+/// Leading brackets and quotes are ignored: "(TP." → "TP."
+fn is_abbreviation(token: &str) -> bool {
+    let token = token.trim_start_matches(|c: char| !c.is_alphanumeric());
+    ABBREVIATIONS.contains(&token)
+}
+
+/// Replace the passed-through break key of `result` with a typography char
+///
+/// The key is consumed; `backspace` chars before the committed word are deleted
+/// (the space in "xin ,", the hyphen in " --").
+fn with_typography(result: Result, backspace: u8, replacement: char) -> Result {
+    let mut output: Vec<char> = result.chars[..result.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    output.push(replacement);
+    let mut typed = Result::send_consumed(result.backspace + backspace, &output);
+    typed.flags |= result.flags;
+    typed
+}
+
 /// Check if a break key should reset pending_capitalize
 /// Neutral keys like quotes, parentheses, arrows should NOT reset (preserve pending)
 /// Word-breaking keys like comma should reset
@@ -270,14 +470,22 @@ pub struct Engine {
     /// Enable free tone placement (skip validation)
     /// When true, allows placing diacritics anywhere without spelling validation
     free_tone_enabled: bool,
-    /// Use modern orthography for tone placement (hoà vs hòa)
-    /// When true: oà, uý (tone on second vowel)
-    /// When false: òa, úy (tone on first vowel - traditional)
-    modern_tone: bool,
+    /// Orthography for tone placement (hoà vs hòa), per rhyme oa/oe/uy
+    /// Modern: oà, uý (tone on second vowel)
+    /// Traditional: òa, úy (tone on first vowel)
+    tone_placement: TonePlacement,
     /// Enable English auto-restore (experimental)
     /// When true, automatically restores English words that were transformed
     /// e.g., "tẽt" → "text", "ễpct" → "expect"
     english_auto_restore: bool,
+    /// Use the previous committed word as context for auto-restore (bigram model)
+    /// e.g., "the box" → restore "box", "chẳng bõ" → keep "bõ"
+    context_restore: bool,
+    /// Known word pairs for context restore (empty until the host adds some)
+    bigrams: Bigrams,
+    /// Backspace right after a shortcut expansion restores the trigger
+    /// e.g., "vn " → "Việt Nam " → Backspace → "vn "
+    shortcut_undo: bool,
     /// Word history for backspace-after-space feature
     word_history: WordHistory,
     /// Number of spaces typed after committing a word (for backspace tracking)
@@ -353,9 +561,63 @@ pub struct Engine {
     /// Only set pending_capitalize when space/Enter follows
     /// Issue #185: don't capitalize immediately after punctuation (e.g., google.com)
     saw_sentence_ending: bool,
+    /// Text typed since the last whitespace (e.g., "v.v."), for abbreviation checks
+    sentence_token: String,
+    /// Enter starts a new line with a capital letter (when auto-capitalize is on)
+    capitalize_new_line: bool,
+    /// User proper nouns capitalized mid-sentence ("hà nội" → "Hà Nội")
+    proper_nouns: ProperNouns,
+    /// Words committed so far, each followed by exactly one space
+    /// e.g., "việt " → ["việt"], so "nam " can match the proper noun "Việt Nam"
+    phrase_words: Vec<Buffer>,
+    /// Smart quotes, dashes and punctuation spacing on break keys
+    typography: Typography,
+    /// Detect URLs, e-mail addresses, paths, identifiers and numbers as typed
+    token_detection: bool,
+    /// Class of the token being typed; transforms are off until the next whitespace
+    token_class: Option<TokenClass>,
     /// Allow foreign consonants (z, w, j, f) as valid initial consonants
     /// When true, these letters are accepted as Vietnamese consonants for loanwords
     allow_foreign_consonants: bool,
+    /// User word layers, consulted before the embedded dictionaries
+    user_dict: UserDictionary,
+    /// Never-transform (raw keystrokes) and never-restore (Vietnamese words) lists
+    word_lists: WordLists,
+    /// Deliver expansions longer than one result in chunks (host handles
+    /// `FLAG_MORE_OUTPUT`); when false they are cut to `MAX_REPLACEMENT_LEN`
+    long_output: bool,
+    /// Rest of a long shortcut expansion, drained by `next_output_chunk`
+    pending_output: VecDeque<char>,
+    /// Caret offset for the last pending chunk
+    pending_cursor_left: usize,
+    /// Last shortcut expansion or autocorrection: Backspace right after it
+    /// restores the text as typed
+    last_expansion: Option<ExpansionUndo>,
+    /// Replace an invalid committed word with its nearest valid Vietnamese word
+    /// e.g., "tòan " → "toàn ", "nguời " → "người "
+    autocorrect: bool,
+    /// Committed words must be valid Vietnamese or English (opposite of free tone)
+    strict_spelling: StrictSpelling,
+    /// Transforms of the current word, undone one by one with `undo_key`
+    /// e.g., "được" → "đươc" → "đuoc" → "duoc"
+    transform_history: Vec<TransformStep>,
+    /// Key that steps back through `transform_history` (None = disabled)
+    undo_key: Option<u16>,
+    /// Keys forcing the current word to be committed as raw keys (English)
+    /// or as composed (Vietnamese), regardless of auto-restore
+    force_english: Option<WordEscape>,
+    force_vietnamese: Option<WordEscape>,
+    /// Language forced for the current word by an escape key
+    forced_word: Option<Language>,
+    /// First tap of a double-tapped escape key, waiting for the second
+    escape_tap: Option<EscapeTap>,
+    /// Add forced words to the word lists (English → never-transform,
+    /// Vietnamese → never-restore)
+    learn_forced_words: bool,
+    /// Track the current line so Left/Right/Backspace re-enter committed words
+    line_editing: bool,
+    /// Screen text of the current line (only maintained with `line_editing`)
+    line: Line,
 }
 
 impl Default for Engine {
@@ -378,8 +640,11 @@ impl Engine {
             bracket_shortcut: false,    // Default: OFF (Issue #159)
             esc_restore_enabled: false, // Default: OFF (user request)
             free_tone_enabled: false,
-            modern_tone: true,           // Default: modern style (hoà, thuý)
-            english_auto_restore: false, // Default: OFF (experimental feature)
+            tone_placement: TonePlacement::MODERN, // Default: modern style (hoà, thuý)
+            english_auto_restore: false,           // Default: OFF (experimental feature)
+            context_restore: false,                // Default: OFF
+            bigrams: Bigrams::new(),
+            shortcut_undo: false, // Default: OFF
             word_history: WordHistory::new(),
             spaces_after_commit: 0,
             pending_breve_pos: None,
@@ -401,7 +666,31 @@ impl Engine {
             pending_capitalize: false,
             auto_capitalize_used: false,
             saw_sentence_ending: false,
+            sentence_token: String::new(),
+            capitalize_new_line: true,
+            proper_nouns: ProperNouns::new(),
+            phrase_words: Vec::new(),
+            typography: Typography::default(),
+            token_detection: true,
+            token_class: None,
             allow_foreign_consonants: false, // Default: OFF
+            user_dict: UserDictionary::new(),
+            word_lists: WordLists::new(),
+            long_output: false, // Default: OFF
+            pending_output: VecDeque::new(),
+            pending_cursor_left: 0,
+            last_expansion: None,
+            autocorrect: false,
+            strict_spelling: StrictSpelling::Off,
+            transform_history: Vec::new(),
+            undo_key: None, // Default: OFF
+            force_english: None,
+            force_vietnamese: None,
+            forced_word: None,
+            escape_tap: None,
+            learn_forced_words: false,
+            line_editing: false, // Default: OFF
+            line: Line::new(),
         }
     }
 
@@ -438,9 +727,21 @@ impl Engine {
         self.free_tone_enabled = enabled;
     }
 
-    /// Set whether to use modern orthography for tone placement
+    /// Set whether to use modern orthography for tone placement (all rhymes)
+    ///
+    /// Replaces per-rhyme overrides from `set_tone_placement`; apply those after.
     pub fn set_modern_tone(&mut self, modern: bool) {
-        self.modern_tone = modern;
+        self.tone_placement = TonePlacement::from(modern);
+    }
+
+    /// Set tone placement per rhyme (house style, e.g. modern oa/oe, traditional uy)
+    pub fn set_tone_placement(&mut self, placement: TonePlacement) {
+        self.tone_placement = placement;
+    }
+
+    /// Current tone placement per rhyme
+    pub fn tone_placement(&self) -> TonePlacement {
+        self.tone_placement
     }
 
     /// Set whether to enable English auto-restore (experimental)
@@ -448,6 +749,85 @@ impl Engine {
         self.english_auto_restore = enabled;
     }
 
+    /// Set whether auto-restore uses the previous word as context (bigram model)
+    pub fn set_context_restore(&mut self, enabled: bool) {
+        self.context_restore = enabled;
+    }
+
+    /// Set whether Backspace right after a shortcut expansion restores the trigger
+    pub fn set_shortcut_undo(&mut self, enabled: bool) {
+        self.shortcut_undo = enabled;
+        if !enabled {
+            self.last_expansion = None;
+        }
+    }
+
+    /// Set whether long shortcut expansions are delivered in chunks
+    ///
+    /// Only enable if the host fetches `next_output_chunk` on `FLAG_MORE_OUTPUT`;
+    /// otherwise expansions are cut to `MAX_REPLACEMENT_LEN` chars.
+    pub fn set_long_output(&mut self, enabled: bool) {
+        self.long_output = enabled;
+    }
+
+    /// Set whether invalid committed words are autocorrected from the dictionary
+    pub fn set_autocorrect(&mut self, enabled: bool) {
+        self.autocorrect = enabled;
+    }
+
+    /// Set how committed words missing from the dictionaries are handled
+    pub fn set_strict_spelling(&mut self, mode: StrictSpelling) {
+        self.strict_spelling = mode;
+    }
+
+    /// Set the key that undoes the current word's transforms one at a time
+    ///
+    /// None disables it. When the word has no transform left, the key is
+    /// processed normally (e.g. ESC still restores raw keys).
+    pub fn set_undo_key(&mut self, key: Option<u16>) {
+        self.undo_key = key;
+    }
+
+    /// Set the key forcing the language of one word (None disables it)
+    ///
+    /// English commits the word as typed ("tieengs" → "tieengs"), Vietnamese
+    /// keeps it composed even if it looks like English ("door" → "dổ").
+    /// A double-tapped key must type a character and cannot be Space.
+    pub fn set_word_escape(&mut self, language: Language, escape: Option<WordEscape>) {
+        match language {
+            Language::English => self.force_english = escape,
+            Language::Vietnamese => self.force_vietnamese = escape,
+        }
+        self.forced_word = None;
+        self.escape_tap = None;
+    }
+
+    /// Set whether forced words are learned into the word lists
+    pub fn set_learn_forced_words(&mut self, enabled: bool) {
+        self.learn_forced_words = enabled;
+    }
+
+    /// Language forced for the word being typed, if any
+    pub fn forced_word(&self) -> Option<Language> {
+        self.forced_word
+    }
+
+    /// Set whether the engine tracks the current line for in-place editing
+    ///
+    /// When enabled, the host passes Left/Right arrows to the engine (instead of
+    /// calling `clear_all`). Moving the caret right after a committed word, or
+    /// backspacing to it, re-enters the word: "việt nam" ← ← ← ← "j" → "việt nam"
+    /// with the tone of "việt" editable again.
+    pub fn set_line_editing(&mut self, enabled: bool) {
+        self.line_editing = enabled;
+        self.line.reset();
+    }
+
+    /// Current line model (empty unless line editing is enabled)
+    pub fn line(&self) -> &Line {
+        &self.line
+    }
+
     /// Set whether to enable auto-capitalize after sentence-ending punctuation
     pub fn set_auto_capitalize(&mut self, enabled: bool) {
         self.auto_capitalize = enabled;
@@ -457,6 +837,39 @@ impl Engine {
         }
     }
 
+    /// Set whether Enter capitalizes the first letter of the new line
+    /// (only when auto-capitalize is on; default: true)
+    pub fn set_capitalize_new_line(&mut self, enabled: bool) {
+        self.capitalize_new_line = enabled;
+    }
+
+    /// Set the typography rules (smart quotes, dashes, punctuation spacing)
+    pub fn set_typography(&mut self, rules: Typography) {
+        self.typography = rules;
+    }
+
+    /// Set whether URLs, e-mail addresses, paths, identifiers and numbers
+    /// are typed verbatim (default: true)
+    pub fn set_token_detection(&mut self, enabled: bool) {
+        self.token_detection = enabled;
+        if !enabled {
+            self.token_class = None;
+        }
+    }
+
+    /// Class of the verbatim token being typed, None inside ordinary words
+    pub fn token_class(&self) -> Option<TokenClass> {
+        self.token_class
+    }
+
+    pub fn proper_nouns(&self) -> &ProperNouns {
+        &self.proper_nouns
+    }
+
+    pub fn proper_nouns_mut(&mut self) -> &mut ProperNouns {
+        &mut self.proper_nouns
+    }
+
     /// Set whether to allow foreign consonants (z, w, j, f) as valid initials
     pub fn set_allow_foreign_consonants(&mut self, enabled: bool) {
         self.allow_foreign_consonants = enabled;
@@ -475,6 +888,147 @@ impl Engine {
         &mut self.shortcuts
     }
 
+    /// Set the active context (e.g. app id) that decides which shortcut groups fire
+    ///
+    /// Empty string means no context: only groups without context bindings fire.
+    pub fn set_active_context(&mut self, id: &str) {
+        self.shortcuts.set_active_context(id);
+    }
+
+    pub fn user_dict(&self) -> &UserDictionary {
+        &self.user_dict
+    }
+
+    pub fn user_dict_mut(&mut self) -> &mut UserDictionary {
+        &mut self.user_dict
+    }
+
+    /// Add a user word to a language layer (takes precedence over embedded dictionary)
+    pub fn add_user_word(&mut self, lang: Language, word: &str) {
+        self.user_dict.add(lang, word);
+    }
+
+    /// Block a word in a language layer (hides it from the embedded dictionary)
+    pub fn block_user_word(&mut self, lang: Language, word: &str) {
+        self.user_dict.block(lang, word);
+    }
+
+    /// Remove a word from a user layer, returns true if it was present
+    pub fn remove_user_word(&mut self, lang: Language, word: &str) -> bool {
+        self.user_dict.remove(lang, word)
+    }
+
+    /// Add a word pair that decides context restore (see `set_context_restore`)
+    ///
+    /// Vietnamese pairs are given in the current tone placement.
+    pub fn add_bigram(&mut self, lang: Language, prev: &str, next: &str) {
+        match lang {
+            Language::English => self.bigrams.add_english_pair(prev, next),
+            Language::Vietnamese => {
+                self.bigrams
+                    .add_vietnamese_pair(prev, next, self.tone_placement)
+            }
+        }
+    }
+
+    pub fn bigrams_mut(&mut self) -> &mut Bigrams {
+        &mut self.bigrams
+    }
+
+    pub fn word_lists(&self) -> &WordLists {
+        &self.word_lists
+    }
+
+    pub fn word_lists_mut(&mut self) -> &mut WordLists {
+        &mut self.word_lists
+    }
+
+    /// Check if the raw keystrokes typed so far are in the never-transform list
+    fn is_never_transform_input(&self) -> bool {
+        // Runs on every modifier key: no allocation while the list is empty
+        if self.word_lists.never_transform_is_empty() {
+            return false;
+        }
+        let raw: String = self
+            .raw_input
+            .iter()
+            .filter_map(|&(key, _, _)| utils::key_to_char(key, false))
+            .collect();
+        self.word_lists.is_never_transform(&raw)
+    }
+
+    /// Classify a committed word's language for context-aware auto-restore
+    ///
+    /// - Vietnamese diacritics or Vietnamese-only dictionary word → Vietnamese
+    /// - English-only dictionary word → English
+    /// - In both or neither (e.g. "a", "an") → None (no context)
+    fn classify_word(&self, buf: &Buffer) -> Option<Language> {
+        let word = buf.to_full_string();
+        let has_diacritics = buf
+            .iter()
+            .any(|c| c.tone != tone::NONE || c.mark != mark::NONE || c.stroke);
+        if has_diacritics {
+            return Some(Language::Vietnamese);
+        }
+        match (self.is_vietnamese_word(&word), self.is_english_word(&word)) {
+            (true, false) => Some(Language::Vietnamese),
+            (false, true) => Some(Language::English),
+            _ => None,
+        }
+    }
+
+    /// Decide auto-restore from the previous committed word (bigram context)
+    ///
+    /// - `Some(true)`: restore to English ("the" + "box" → "box")
+    /// - `Some(false)`: keep Vietnamese ("chẳng" + "bõ" → "bõ")
+    /// - `None`: context is not conclusive, use the per-word rules
+    ///
+    /// Known word pairs (`add_bigram`) win; otherwise lean towards the previous
+    /// word's language when the current word is valid in that language.
+    fn context_restore_decision(&self) -> Option<bool> {
+        let prev_buf = self.word_history.last()?;
+        let prev = prev_buf.to_full_string();
+        let raw = self.get_raw_input_string();
+        let composed = self.buf.to_full_string();
+
+        if self.bigrams.is_english_pair(&prev, &raw) {
+            return Some(true);
+        }
+        if self
+            .bigrams
+            .is_vietnamese_pair(&prev, &composed, self.tone_placement)
+        {
+            return Some(false);
+        }
+
+        match self.classify_word(prev_buf)? {
+            Language::English if self.is_english_word(&raw) => Some(true),
+            Language::Vietnamese if self.is_vietnamese_word(&composed) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Check English word: user layer first, then embedded dictionary
+    fn is_english_word(&self, word: &str) -> bool {
+        self.user_dict
+            .lookup(Language::English, word)
+            .unwrap_or_else(|| english_dict::is_english_word(word))
+    }
+
+    /// Check Vietnamese word: user layer first, then embedded dictionary
+    /// (respects tone_placement and allow_foreign_consonants)
+    fn is_vietnamese_word(&self, word: &str) -> bool {
+        self.user_dict
+            .lookup(Language::Vietnamese, word)
+            .unwrap_or_else(|| {
+                vietnamese_spellcheck::check_with_style_and_foreign(
+                    word,
+                    self.tone_placement,
+                    self.allow_foreign_consonants,
+                )
+            })
+    }
+
     /// Debug: get buffer length
     pub fn debug_buffer_len(&self) -> usize {
         self.buf.len()
@@ -568,58 +1122,63 @@ impl Engine {
             return self.on_key_ext(key, caps, ctrl, shift);
         };
 
+        let result = self.handle_key_with_char(key, ctrl, ch);
+        if self.line_editing {
+            if ctrl {
+                self.line.reset();
+            } else {
+                self.apply_to_line(&result);
+                if !result.key_consumed() {
+                    self.line.insert(ch);
+                }
+            }
+        }
+        result
+    }
+
+    /// Process one key with its Unicode character (see `on_key_with_char`)
+    fn handle_key_with_char(&mut self, key: u16, ctrl: bool, ch: char) -> Result {
+        // Unfetched chunks of a previous expansion are dropped on the next key
+        self.pending_output.clear();
+        if let Some(result) = self.try_undo_expansion(key, ctrl) {
+            return result;
+        }
+
         // Ctrl/Cmd bypasses everything
         if ctrl {
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
+            self.sentence_token.clear();
+            self.token_class = None;
+            self.phrase_words.clear();
             return Result::none();
         }
 
         // Accumulate character for suffix matching
         self.shortcut_prefix.push(ch);
 
-        // Try suffix matches (longest first) using char_indices to avoid allocations
+        // Try suffix matches (longest first) via the reversed-trigger index
         let input_method = self.current_input_method();
-        for (idx, _) in self.shortcut_prefix.char_indices() {
-            let suffix = &self.shortcut_prefix[idx..];
-            if let Some(m) = self.shortcuts.try_match_for_method(
-                suffix,
-                None,
-                false, // immediate, not word boundary
-                input_method,
-            ) {
-                let output: Vec<char> = m.output.chars().collect();
-                let backspace_count = (m.backspace_count as u8).saturating_sub(1);
-                self.shortcut_prefix.clear();
-                return Result::send_consumed(backspace_count, &output);
-            }
+        if let Some(m) = self.shortcuts.try_match_suffix_for_method(
+            &self.shortcut_prefix,
+            None,
+            false, // immediate, not word boundary
+            input_method,
+        ) {
+            let output: Vec<char> = m.output.chars().collect();
+            let backspace_count = (m.backspace_count as u8).saturating_sub(1);
+            // Trigger as typed: the matched suffix of the prefix
+            let prefix: Vec<char> = self.shortcut_prefix.chars().collect();
+            let typed = prefix[prefix.len().saturating_sub(m.backspace_count)..].to_vec();
+            self.shortcut_prefix.clear();
+            return self.shortcut_result(backspace_count, output, true, m.cursor_left, typed, None);
         }
 
         // No match yet, let the character pass through
         Result::none()
     }
 
-    /// Check if key+shift combo is a raw mode prefix character
-    /// Raw prefixes: @ # : /
-    #[allow(dead_code)] // TEMP DISABLED
-    fn is_raw_prefix(key: u16, shift: bool) -> bool {
-        // / doesn't need shift
-        if key == keys::SLASH && !shift {
-            return true;
-        }
-        // @ # : need shift
-        if !shift {
-            return false;
-        }
-        matches!(
-            key,
-            keys::N2              // @ = Shift+2
-                | keys::N3        // # = Shift+3
-                | keys::SEMICOLON // : = Shift+;
-        )
-    }
-
     /// Handle key event with extended parameters
     ///
     /// # Arguments
@@ -628,12 +1187,118 @@ impl Engine {
     /// * `ctrl` - true if Cmd/Ctrl/Alt is pressed (bypasses IME)
     /// * `shift` - true if Shift key is pressed (for symbols like @, #, $)
     pub fn on_key_ext(&mut self, key: u16, caps: bool, ctrl: bool, shift: bool) -> Result {
+        let tap = self.escape_tap.take();
+        let result = if !ctrl && self.undo_key == Some(key) && !self.transform_history.is_empty() {
+            // Undo key steps back through this word's transforms
+            self.pending_output.clear();
+            self.last_expansion = None;
+            self.undo_transform()
+        } else if let Some(result) = self.try_word_escape(key, ctrl, tap) {
+            self.pending_output.clear();
+            self.last_expansion = None;
+            result
+        } else {
+            let tap = self.start_escape_tap(key, ctrl);
+            let before = self.buf.clone();
+            let result = self.handle_key(key, caps, ctrl, shift);
+            self.record_transform(before, key, caps);
+            self.escape_tap = tap.and_then(|tap| tap.after(&result, key, shift));
+            result
+        };
+        if self.line_editing {
+            self.track_line(key, caps, ctrl, shift, &result);
+        }
+        result
+    }
+
+    /// Apply a sent result to the line model, as the host does on screen
+    ///
+    /// Returns whether the result was sent.
+    fn apply_to_line(&mut self, result: &Result) -> bool {
+        if result.action != Action::Send as u8 {
+            return false;
+        }
+        let chars: Vec<char> = result.chars[..result.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c))
+            .collect();
+        self.line.apply(result.backspace as usize, &chars);
+        if !result.has_more_output() {
+            self.line.move_left(result.cursor_left as usize);
+        }
+        true
+    }
+
+    /// Mirror a key and its result in the line model, then re-enter the word
+    /// before the caret after Left/Right/Backspace
+    fn track_line(&mut self, key: u16, caps: bool, ctrl: bool, shift: bool, result: &Result) {
+        if ctrl {
+            self.line.reset();
+            return;
+        }
+        let sent = self.apply_to_line(result);
+
+        match key {
+            keys::RETURN | keys::ENTER => {
+                self.line.reset();
+                return;
+            }
+            keys::LEFT => self.line.move_left(1),
+            keys::RIGHT => self.line.move_right(),
+            keys::DELETE if !sent => self.line.delete_back(),
+            keys::DELETE | keys::ESC => {}
+            keys::SPACE if !sent => self.line.insert(' '),
+            keys::SPACE => {}
+            _ => {
+                // Letters are part of a sent result; break keys pass through after it
+                let passthrough = if keys::is_break_ext(key, shift) {
+                    !result.key_consumed()
+                } else {
+                    !sent
+                };
+                if passthrough {
+                    match utils::key_to_char_ext(key, caps, shift)
+                        .or_else(|| break_key_to_char(key, shift))
+                    {
+                        Some(ch) => self.line.insert(ch),
+                        // Navigation or unknown key: caret position is unknown
+                        None => {
+                            self.line.reset();
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
+        if matches!(key, keys::LEFT | keys::RIGHT | keys::DELETE) && self.buf.is_empty() {
+            if let Some(word) = self.line.word_before_cursor() {
+                // Only words the buffer can represent exactly (screen and buffer stay in sync)
+                if word.chars().all(|c| chars::parse_char(c).is_some()) {
+                    self.restore_word(&word);
+                }
+            }
+        }
+    }
+
+    /// Process one key (see `on_key_ext`)
+    fn handle_key(&mut self, key: u16, caps: bool, ctrl: bool, shift: bool) -> Result {
+        // Unfetched chunks of a previous expansion are dropped on the next key
+        self.pending_output.clear();
+        // Backspace right after a shortcut expansion restores the trigger
+        if let Some(result) = self.try_undo_expansion(key, ctrl) {
+            return result;
+        }
+
         // Issue #129: Process shortcuts even when IME is disabled
         // Only bypass completely for Ctrl/Cmd modifier keys
         if ctrl {
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
+            self.sentence_token.clear();
+            self.token_class = None;
+            self.phrase_words.clear();
             return Result::none();
         }
 
@@ -658,14 +1323,36 @@ impl Engine {
                     ) {
                         let output: Vec<char> = m.output.chars().collect();
                         let backspace_count = m.backspace_count as u8;
+                        let mut typed: Vec<char> = self.shortcut_prefix.chars().collect();
                         self.shortcut_prefix.clear();
                         // For Space, include space in output; for Enter, don't
                         if key == keys::SPACE {
                             let mut output_with_space = output;
                             output_with_space.push(' ');
-                            return Result::send(backspace_count, &output_with_space);
+                            typed.push(' ');
+                            // Caret stays before the appended space
+                            let cursor_left = match m.cursor_left {
+                                0 => 0,
+                                n => n + 1,
+                            };
+                            return self.shortcut_result(
+                                backspace_count,
+                                output_with_space,
+                                false,
+                                cursor_left,
+                                typed,
+                                None,
+                            );
                         } else {
-                            return Result::send(backspace_count, &output);
+                            // Enter passes through after the replacement
+                            return self.shortcut_result(
+                                backspace_count,
+                                output,
+                                false,
+                                m.cursor_left,
+                                typed,
+                                Some('\n'),
+                            );
                         }
                     }
                 }
@@ -687,8 +1374,16 @@ impl Engine {
                     ) {
                         let output: Vec<char> = m.output.chars().collect();
                         let backspace_count = (m.backspace_count as u8).saturating_sub(1);
+                        let typed: Vec<char> = self.shortcut_prefix.chars().collect();
                         self.shortcut_prefix.clear();
-                        return Result::send_consumed(backspace_count, &output);
+                        return self.shortcut_result(
+                            backspace_count,
+                            output,
+                            true,
+                            m.cursor_left,
+                            typed,
+                            None,
+                        );
                     }
                     return Result::none();
                 }
@@ -725,35 +1420,75 @@ impl Engine {
                 // telex_double_raw for restore which has the correct original chars.
             }
 
+            // Typography: second space right after a word → ". "
+            if self.typography.double_space_period
+                && self.buf.is_empty()
+                && self.spaces_after_commit == 1
+                && self.word_history.len > 0
+            {
+                self.word_history.clear();
+                self.spaces_after_commit = 0;
+                self.sentence_token.clear();
+                self.phrase_words.clear();
+                self.saw_sentence_ending = true;
+                self.pending_capitalize = self.auto_capitalize;
+                return Result::send(1, &['.', ' ']);
+            }
+
             // First check for shortcut
             let shortcut_result = self.try_word_boundary_shortcut();
+            self.sentence_token.clear();
+            self.token_class = None;
             if shortcut_result.action != 0 {
+                self.phrase_words.clear();
                 self.clear();
                 return shortcut_result;
             }
 
             // Auto-restore: if buffer has transforms but is invalid Vietnamese,
             // restore to raw English (like ESC but triggered by space)
-            let restore_result = self.try_auto_restore_on_space();
+            let mut restore_result = match self.forced_word {
+                Some(language) => self.commit_forced_word(language, Some(' ')),
+                None => self.try_auto_restore_on_space(),
+            };
 
             // If auto-restore happened, repopulate buffer with plain chars from raw_input
             // This ensures word_history stores the correct restored word (not transformed)
             // Example: "restore" → buffer was "rếtore" (6 chars), raw_input has 7 keys
             // After this, buffer has "restore" (7 chars) for correct history
-            if restore_result.action != 0 {
+            if self.forced_word.is_some() {
+                // Escape key decided the word: no autocorrect or strict spelling
+            } else if restore_result.action != 0 {
                 self.buf.clear();
                 for &(key, caps, _) in &self.raw_input {
                     self.buf.push(Char::new(key, caps));
                 }
+            } else if let Some(result) = self.try_autocorrect(' ', false) {
+                // Kept as Vietnamese but not a word: buffer now holds the correction
+                restore_result = result;
+            } else {
+                restore_result = self.apply_strict_spelling(restore_result, Some(' '));
+            }
+            if restore_result.action == 0 {
+                if let Some(result) = self.try_proper_noun(Some(' ')) {
+                    restore_result = result;
+                }
             }
 
             // Push buffer to history before clearing (for backspace-after-space feature)
             if !self.buf.is_empty() {
+                self.phrase_words.push(self.buf.clone());
+                if self.phrase_words.len() >= MAX_PHRASE_WORDS {
+                    self.phrase_words.remove(0);
+                }
                 self.word_history.push(self.buf.clone());
                 self.spaces_after_commit = 1; // First space after word
             } else if self.spaces_after_commit > 0 {
                 // Additional space after commit - increment counter
                 self.spaces_after_commit = self.spaces_after_commit.saturating_add(1);
+                self.phrase_words.clear();
+            } else {
+                self.phrase_words.clear();
             }
             self.auto_capitalize_used = false; // Reset on word commit
 
@@ -779,6 +1514,9 @@ impl Engine {
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
+            self.sentence_token.clear();
+            self.token_class = None;
+            self.phrase_words.clear();
             return result;
         }
 
@@ -794,6 +1532,34 @@ impl Engine {
         // Also trigger auto-restore for invalid Vietnamese before clearing
         // Use is_break_ext to handle shifted symbols like @, !, #, etc.
         if keys::is_break_ext(key, shift) {
+            // Verbatim token (URL, e-mail, path…): no shortcuts or restore until whitespace
+            let entering_token = self.token_class.is_none()
+                && !matches!(key, keys::RETURN | keys::ENTER | keys::TAB)
+                && break_key_to_char(key, shift)
+                    .is_some_and(|ch| self.detect_token_class(ch, None));
+            let verbatim = self.token_class.is_some();
+            let typography = self.typography_for_break(key, shift);
+
+            // Abbreviation check needs the text since the last whitespace ("v.v.")
+            match break_key_to_char(key, shift) {
+                Some(ch) if key != keys::RETURN && key != keys::ENTER && key != keys::TAB => {
+                    self.sentence_token.push_str(&self.buf.to_full_string());
+                    match typography {
+                        Some((backspace, replacement)) => {
+                            for _ in 0..backspace {
+                                self.sentence_token.pop();
+                            }
+                            self.sentence_token.push(replacement);
+                        }
+                        None => self.sentence_token.push(ch),
+                    }
+                }
+                _ => {
+                    self.sentence_token.clear();
+                    self.token_class = None;
+                }
+            }
+
             // Issue #107 + Bug #11: When buffer is empty AND we're at true start of input
             // (no word history), accumulate break chars for shortcuts.
             // This allows shortcuts like "#fne", "->", "=>" to work.
@@ -805,7 +1571,7 @@ impl Engine {
             // Also continue accumulating if we already started a prefix
             let continuing_prefix = self.buf.is_empty() && !self.shortcut_prefix.is_empty();
 
-            if at_true_start || continuing_prefix {
+            if !verbatim && (at_true_start || continuing_prefix) {
                 // Reset has_non_letter_prefix when starting a new shortcut at true start
                 // This ensures shortcuts like "->" work after DELETE cleared the buffer
                 if at_true_start {
@@ -829,30 +1595,41 @@ impl Engine {
                         // Example: "->" trigger has backspace_count=2, but only '-' is on screen
                         let output: Vec<char> = m.output.chars().collect();
                         let backspace_count = (m.backspace_count as u8).saturating_sub(1);
+                        let typed: Vec<char> = self.shortcut_prefix.chars().collect();
                         self.shortcut_prefix.clear();
-                        return Result::send_consumed(backspace_count, &output);
+                        return self.shortcut_result(
+                            backspace_count,
+                            output,
+                            true,
+                            m.cursor_left,
+                            typed,
+                            None,
+                        );
                     }
 
                     // Issue #185: Only set saw_sentence_ending for punctuation (not Enter)
                     // pending_capitalize will be set when space follows
-                    if self.auto_capitalize && is_sentence_ending_punctuation(key, shift) {
+                    if self.auto_capitalize && self.ends_sentence(key, shift) {
                         self.saw_sentence_ending = true;
                     } else if self.auto_capitalize && (key == keys::RETURN || key == keys::ENTER) {
                         // Enter = newline = immediate capitalize (no space needed)
-                        self.pending_capitalize = true;
+                        self.pending_capitalize = self.capitalize_new_line;
                         self.saw_sentence_ending = false;
                     }
+                    if let Some((backspace, replacement)) = typography {
+                        return Result::send_consumed(backspace, &[replacement]);
+                    }
                     return Result::none(); // Let the char pass through, keep accumulating
                 }
             }
 
             // Issue #185: Only set saw_sentence_ending for punctuation (not Enter)
             // pending_capitalize will be set when space follows
-            if self.auto_capitalize && is_sentence_ending_punctuation(key, shift) {
+            if self.auto_capitalize && self.ends_sentence(key, shift) {
                 self.saw_sentence_ending = true;
             } else if self.auto_capitalize && (key == keys::RETURN || key == keys::ENTER) {
                 // Enter = newline = immediate capitalize (no space needed)
-                self.pending_capitalize = true;
+                self.pending_capitalize = self.capitalize_new_line;
                 self.saw_sentence_ending = false;
             } else if self.auto_capitalize && should_reset_pending_capitalize(key, shift) {
                 // Reset pending for word-breaking keys (comma, semicolon, etc.)
@@ -862,6 +1639,19 @@ impl Engine {
             }
             self.auto_capitalize_used = false; // Reset on word boundary
 
+            if verbatim {
+                let result = if entering_token {
+                    self.restore_verbatim_token(None)
+                } else {
+                    Result::none()
+                };
+                self.phrase_words.clear();
+                self.clear();
+                self.word_history.clear();
+                self.spaces_after_commit = 0;
+                return result;
+            }
+
             // Issue #167: Check for word boundary shortcuts on punctuation and ENTER
             // Example: "ko." → "không." or "ko<Enter>" → "không<Enter>"
             // ENTER doesn't have a printable char, so check it separately
@@ -880,7 +1670,28 @@ impl Engine {
                 }
             }
 
-            let restore_result = self.try_auto_restore_on_break();
+            let mut restore_result = match self.forced_word {
+                Some(language) => self.commit_forced_word(language, None),
+                None => {
+                    let result = self.try_auto_restore_on_break();
+                    let corrected = trigger_char
+                        .filter(|_| result.action == 0)
+                        .and_then(|ch| self.try_autocorrect(ch, true));
+                    match corrected {
+                        Some(corrected) => corrected,
+                        None => self.apply_strict_spelling(result, None),
+                    }
+                }
+            };
+            if restore_result.action == 0 {
+                if let Some(result) = self.try_proper_noun(None) {
+                    restore_result = result;
+                }
+            }
+            if let Some((backspace, replacement)) = typography {
+                restore_result = with_typography(restore_result, backspace, replacement);
+            }
+            self.phrase_words.clear();
             self.clear();
             self.word_history.clear();
             self.spaces_after_commit = 0;
@@ -896,6 +1707,10 @@ impl Engine {
         }
 
         if key == keys::DELETE {
+            if self.buf.is_empty() {
+                self.sentence_token.pop();
+                self.phrase_words.clear();
+            }
             // Backspace-after-space feature: restore previous word when all spaces deleted
             // Track spaces typed after commit, restore word when counter reaches 0
             if self.spaces_after_commit > 0 && self.buf.is_empty() {
@@ -980,6 +1795,10 @@ impl Engine {
             self.restored_is_ascii = false;
         }
 
+        if let Some(result) = self.try_verbatim_token(key, caps) {
+            return result;
+        }
+
         // Issue #212: Reset has_non_letter_prefix when user starts typing letter into empty buffer
         // This allows shortcuts to work after: expand → delete all → retype
         // e.g., "ko" → "không " → backspace×6 → "ko" → should expand again
@@ -1172,145 +1991,781 @@ impl Engine {
                         self.buf.push(Char::new(k, c));
                     }
 
-                    // Reapply the mark to the vowel at the same position
-                    // Note: vowel_pos is still valid since raw_input has all chars
-                    if mark_val > 0 {
-                        if let Some(c) = self.buf.get_mut(vowel_pos) {
-                            c.mark = mark_val;
-                        }
-                    }
+                    // Reapply the mark to the vowel at the same position
+                    // Note: vowel_pos is still valid since raw_input has all chars
+                    if mark_val > 0 {
+                        if let Some(c) = self.buf.get_mut(vowel_pos) {
+                            c.mark = mark_val;
+                        }
+                    }
+
+                    self.last_transform = None;
+                    self.had_vowel_triggered_circumflex = false;
+                    // Keep had_any_transform if mark was preserved
+                    if mark_val == 0 {
+                        self.had_any_transform = false;
+                    }
+
+                    // Build output chars (with mark if any)
+                    let output: Vec<char> = self
+                        .buf
+                        .iter()
+                        .filter_map(|c| {
+                            if c.mark > 0 {
+                                chars::to_char(c.key, c.caps, tone::NONE, c.mark)
+                            } else {
+                                utils::key_to_char(c.key, c.caps)
+                            }
+                        })
+                        .collect();
+
+                    return Result::send(backspace, &output);
+                }
+            }
+        }
+
+        // In VNI mode, if Shift is pressed with a number key, skip all modifiers
+        // User wants the symbol (@ for Shift+2, # for Shift+3, etc.), not VNI marks
+        let skip_vni_modifiers = self.method == 1 && shift && keys::is_number(key);
+
+        // Skip modifiers after circumflex revert (ooo→oo, eee→ee, aaa→aa)
+        // Example: "booo" → "boo" (revert), then "s" → "boos" (not "boós")
+        // Example: "seee" → "see" (revert), then "m" → "seem" (not "seém")
+        // This applies to ALL subsequent keys until word ends (space/break clears flag)
+        // EXCEPTION: Vietnamese triple-o words with valid tones (s=sắc, f=huyền)
+        // Triple-o words only use sắc or huyền tones, NOT ngã (x), hỏi (r), nặng (j)
+        let is_valid_triple_o_tone =
+            (key == keys::S || key == keys::F) && self.is_vietnamese_triple_o_word();
+        let skip_after_revert = self.had_circumflex_revert && !is_valid_triple_o_tone;
+
+        // Check modifiers by scanning buffer for patterns
+
+        // 1. Stroke modifier (d → đ)
+        if !skip_vni_modifiers && m.stroke(key) {
+            if let Some(result) = self.try_stroke(key, caps) {
+                return result;
+            }
+        }
+
+        // 2. Tone modifier (circumflex, horn, breve)
+        if !skip_vni_modifiers && !skip_after_revert {
+            if let Some(tone_type) = m.tone(key) {
+                let targets = m.tone_targets(key);
+                if let Some(result) = self.try_tone(key, caps, tone_type, targets) {
+                    return result;
+                }
+            }
+        }
+
+        // 3. Mark modifier
+        if !skip_vni_modifiers && !skip_after_revert {
+            if let Some(mark_val) = m.mark(key) {
+                if let Some(result) = self.try_mark(key, caps, mark_val) {
+                    return result;
+                }
+            }
+        }
+
+        // 4. Remove modifier
+        // Only consume key if there's something to remove; otherwise fall through to normal letter
+        // This allows shortcuts like "zz" to work when buffer has no marks/tones to remove
+        if !skip_vni_modifiers && m.remove(key) {
+            if let Some(result) = self.try_remove() {
+                return result;
+            }
+        }
+
+        // 5. In Telex: "w" as vowel "ư" when valid Vietnamese context
+        // Examples: "w" → "ư", "nhw" → "như", but "kw" → "kw" (invalid)
+        if self.method == 0 && key == keys::W {
+            if let Some(result) = self.try_w_as_vowel(caps) {
+                return result;
+            }
+        }
+
+        // Not a modifier - normal letter
+        self.handle_normal_letter(key, caps)
+    }
+
+    /// Try word boundary shortcuts (triggered by space, punctuation, etc.)
+    /// The `trigger_char` is appended to the output (space for space, punctuation for punctuation)
+    fn try_word_boundary_shortcut_with_char(&mut self, trigger_char: char) -> Result {
+        // Issue #107: Allow shortcuts with special char prefix (like "#fne")
+        // If shortcut_prefix is set, we still try to match even with empty buffer
+        if self.buf.is_empty() && self.shortcut_prefix.is_empty() {
+            return Result::none();
+        }
+
+        // Don't trigger shortcut if word has non-letter prefix (like "149k")
+        // But DO allow shortcut_prefix (like "#fne") - that's intentional
+        if self.has_non_letter_prefix {
+            return Result::none();
+        }
+
+        // Build full trigger string including shortcut_prefix if present
+        let full_trigger = if self.shortcut_prefix.is_empty() {
+            self.buf.to_full_string()
+        } else {
+            format!("{}{}", self.shortcut_prefix, self.buf.to_full_string())
+        };
+
+        let input_method = self.current_input_method();
+
+        // Check for word boundary shortcut match
+        // For SPACE: append to output (space is "consumed" via Result::forward later)
+        // For punctuation: pass None - don't append, platform layer types it normally
+        // (This matches auto-restore behavior which also doesn't append break char)
+        let key_char = if trigger_char == ' ' {
+            Some(' ')
+        } else {
+            None // Punctuation: don't append, let platform type it
+        };
+        // Raw keys differ from the composed text once transforms applied ("ddc" → "đc"):
+        // each shortcut is then checked against the text it declares
+        let raw = self.get_raw_input_string_preserve_case();
+        let raw_trigger = format!("{}{}", self.shortcut_prefix, raw);
+        let matched = if raw_trigger == full_trigger {
+            self.shortcuts
+                .try_match_for_method(&full_trigger, key_char, true, input_method)
+        } else {
+            self.shortcuts
+                .try_match_source_for_method(
+                    &full_trigger,
+                    MatchSource::Composed,
+                    key_char,
+                    true,
+                    input_method,
+                )
+                .or_else(|| {
+                    self.shortcuts.try_match_source_for_method(
+                        &raw_trigger,
+                        MatchSource::RawKeys,
+                        key_char,
+                        true,
+                        input_method,
+                    )
+                })
+        };
+        if let Some(mut m) = matched {
+            // Erase what is on screen: the composed trigger, whichever text matched
+            m.backspace_count = full_trigger.chars().count();
+            let output: Vec<char> = m.output.chars().collect();
+            // Space is part of the output, other boundary keys pass through after it
+            let mut typed: Vec<char> = full_trigger.chars().collect();
+            let passthrough = if key_char.is_some() {
+                typed.push(trigger_char);
+                None
+            } else {
+                Some(trigger_char)
+            };
+            // backspace_count = trigger.len() which already includes prefix (e.g., "#fne" = 4)
+            return self.shortcut_result(
+                m.backspace_count as u8,
+                output,
+                false,
+                m.cursor_left,
+                typed,
+                passthrough,
+            );
+        }
+
+        Result::none()
+    }
+
+    /// Build the result for a shortcut expansion
+    ///
+    /// Output longer than `MAX_REPLACEMENT_LEN` is split when `long_output` is on:
+    /// the first chunk is returned with `FLAG_MORE_OUTPUT`, the rest is queued for
+    /// `next_output_chunk`. The caret offset goes with the last chunk.
+    /// Otherwise the output is cut to one result.
+    ///
+    /// `typed` is the text being replaced (trigger as typed, plus the boundary key if
+    /// it is part of `output`); `passthrough` is a boundary key the platform types
+    /// after the output. Both are remembered for Backspace undo.
+    fn shortcut_result(
+        &mut self,
+        backspace: u8,
+        mut output: Vec<char>,
+        consumed: bool,
+        mut cursor_left: usize,
+        mut typed: Vec<char>,
+        passthrough: Option<char>,
+    ) -> Result {
+        if !self.long_output && output.len() > MAX_REPLACEMENT_LEN {
+            let cut = output.len() - MAX_REPLACEMENT_LEN;
+            output.truncate(MAX_REPLACEMENT_LEN);
+            cursor_left = cursor_left.saturating_sub(cut);
+        }
+        // Undo needs the caret at the end and must fit in one result
+        let screen_len = output.len() + usize::from(passthrough.is_some());
+        typed.extend(passthrough);
+        self.last_expansion = (self.shortcut_undo
+            && cursor_left == 0
+            && screen_len <= u8::MAX as usize
+            && typed.len() <= MAX_REPLACEMENT_LEN)
+            .then_some(ExpansionUndo { screen_len, typed });
+
+        self.pending_output.clear();
+        if output.len() > MAX_REPLACEMENT_LEN {
+            self.pending_output
+                .extend(output.split_off(MAX_REPLACEMENT_LEN));
+            self.pending_cursor_left = cursor_left;
+        }
+        let mut result = if consumed {
+            Result::send_consumed(backspace, &output)
+        } else {
+            Result::send(backspace, &output)
+        };
+        if self.pending_output.is_empty() {
+            result = result.with_cursor_left(cursor_left);
+        } else {
+            result.flags |= FLAG_MORE_OUTPUT;
+        }
+        result
+    }
+
+    /// Fetch the next chunk of a long shortcut expansion
+    ///
+    /// Call while the previous result has `FLAG_MORE_OUTPUT` and inject each chunk
+    /// (no backspaces) right after the previous one. Pending chunks are dropped
+    /// when the next key is processed.
+    pub fn next_output_chunk(&mut self) -> Option<Result> {
+        if self.pending_output.is_empty() {
+            return None;
+        }
+        let len = self.pending_output.len().min(MAX_REPLACEMENT_LEN);
+        let chunk: Vec<char> = self.pending_output.drain(..len).collect();
+        if self.line_editing {
+            self.line.apply(0, &chunk);
+        }
+        let mut result = Result::send(0, &chunk);
+        if self.pending_output.is_empty() {
+            result = result.with_cursor_left(self.pending_cursor_left);
+            if self.line_editing {
+                self.line.move_left(self.pending_cursor_left);
+            }
+        } else {
+            result.flags |= FLAG_MORE_OUTPUT;
+        }
+        Some(result)
+    }
+
+    /// Check if a long expansion still has chunks to fetch
+    pub fn has_pending_output(&self) -> bool {
+        !self.pending_output.is_empty()
+    }
+
+    /// Remember the buffer state before a key that added diacritics
+    ///
+    /// A key that removed diacritics reverted the last transform ("ass" → "as"),
+    /// so its step is dropped. Buffer jumps (word restored from history) are not steps.
+    fn record_transform(&mut self, before: Buffer, key: u16, caps: bool) {
+        if self.buf.len() > before.len() + 1 {
+            return;
+        }
+        let (old, new) = (diacritic_count(&before), diacritic_count(&self.buf));
+        if new < old {
+            self.transform_history.pop();
+            return;
+        }
+        let appended = self.buf.len() > before.len();
+        let created = appended && self.buf.last().is_some_and(|c| c.has_tone() || c.stroke);
+        // A plain letter completing an earlier transform ("đuơ" + "c" → "đươc")
+        // belongs to that transform's step
+        if new == old || (appended && !created) {
+            return;
+        }
+        if self.transform_history.len() == MAX_TRANSFORM_UNDO {
+            self.transform_history.remove(0);
+        }
+        self.transform_history.push(TransformStep {
+            before,
+            key,
+            caps,
+            created,
+        });
+    }
+
+    /// Undo the last transform of the current word
+    ///
+    /// Diacritics go back to their state before the transform; letters typed
+    /// after it are kept: "được" → "đươc" → "đuoc" → "duoc".
+    /// Returns `Result::none()` when the word has no transform left.
+    pub fn undo_transform(&mut self) -> Result {
+        let Some(step) = self.transform_history.pop() else {
+            return Result::none();
+        };
+        let old = self.buf.clone();
+        for i in 0..self.buf.len() {
+            let prev = step.before.get(i).copied();
+            if let Some(c) = self.buf.get_mut(i) {
+                match prev {
+                    Some(p) => {
+                        c.tone = p.tone;
+                        c.mark = p.mark;
+                        c.stroke = p.stroke;
+                    }
+                    // Char created by the transform: back to the key typed
+                    None if step.created && i == step.before.len() => {
+                        *c = Char::new(step.key, step.caps);
+                    }
+                    None => {
+                        c.tone = 0;
+                        c.mark = 0;
+                        c.stroke = false;
+                    }
+                }
+            }
+        }
+
+        // The undone form is what the user wants: restart from it as if typed plainly
+        self.restore_raw_input_from_buffer(&self.buf.clone());
+        self.had_any_transform = false;
+        self.telex_double_raw = None;
+        self.telex_double_raw_len = 0;
+        self.last_transform = None;
+        self.pending_breve_pos = None;
+        self.pending_u_horn_pos = None;
+
+        let first = (0..self.buf.len())
+            .find(|&i| {
+                let (a, b) = (old.get(i), self.buf.get(i));
+                a.map(|c| (c.key, c.tone, c.mark, c.stroke))
+                    != b.map(|c| (c.key, c.tone, c.mark, c.stroke))
+            })
+            .unwrap_or(self.buf.len());
+        self.rebuild_from(first)
+    }
+
+    /// Undo the last shortcut expansion if `key` is a Backspace right after it
+    ///
+    /// Any other key forgets the expansion. The restored text already contains the
+    /// boundary key, so the word is treated as committed (no backspace-after-space).
+    fn try_undo_expansion(&mut self, key: u16, ctrl: bool) -> Option<Result> {
+        let undo = self.last_expansion.take()?;
+        if key != keys::DELETE || ctrl {
+            return None;
+        }
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        Some(Result::send(undo.screen_len as u8, &undo.typed))
+    }
+
+    /// Autocorrect the word being committed by `boundary` (see `autocorrect`)
+    ///
+    /// Only words typed with diacritics that fail the dictionary are touched.
+    /// The buffer is replaced by the correction, so word history keeps the
+    /// corrected word; an immediate Backspace puts the word back as typed.
+    /// `passthrough`: the host types `boundary` after the output (punctuation,
+    /// Enter) instead of it being part of the output (Space).
+    fn try_autocorrect(&mut self, boundary: char, passthrough: bool) -> Option<Result> {
+        if !self.autocorrect
+            || self.has_non_letter_prefix
+            || !self
+                .buf
+                .iter()
+                .any(|c| c.tone != 0 || c.has_mark() || c.stroke)
+        {
+            return None;
+        }
+        let typed = self.buf.to_full_string();
+        if typed.chars().count() != self.buf.len() || self.is_vietnamese_word(&typed) {
+            return None;
+        }
+        let corrected = autocorrect::correct(&self.buf, |w| self.is_vietnamese_word(w))?;
+
+        let mut output: Vec<char> = corrected.to_full_string().chars().collect();
+        if !passthrough {
+            output.push(boundary);
+        }
+        let mut typed: Vec<char> = typed.chars().collect();
+        typed.push(boundary);
+        let result = Result::send(self.buf.len() as u8, &output);
+        self.last_expansion = Some(ExpansionUndo {
+            screen_len: output.len() + usize::from(passthrough),
+            typed,
+        });
+        self.buf = corrected;
+        Some(result)
+    }
+
+    /// Typography rule for a break key: (chars to delete before the caret, char to type)
+    ///
+    /// None when no rule applies, the text looks like a URL, or the key may be
+    /// part of a shortcut trigger.
+    fn typography_for_break(&self, key: u16, shift: bool) -> Option<(u8, char)> {
+        if !self.typography.is_enabled() {
+            return None;
+        }
+        let ch = break_key_to_char(key, shift)?;
+        let token = format!("{}{}", self.sentence_token, self.buf.to_full_string());
+        let mut candidate = self.shortcut_prefix.clone();
+        candidate.push(ch);
+        if self.token_class.is_some()
+            || typography::is_url_like(&token)
+            || self
+                .shortcuts
+                .has_trigger_prefix_for_method(&candidate, self.current_input_method())
+        {
+            return None;
+        }
+        match ch {
+            '"' | '\'' if self.typography.smart_quotes => {
+                let prev = token
+                    .chars()
+                    .last()
+                    .or((self.spaces_after_commit > 0).then_some(' '));
+                Some((0, typography::smart_quote(ch, prev)))
+            }
+            '-' if self.typography.dashes => typography::dash(&token).map(|d| (1, d)),
+            c if self.typography.punctuation_spacing
+                && typography::is_closing_punctuation(c)
+                && token.is_empty()
+                && self.spaces_after_commit == 1
+                && self.word_history.len > 0 =>
+            {
+                Some((1, c))
+            }
+            _ => None,
+        }
+    }
+
+    /// Classify the token being typed: `sentence_token`, the current word as
+    /// typed (raw keys) and `ch`, the key being typed
+    ///
+    /// `letter` is the (key, caps) of `ch` when it is a letter or digit.
+    /// Returns true when a class is detected. Tokens that may still become a
+    /// shortcut trigger are left alone.
+    fn detect_token_class(&mut self, ch: char, letter: Option<(u16, bool)>) -> bool {
+        if !self.token_detection {
+            return false;
+        }
+        let mut token = self.sentence_token.clone();
+        match self.build_raw_chars_exact() {
+            Some(raw) if !self.buf.is_empty() => token.extend(raw),
+            _ => token.push_str(&self.buf.to_full_string()),
+        }
+        token.push(ch);
+        if self
+            .shortcuts
+            .has_trigger_prefix_for_method(&token, self.current_input_method())
+        {
+            return false;
+        }
+        self.token_class = token_class::classify(&token).or_else(|| {
+            letter
+                .filter(|&(key, caps)| self.starts_camel_hump(key, caps))
+                .map(|_| TokenClass::Identifier)
+        });
+        self.token_class.is_some()
+    }
 
-                    self.last_transform = None;
-                    self.had_vowel_triggered_circumflex = false;
-                    // Keep had_any_transform if mark was preserved
-                    if mark_val == 0 {
-                        self.had_any_transform = false;
-                    }
+    /// Uppercase letter after a lowercase one that cannot continue the syllable
+    ///
+    /// "getU", "onC" and "WebS" start a camelCase hump; "viE" (→ "viẾt") and
+    /// modifier keys typed with Shift stay Vietnamese.
+    fn starts_camel_hump(&self, key: u16, caps: bool) -> bool {
+        if !caps || !keys::is_letter(key) || self.buf.last().is_none_or(|c| c.caps) {
+            return false;
+        }
+        let m = input::get(self.method);
+        if m.mark(key).is_some() || m.tone(key).is_some() || m.remove(key) || m.stroke(key) {
+            return false;
+        }
+        let mut buffer_keys: Vec<u16> = self.buf.iter().map(|c| c.key).collect();
+        buffer_keys.push(key);
+        !is_valid_for_transform_with_foreign(&buffer_keys, self.allow_foreign_consonants)
+    }
 
-                    // Build output chars (with mark if any)
-                    let output: Vec<char> = self
-                        .buf
-                        .iter()
-                        .filter_map(|c| {
-                            if c.mark > 0 {
-                                chars::to_char(c.key, c.caps, tone::NONE, c.mark)
-                            } else {
-                                utils::key_to_char(c.key, c.caps)
-                            }
-                        })
-                        .collect();
+    /// Letter or digit inside (or starting) a verbatim token: typed as is
+    fn try_verbatim_token(&mut self, key: u16, caps: bool) -> Option<Result> {
+        let ch = utils::key_to_char(key, caps)?;
+        if self.token_class.is_none() && !self.detect_token_class(ch, Some((key, caps))) {
+            return None;
+        }
+        let result = if self.buf.is_empty() {
+            Result::none()
+        } else {
+            self.restore_verbatim_token(Some(ch))
+        };
+        self.sentence_token.push_str(&self.buf.to_full_string());
+        self.sentence_token.push(ch);
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        self.phrase_words.clear();
+        Some(result)
+    }
 
-                    return Result::send(backspace, &output);
-                }
-            }
+    /// Put the word before a newly detected token back to its raw keys
+    ///
+    /// "toanf" + "@" → "toanf@", not "toàn@". `typed` is the letter being typed
+    /// (sent with the word); a break key passes through after the result.
+    fn restore_verbatim_token(&mut self, typed: Option<char>) -> Result {
+        let shown = self.buf.to_full_string();
+        let raw = self
+            .build_raw_chars_exact()
+            .unwrap_or_else(|| shown.chars().collect());
+        if raw.iter().copied().eq(shown.chars()) {
+            return Result::none();
         }
+        let mut output = raw;
+        output.extend(typed);
+        let result = Result::send(self.buf.len() as u8, &output);
+        self.buf.clear();
+        for &(key, caps, _) in &self.raw_input {
+            self.buf.push(Char::new(key, caps));
+        }
+        result
+    }
 
-        // In VNI mode, if Shift is pressed with a number key, skip all modifiers
-        // User wants the symbol (@ for Shift+2, # for Shift+3, etc.), not VNI marks
-        let skip_vni_modifiers = self.method == 1 && shift && keys::is_number(key);
+    /// Escape key configured for `language`
+    fn word_escape(&self, language: Language) -> Option<WordEscape> {
+        match language {
+            Language::English => self.force_english,
+            Language::Vietnamese => self.force_vietnamese,
+        }
+    }
 
-        // Skip modifiers after circumflex revert (ooo→oo, eee→ee, aaa→aa)
-        // Example: "booo" → "boo" (revert), then "s" → "boos" (not "boós")
-        // Example: "seee" → "see" (revert), then "m" → "seem" (not "seém")
-        // This applies to ALL subsequent keys until word ends (space/break clears flag)
-        // EXCEPTION: Vietnamese triple-o words with valid tones (s=sắc, f=huyền)
-        // Triple-o words only use sắc or huyền tones, NOT ngã (x), hỏi (r), nặng (j)
-        let is_valid_triple_o_tone =
-            (key == keys::S || key == keys::F) && self.is_vietnamese_triple_o_word();
-        let skip_after_revert = self.had_circumflex_revert && !is_valid_triple_o_tone;
+    /// Language whose escape key is `escape`
+    fn escape_language(&self, escape: WordEscape) -> Option<Language> {
+        [Language::English, Language::Vietnamese]
+            .into_iter()
+            .find(|&language| self.word_escape(language) == Some(escape))
+    }
 
-        // Check modifiers by scanning buffer for patterns
+    /// Handle a prefix escape key, or the second tap of a double-tapped one
+    ///
+    /// `tap` is the word saved by the previous key if it was a first tap.
+    fn try_word_escape(&mut self, key: u16, ctrl: bool, tap: Option<EscapeTap>) -> Option<Result> {
+        if ctrl {
+            return None;
+        }
+        if let Some(tap) =
+            tap.filter(|tap| self.word_escape(tap.language) == Some(WordEscape::DoubleSuffix(key)))
+        {
+            return Some(self.force_tapped_word(tap));
+        }
+        let language = self.escape_language(WordEscape::Prefix(key))?;
+        if !self.buf.is_empty() {
+            return None;
+        }
+        if self.forced_word == Some(language) {
+            // Pressed twice: type the key itself
+            self.forced_word = None;
+            return None;
+        }
+        self.forced_word = Some(language);
+        Some(Result::send_consumed(0, &[]))
+    }
 
-        // 1. Stroke modifier (d → đ)
-        if !skip_vni_modifiers && m.stroke(key) {
-            if let Some(result) = self.try_stroke(key, caps) {
-                return result;
-            }
+    /// Save the word before the first tap of a double-tapped escape key
+    fn start_escape_tap(&self, key: u16, ctrl: bool) -> Option<EscapeTap> {
+        if ctrl || key == keys::SPACE || self.buf.is_empty() {
+            return None;
         }
+        let language = self.escape_language(WordEscape::DoubleSuffix(key))?;
+        let shown = self.buf.to_full_string();
+        Some(EscapeTap {
+            language,
+            buf: self.buf.clone(),
+            raw_input: self.raw_input.clone(),
+            raw: self
+                .build_raw_chars_exact()
+                .unwrap_or_else(|| shown.chars().collect()),
+            sentence_token: self.sentence_token.clone(),
+            screen_len: self.buf.len(),
+        })
+    }
 
-        // 2. Tone modifier (circumflex, horn, breve)
-        if !skip_vni_modifiers && !skip_after_revert {
-            if let Some(tone_type) = m.tone(key) {
-                let targets = m.tone_targets(key);
-                if let Some(result) = self.try_tone(key, caps, tone_type, targets) {
-                    return result;
+    /// Replace the word and the first tap with the forced word
+    ///
+    /// The word becomes current again (uncommitted), so the next boundary key
+    /// commits it without auto-restore.
+    fn force_tapped_word(&mut self, tap: EscapeTap) -> Result {
+        let output: Vec<char> = match tap.language {
+            Language::English => tap.raw,
+            Language::Vietnamese => tap.buf.to_full_string().chars().collect(),
+        };
+        let backspace = tap.screen_len as u8;
+        self.clear();
+        self.word_history.clear();
+        self.spaces_after_commit = 0;
+        self.phrase_words.clear();
+        self.token_class = None;
+        self.sentence_token = tap.sentence_token;
+        self.raw_input = tap.raw_input;
+        self.buf = match tap.language {
+            Language::English => {
+                let mut buf = Buffer::new();
+                for &(key, caps, _) in &self.raw_input {
+                    buf.push(Char::new(key, caps));
                 }
+                buf
             }
-        }
+            Language::Vietnamese => tap.buf,
+        };
+        self.forced_word = Some(tap.language);
+        Result::send_consumed(backspace, &output)
+    }
 
-        // 3. Mark modifier
-        if !skip_vni_modifiers && !skip_after_revert {
-            if let Some(mark_val) = m.mark(key) {
-                if let Some(result) = self.try_mark(key, caps, mark_val) {
-                    return result;
+    /// Commit the current word in the language chosen with an escape key
+    ///
+    /// English sends the raw keys (`boundary` appended, as for auto-restore);
+    /// Vietnamese keeps the composed word. With learning on, the raw keys go
+    /// to the never-transform list or the word to the never-restore list.
+    fn commit_forced_word(&mut self, language: Language, boundary: Option<char>) -> Result {
+        if self.buf.is_empty() {
+            return Result::none();
+        }
+        let shown = self.buf.to_full_string();
+        let raw = self
+            .build_raw_chars_exact()
+            .unwrap_or_else(|| shown.chars().collect());
+        if self.learn_forced_words {
+            let typed = self.get_raw_input_string();
+            match language {
+                Language::English => {
+                    self.word_lists.remove_never_restore(&shown);
+                    self.word_lists.add_never_transform(&typed);
+                }
+                Language::Vietnamese => {
+                    self.word_lists.remove_never_transform(&typed);
+                    self.word_lists.add_never_restore(&shown);
                 }
             }
         }
-
-        // 4. Remove modifier
-        // Only consume key if there's something to remove; otherwise fall through to normal letter
-        // This allows shortcuts like "zz" to work when buffer has no marks/tones to remove
-        if !skip_vni_modifiers && m.remove(key) {
-            if let Some(result) = self.try_remove() {
-                return result;
-            }
+        if language == Language::Vietnamese || raw.iter().copied().eq(shown.chars()) {
+            return Result::none();
         }
-
-        // 5. In Telex: "w" as vowel "ư" when valid Vietnamese context
-        // Examples: "w" → "ư", "nhw" → "như", but "kw" → "kw" (invalid)
-        if self.method == 0 && key == keys::W {
-            if let Some(result) = self.try_w_as_vowel(caps) {
-                return result;
-            }
+        let mut output = raw;
+        output.extend(boundary);
+        let result = Result::send(self.buf.len() as u8, &output);
+        self.buf.clear();
+        for &(key, caps, _) in &self.raw_input {
+            self.buf.push(Char::new(key, caps));
         }
+        result
+    }
 
-        // Not a modifier - normal letter
-        self.handle_normal_letter(key, caps)
+    /// Sentence-ending punctuation that does not close a known abbreviation
+    /// ("TP." and "v.v." keep the next word lowercase)
+    fn ends_sentence(&self, key: u16, shift: bool) -> bool {
+        is_sentence_ending_punctuation(key, shift)
+            && !(key == keys::DOT && is_abbreviation(&self.sentence_token))
     }
 
-    /// Try word boundary shortcuts (triggered by space, punctuation, etc.)
-    /// The `trigger_char` is appended to the output (space for space, punctuation for punctuation)
-    fn try_word_boundary_shortcut_with_char(&mut self, trigger_char: char) -> Result {
-        // Issue #107: Allow shortcuts with special char prefix (like "#fne")
-        // If shortcut_prefix is set, we still try to match even with empty buffer
-        if self.buf.is_empty() && self.shortcut_prefix.is_empty() {
-            return Result::none();
+    /// Capitalize a proper noun ending with the word being committed
+    ///
+    /// Tries the longest phrase first: the current word plus the words before it
+    /// that were each followed by a single space ("việt nam" → "Việt Nam").
+    /// `boundary` is the key char included in the output (Space), None when the
+    /// host types it. Capitals are only added, never removed ("HÀ NỘI" stays);
+    /// rewritten words keep their capitals in word history.
+    fn try_proper_noun(&mut self, boundary: Option<char>) -> Option<Result> {
+        if !self.auto_capitalize || self.proper_nouns.is_empty() || self.buf.is_empty() {
+            return None;
         }
-
-        // Don't trigger shortcut if word has non-letter prefix (like "149k")
-        // But DO allow shortcut_prefix (like "#fne") - that's intentional
-        if self.has_non_letter_prefix {
-            return Result::none();
+        let current = self.buf.to_full_string();
+        if current.chars().count() != self.buf.len() {
+            return None;
         }
+        for previous in (0..=self.phrase_words.len()).rev() {
+            let start = self.phrase_words.len() - previous;
+            let words: Vec<String> = self.phrase_words[start..]
+                .iter()
+                .map(Buffer::to_full_string)
+                .chain(std::iter::once(current.clone()))
+                .collect();
+            let typed = words.join(" ");
+            let Some(display) = self.proper_nouns.lookup(&typed) else {
+                continue;
+            };
+            // Only add capitals: "HÀ NỘI" or "Hà NỘI" is already capitalized enough
+            let capitals = |s: &str| s.chars().filter(|c| c.is_uppercase()).count();
+            if display.chars().count() != typed.chars().count()
+                || capitals(&typed) >= capitals(display)
+            {
+                return None;
+            }
+            let display: Vec<char> = typed
+                .chars()
+                .zip(display.chars())
+                .map(|(t, d)| if t.is_uppercase() { t } else { d })
+                .collect();
 
-        // Build full trigger string including shortcut_prefix if present
-        let full_trigger = if self.shortcut_prefix.is_empty() {
-            self.buf.to_full_string()
-        } else {
-            format!("{}{}", self.shortcut_prefix, self.buf.to_full_string())
-        };
+            // Carry the capitals into the buffers (history and current word)
+            let mut caps = display.iter().map(|c| c.is_uppercase());
+            for buf in self.phrase_words[start..]
+                .iter_mut()
+                .chain(std::iter::once(&mut self.buf))
+            {
+                for i in 0..buf.len() {
+                    if let (Some(c), Some(upper)) = (buf.get_mut(i), caps.next()) {
+                        c.caps |= upper;
+                    }
+                }
+                caps.next(); // space
+            }
+            for _ in 0..previous {
+                self.word_history.pop();
+            }
+            for buf in &self.phrase_words[start..] {
+                self.word_history.push(buf.clone());
+            }
 
-        let input_method = self.current_input_method();
+            let mut output = display;
+            output.extend(boundary);
+            return Some(Result::send(typed.chars().count() as u8, &output));
+        }
+        None
+    }
 
-        // Check for word boundary shortcut match
-        // For SPACE: append to output (space is "consumed" via Result::forward later)
-        // For punctuation: pass None - don't append, platform layer types it normally
-        // (This matches auto-restore behavior which also doesn't append break char)
-        let key_char = if trigger_char == ' ' {
-            Some(' ')
-        } else {
-            None // Punctuation: don't append, let platform type it
-        };
-        if let Some(m) =
-            self.shortcuts
-                .try_match_for_method(&full_trigger, key_char, true, input_method)
+    /// Strict spelling for the word being committed (see `StrictSpelling`)
+    ///
+    /// `result` is the commit result so far; only a plain commit (action None) is
+    /// checked. `boundary` is the key char included in the output (Space), None
+    /// when the host types it (punctuation).
+    fn apply_strict_spelling(&mut self, mut result: Result, boundary: Option<char>) -> Result {
+        if self.strict_spelling == StrictSpelling::Off
+            || result.action != 0
+            || !self.is_misspelled_word()
         {
-            let output: Vec<char> = m.output.chars().collect();
-            // backspace_count = trigger.len() which already includes prefix (e.g., "#fne" = 4)
-            return Result::send(m.backspace_count as u8, &output);
+            return result;
+        }
+        if self.strict_spelling == StrictSpelling::Flag {
+            result.flags |= FLAG_MISSPELLED;
+            return result;
+        }
+        let Some(mut raw_chars) = self.build_raw_chars_exact() else {
+            return result;
+        };
+        if raw_chars.iter().collect::<String>() == self.buf.to_full_string() {
+            return result;
+        }
+        let backspace = self.buf.len() as u8;
+        // Word history keeps the raw word, like auto-restore
+        self.buf.clear();
+        for &(key, caps, _) in &self.raw_input {
+            self.buf.push(Char::new(key, caps));
         }
+        raw_chars.extend(boundary);
+        Result::send(backspace, &raw_chars)
+    }
 
-        Result::none()
+    /// Buffer is a letters-only word found in neither dictionary
+    ///
+    /// Words on the never-restore list count as valid.
+    fn is_misspelled_word(&self) -> bool {
+        if self.buf.is_empty() || self.has_non_letter_prefix {
+            return false;
+        }
+        let word = self.buf.to_full_string();
+        word.chars().all(char::is_alphabetic)
+            && !self.word_lists.is_never_restore(&word)
+            && !self.is_vietnamese_word(&word)
+            && !self.is_english_word(&word)
     }
 
     /// Try word boundary shortcuts (triggered by space)
@@ -1327,6 +2782,11 @@ impl Engine {
     /// - "ww" → revert to "w" (shortcut skipped)
     /// - "www" → "ww" (subsequent w just adds normally)
     fn try_w_as_vowel(&mut self, caps: bool) -> Option<Result> {
+        // Never-transform list: keep raw keystrokes as typed
+        if self.is_never_transform_input() {
+            return None;
+        }
+
         // Issue #44: If breve is pending (deferred due to open syllable),
         // don't convert w→ư. Let w be added as regular letter.
         // Example: "aw" → breve deferred → should stay "aw", not become "aư"
@@ -1418,6 +2878,11 @@ impl Engine {
     /// In VNI mode, '9' is always an intentional stroke command (not a letter), so
     /// delayed stroke is allowed (e.g., "duong9" → "đuong").
     fn try_stroke(&mut self, key: u16, caps: bool) -> Option<Result> {
+        // Never-transform list: keep raw keystrokes as typed
+        if self.is_never_transform_input() {
+            return None;
+        }
+
         // If stroke was already reverted in this word (ddd → dd), skip further stroke attempts
         // This prevents "ddddd" from oscillating and ensures subsequent 'd's are just letters
         if self.stroke_reverted && key == keys::D {
@@ -1682,6 +3147,11 @@ impl Engine {
             return None;
         }
 
+        // Never-transform list: keep raw keystrokes as typed
+        if self.is_never_transform_input() {
+            return None;
+        }
+
         // Issue #44: Cancel pending breve if same modifier pressed again ("aww" → "aw")
         // When breve was deferred and user presses 'w' again, cancel without adding another 'w'
         if self.pending_breve_pos.is_some()
@@ -2598,6 +4068,11 @@ impl Engine {
             return None;
         }
 
+        // Never-transform list: keep raw keystrokes as typed ("fix" stays "fix")
+        if self.is_never_transform_input() {
+            return None;
+        }
+
         // Check revert first
         if let Some(Transform::Mark(last_key, _)) = self.last_transform {
             if last_key == key {
@@ -2796,7 +4271,7 @@ impl Engine {
                             .filter_map(|&(k, caps, _)| utils::key_to_char(k, caps))
                             .collect::<String>()
                             .to_lowercase();
-                        if english_dict::is_english_word(&raw_str) {
+                        if self.is_english_word(&raw_str) {
                             // Raw input is English - don't apply delayed circumflex
                             // Let the letter be added normally, auto-restore will handle it
                         } else {
@@ -2921,7 +4396,7 @@ impl Engine {
         let has_qu = self.has_qu_initial();
         let has_gi = self.has_gi_initial();
         let pos =
-            Phonology::find_tone_position(&vowels, has_final, self.modern_tone, has_qu, has_gi);
+            Phonology::find_tone_position(&vowels, has_final, self.tone_placement, has_qu, has_gi);
 
         // Check if target vowel already has the same mark
         // This handles two cases:
@@ -3197,7 +4672,7 @@ impl Engine {
             .filter_map(|&(k, caps, _)| utils::key_to_char(k, caps))
             .collect::<String>()
             .to_lowercase();
-        let is_english_word = english_dict::is_english_word(&raw_str);
+        let is_english_word = self.is_english_word(&raw_str);
 
         // Find vowel with tone mark (sắc/huyền/hỏi/ngã/nặng)
         let tone_info: Option<(usize, u8)> = self
@@ -3291,8 +4766,13 @@ impl Engine {
                 return None;
             }
 
-            let new_pos =
-                Phonology::find_tone_position(&vowels, has_final, self.modern_tone, has_qu, has_gi);
+            let new_pos = Phonology::find_tone_position(
+                &vowels,
+                has_final,
+                self.tone_placement,
+                has_qu,
+                has_gi,
+            );
 
             if new_pos != old_pos {
                 // Move tone from old position to new position
@@ -3372,7 +4852,7 @@ impl Engine {
             .filter_map(|&(key, caps, _)| utils::key_to_char(key, caps))
             .collect::<String>()
             .to_lowercase();
-        if english_dict::is_english_word(&raw_str) {
+        if self.is_english_word(&raw_str) {
             return None;
         }
 
@@ -3775,7 +5255,7 @@ impl Engine {
                     .filter_map(|&(k, caps, _)| utils::key_to_char(k, caps))
                     .collect::<String>()
                     .to_lowercase();
-                if english_dict::is_english_word(&raw_str) {
+                if self.is_english_word(&raw_str) {
                     // Raw input is English - skip circumflex, add vowel normally
                     // The auto-restore will handle restoring the English word
                 } else {
@@ -4283,6 +5763,8 @@ impl Engine {
         self.restored_pending_clear = false;
         self.restored_is_ascii = false;
         self.shortcut_prefix.clear();
+        self.transform_history.clear();
+        self.forced_word = None;
     }
 
     /// Clear everything including word history
@@ -4292,12 +5774,17 @@ impl Engine {
     /// capitalization after paste/cursor change
     pub fn clear_all(&mut self) {
         self.clear();
+        self.line.reset();
+        self.last_expansion = None;
         self.word_history.clear();
         self.spaces_after_commit = 0;
         // Issue #274: Reset auto-capitalize state on cursor change
         // This prevents incorrect capitalization after copy-paste
         self.pending_capitalize = false;
         self.saw_sentence_ending = false;
+        self.sentence_token.clear();
+        self.token_class = None;
+        self.phrase_words.clear();
     }
 
     /// Get the full composed buffer as a Vietnamese string with diacritics.
@@ -4359,12 +5846,29 @@ impl Engine {
     /// `is_word_complete`: true when called on space/break (word is complete)
     ///                     false when called mid-word (during typing)
     fn should_auto_restore(&self, is_word_complete: bool) -> Option<Vec<char>> {
+        if self.raw_input.is_empty() || self.buf.is_empty() {
+            return None;
+        }
+
+        // Never-transform list applies regardless of the auto-restore setting:
+        // transforms applied before the sequence was complete ("mas" → "má" in "mass")
+        // are undone on commit
+        if is_word_complete && self.is_never_transform_input() {
+            let raw_chars = self.build_raw_chars_exact()?;
+            let raw_str: String = raw_chars.iter().collect();
+            if raw_str == self.buf.to_full_string() {
+                return None;
+            }
+            return Some(raw_chars);
+        }
+
         // Only run auto-restore if the feature is enabled
         if !self.english_auto_restore {
             return None;
         }
 
-        if self.raw_input.is_empty() || self.buf.is_empty() {
+        // Never-restore list: user wants this Vietnamese word kept as composed
+        if self.word_lists.is_never_restore(&self.buf.to_full_string()) {
             return None;
         }
 
@@ -4377,6 +5881,45 @@ impl Engine {
             return None;
         }
 
+        // User dictionary takes precedence over embedded dictionaries:
+        // - Vietnamese word added by user → keep Vietnamese ("mĩ" stays even though "mix" is English)
+        // - English word added by user → restore raw keystrokes
+        if self
+            .user_dict
+            .lookup(Language::Vietnamese, &self.buf.to_full_string())
+            == Some(true)
+        {
+            return None;
+        }
+        if self
+            .user_dict
+            .lookup(Language::English, &self.get_raw_input_string())
+            == Some(true)
+        {
+            let raw_chars = self.build_raw_chars_exact()?;
+            let raw_str: String = raw_chars.iter().collect();
+            if raw_str == self.buf.to_full_string() {
+                return None;
+            }
+            return Some(raw_chars);
+        }
+
+        // Bigram context: decide by the previous committed word when it is conclusive
+        if self.context_restore {
+            match self.context_restore_decision() {
+                Some(true) => {
+                    let raw_chars = self.build_raw_chars_exact()?;
+                    let raw_str: String = raw_chars.iter().collect();
+                    if raw_str == self.buf.to_full_string() {
+                        return None;
+                    }
+                    return Some(raw_chars);
+                }
+                Some(false) => return None,
+                None => {}
+            }
+        }
+
         // Issue #211: Skip auto-restore for extended character patterns
         // When user types "ơiiiiii", "điiii", "ôiiii", "vàooooo", etc.
         // This is intentional Vietnamese (casual messaging) not English.
@@ -4522,7 +6065,7 @@ impl Engine {
 
                 let has_stroke = self.buf.iter().any(|c| c.stroke);
                 let buffer_invalid_vn = self.is_buffer_invalid_vietnamese();
-                let raw_in_english_dict = english_dict::is_english_word(&raw_str);
+                let raw_in_english_dict = self.is_english_word(&raw_str);
 
                 // W at end pattern: foreign words like moscow, warsaw, saw, law
                 let w_at_end = self
@@ -4546,7 +6089,7 @@ impl Engine {
                     // If buffer is a known English word, keep it (e.g., "lissa" → "lisa")
                     // If buffer is NOT a known word, restore original (e.g., "larissa" → "larissa")
                     let buffer_str = self.get_buffer_string().to_lowercase();
-                    if !english_dict::is_english_word(&buffer_str) {
+                    if !self.is_english_word(&buffer_str) {
                         // Buffer not in dict → restore to original English
                         return self.build_raw_chars_exact();
                     }
@@ -4577,7 +6120,7 @@ impl Engine {
 
                         if is_double_ss || is_double_ff {
                             let original_lower = stored.to_lowercase();
-                            if english_dict::is_english_word(&original_lower) {
+                            if self.is_english_word(&original_lower) {
                                 // EXCEPTIONS: certain words should keep reverted form (buffer)
                                 // instead of restoring to raw double letter pattern.
                                 // This handles cases where collapsed buffer is more common:
@@ -4657,7 +6200,7 @@ impl Engine {
                 //    - "herer": e-r-e-r (V-M-V-M) → keep "her"
                 //    - "harare": a-r-a-r-e (different vowels a≠e) → skip fix
                 let raw_input_str = self.get_raw_input_string();
-                let raw_is_english = english_dict::is_english_word(&raw_input_str);
+                let raw_is_english = self.is_english_word(&raw_input_str);
                 let chars: Vec<char> = raw_input_str.chars().collect();
 
                 if !raw_is_english && chars.len() >= 4 {
@@ -5241,14 +6784,10 @@ impl Engine {
 
         // DICTIONARY-BASED VALIDATION (when english_auto_restore is enabled)
         // If word is in Vietnamese dictionary, it's definitely valid Vietnamese.
-        // Uses check_with_style to respect modern_tone setting (DauMoi vs DauCu).
+        // Uses check_with_style to respect tone_placement setting (DauMoi vs DauCu).
         if self.english_auto_restore {
             let buffer_str = self.buf.to_full_string();
-            if vietnamese_spellcheck::check_with_style_and_foreign(
-                &buffer_str,
-                self.modern_tone,
-                self.allow_foreign_consonants,
-            ) {
+            if self.is_vietnamese_word(&buffer_str) {
                 return false; // Valid VN word in dictionary
             }
 
@@ -5279,7 +6818,7 @@ impl Engine {
                     )
             });
 
-            if has_telex_double && english_dict::is_english_word(&raw_str) {
+            if has_telex_double && self.is_english_word(&raw_str) {
                 return true; // Telex double + Not in VN dict + IS in EN dict → invalid VN
             }
         }
@@ -6187,7 +7726,7 @@ impl Engine {
                             .iter()
                             .filter_map(|&(k, c, s)| utils::key_to_char_ext(k, c, s))
                             .collect();
-                        let raw_in_dict = english_dict::is_english_word(&raw_str);
+                        let raw_in_dict = self.is_english_word(&raw_str);
 
                         // If raw is NOT in English dict AND buffer is valid Vietnamese, keep it
                         if !raw_in_dict && !self.is_buffer_invalid_vietnamese() {
@@ -6764,7 +8303,7 @@ impl Engine {
                                 .iter()
                                 .filter_map(|&(k, c, s)| utils::key_to_char_ext(k, c, s))
                                 .collect();
-                            if english_dict::is_english_word(&raw_str) {
+                            if self.is_english_word(&raw_str) {
                                 return true; // Restore to English
                             }
                             // Not English word, keep Vietnamese
@@ -6852,7 +8391,7 @@ impl Engine {
                                                 utils::key_to_char_ext(k, c, s)
                                             })
                                             .collect();
-                                        if english_dict::is_english_word(&raw_str) {
+                                        if self.is_english_word(&raw_str) {
                                             return true; // English word (param, etc.)
                                         }
                                         // Not in English dict → keep Vietnamese (vận, hận, etc.)
@@ -6939,7 +8478,7 @@ impl Engine {
                                     .iter()
                                     .filter_map(|&(k, c, s)| utils::key_to_char_ext(k, c, s))
                                     .collect();
-                                if !english_dict::is_english_word(&raw_str) {
+                                if !self.is_english_word(&raw_str) {
                                     // Not a common English word, keep Vietnamese
                                     continue;
                                 }
@@ -6987,7 +8526,7 @@ impl Engine {
                                     .iter()
                                     .filter_map(|&(k, c, s)| utils::key_to_char_ext(k, c, s))
                                     .collect();
-                                if !english_dict::is_english_word(&raw_str) {
+                                if !self.is_english_word(&raw_str) {
                                     // Not a common English word, keep Vietnamese
                                     continue;
                                 }
@@ -7845,6 +9384,7 @@ mod tests {
     /// - "booo" → "boo" (revert), then "s" → "boos" (not "boós")
     /// - "seee" → "see" (revert), then "m" → "seem" (not "seém")
     /// - "booo" + "k" → "book" (consonant also literal)
+    ///
     /// Note: Only works with valid Vietnamese initials (b, c, d, h, l, m, n, p, s, t, etc.)
     #[test]
     fn test_literal_after_circumflex_revert() {
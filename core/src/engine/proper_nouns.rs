@@ -0,0 +1,93 @@
+//! Proper Nouns - user list of names capitalized mid-sentence
+//!
+//! Each entry is a phrase of one or more words in its display form
+//! ("Việt Nam", "Hà Nội", "Huế"). When the last committed words match an entry
+//! case-insensitively, auto-capitalize rewrites them in the display form.
+
+use std::collections::HashMap;
+
+/// Longest phrase, in words, that can be matched
+pub const MAX_PHRASE_WORDS: usize = 4;
+
+/// Proper noun phrases keyed by their lowercase form
+#[derive(Debug, Default)]
+pub struct ProperNouns {
+    phrases: HashMap<String, String>,
+}
+
+impl ProperNouns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Single spaces between words, None if empty or too long
+    fn normalize(phrase: &str) -> Option<String> {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        if words.is_empty() || words.len() > MAX_PHRASE_WORDS {
+            None
+        } else {
+            Some(words.join(" "))
+        }
+    }
+
+    /// Add a phrase in display form, returns false if empty or longer than
+    /// `MAX_PHRASE_WORDS` words
+    pub fn add(&mut self, phrase: &str) -> bool {
+        let Some(phrase) = Self::normalize(phrase) else {
+            return false;
+        };
+        self.phrases.insert(phrase.to_lowercase(), phrase);
+        true
+    }
+
+    /// Remove a phrase (any case), returns true if it was present
+    pub fn remove(&mut self, phrase: &str) -> bool {
+        Self::normalize(phrase).is_some_and(|p| self.phrases.remove(&p.to_lowercase()).is_some())
+    }
+
+    pub fn clear(&mut self) {
+        self.phrases.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.phrases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    /// Display form of a phrase typed in any case ("hà nội" → "Hà Nội")
+    pub fn lookup(&self, phrase: &str) -> Option<&str> {
+        if self.phrases.is_empty() {
+            return None;
+        }
+        self.phrases.get(&phrase.to_lowercase()).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_lookup_remove() {
+        let mut nouns = ProperNouns::new();
+        assert!(nouns.add("Việt  Nam"));
+        assert!(nouns.add("Huế"));
+        assert_eq!(nouns.lookup("việt nam"), Some("Việt Nam"));
+        assert_eq!(nouns.lookup("HUẾ"), Some("Huế"));
+        assert_eq!(nouns.lookup("nam"), None);
+        assert!(nouns.remove("VIỆT NAM"));
+        assert!(!nouns.remove("việt nam"));
+        assert_eq!(nouns.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_empty_and_long_phrases() {
+        let mut nouns = ProperNouns::new();
+        assert!(!nouns.add("  "));
+        assert!(!nouns.add("Thành phố Hồ Chí Minh"));
+        assert!(nouns.is_empty());
+    }
+}
@@ -4,6 +4,9 @@
 //! Shortcuts can be specific to input methods (Telex/VNI) or apply to all.
 
 use super::buffer::MAX;
+use super::template::Templates;
+use super::ParseError;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Maximum replacement length in UTF-32 codepoints (matches Result.chars array size)
@@ -11,8 +14,16 @@ use std::collections::HashMap;
 /// Note: Vietnamese characters with diacritics (ồ, ế, ẫ) count as 1 codepoint each.
 pub const MAX_REPLACEMENT_LEN: usize = MAX - 1; // -1 to leave room for trailing space
 
+/// Maximum length of long expansions (multi-paragraph canned replies).
+/// Output beyond `MAX_REPLACEMENT_LEN` is delivered in chunks (see `FLAG_MORE_OUTPUT`).
+pub const MAX_LONG_REPLACEMENT_LEN: usize = 16 * 1024;
+
+/// Caret position marker in replacements: "($|)" → "()" with caret between the parens
+pub const CURSOR_MARKER: &str = "$|";
+
 /// Input method that shortcut applies to
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(u8)]
 pub enum InputMethod {
     /// Apply to all input methods
     #[default]
@@ -25,6 +36,7 @@ pub enum InputMethod {
 
 /// Trigger condition for shortcut
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
 pub enum TriggerCondition {
     /// Trigger immediately when buffer matches
     Immediate,
@@ -34,11 +46,72 @@ pub enum TriggerCondition {
 
 /// Case handling mode
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
 pub enum CaseMode {
     /// Keep replacement exactly as defined
     Exact,
     /// Match case of trigger: "VN" → "VIỆT NAM", "vn" → "Việt Nam"
     MatchCase,
+    /// Title-case every word: "Vn" → "Việt Nam", "VN" → "VIỆT NAM"
+    TitleCase,
+    /// Sentence case: "Vn" → "Việt nam", "VN" → "VIỆT NAM"
+    SentenceCase,
+    /// Like `MatchCase`, but words with internal capitals ("iPhone", "macOS",
+    /// "UBND") keep their casing: "IP" → "IPHONE" becomes "iPhone"
+    Smart,
+}
+
+impl CaseMode {
+    /// Convert FFI value (0=Exact, 1=MatchCase, 2=TitleCase, 3=SentenceCase, 4=Smart)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(CaseMode::Exact),
+            1 => Some(CaseMode::MatchCase),
+            2 => Some(CaseMode::TitleCase),
+            3 => Some(CaseMode::SentenceCase),
+            4 => Some(CaseMode::Smart),
+            _ => None,
+        }
+    }
+
+    /// Name used in the native macro format
+    fn name(self) -> &'static str {
+        match self {
+            CaseMode::Exact => "exact",
+            CaseMode::MatchCase => "match",
+            CaseMode::TitleCase => "title",
+            CaseMode::SentenceCase => "sentence",
+            CaseMode::Smart => "smart",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        (0..=4)
+            .filter_map(Self::from_u8)
+            .find(|mode| mode.name() == name)
+    }
+}
+
+/// Text a trigger is compared against while Vietnamese input is on
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(u8)]
+pub enum MatchSource {
+    /// Composed output on screen: "đc" fires for Telex "ddc" and VNI "d9c"
+    #[default]
+    Composed,
+    /// Raw keystrokes: "ddc" fires for Telex "ddc" although "đc" is on screen
+    RawKeys,
+}
+
+impl MatchSource {
+    /// Convert FFI value (0=Composed, 1=RawKeys)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MatchSource::Composed),
+            1 => Some(MatchSource::RawKeys),
+            _ => None,
+        }
+    }
 }
 
 /// A single shortcut entry
@@ -56,18 +129,33 @@ pub struct Shortcut {
     pub enabled: bool,
     /// Which input method this shortcut applies to
     pub input_method: InputMethod,
+    /// Named group (None = always active), see `ShortcutTable::set_active_context`
+    pub group: Option<String>,
+    /// Whether the trigger is compared against composed text or raw keys
+    pub match_source: MatchSource,
 }
 
 impl Shortcut {
     /// Validate and truncate replacement if it exceeds MAX_REPLACEMENT_LEN.
     /// Counts UTF-32 codepoints (Vietnamese diacritics = 1 codepoint each).
     fn validate_replacement(replacement: &str) -> String {
-        let char_count = replacement.chars().count();
-        if char_count <= MAX_REPLACEMENT_LEN {
+        Self::validate_replacement_with_limit(replacement, MAX_REPLACEMENT_LEN)
+    }
+
+    /// Normalize line breaks ("\r\n", "\r" → "\n") and truncate to `limit` codepoints.
+    /// Output contains only '\n' for new lines and '\t' for tabs.
+    fn validate_replacement_with_limit(replacement: &str, limit: usize) -> String {
+        let replacement = if replacement.contains('\r') {
+            replacement.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
             replacement.to_string()
+        };
+        let char_count = replacement.chars().count();
+        if char_count <= limit {
+            replacement
         } else {
-            // Truncate to MAX_REPLACEMENT_LEN codepoints
-            replacement.chars().take(MAX_REPLACEMENT_LEN).collect()
+            // Truncate to limit codepoints
+            replacement.chars().take(limit).collect()
         }
     }
 
@@ -82,6 +170,8 @@ impl Shortcut {
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::All,
+            group: None,
+            match_source: MatchSource::Composed,
         }
     }
 
@@ -96,6 +186,8 @@ impl Shortcut {
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::All,
+            group: None,
+            match_source: MatchSource::Composed,
         }
     }
 
@@ -110,6 +202,8 @@ impl Shortcut {
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::Telex,
+            group: None,
+            match_source: MatchSource::Composed,
         }
     }
 
@@ -124,15 +218,58 @@ impl Shortcut {
             case_mode: CaseMode::MatchCase, // Smart case transformation
             enabled: true,
             input_method: InputMethod::Vni,
+            group: None,
+            match_source: MatchSource::Composed,
         }
     }
 
+    /// Create a shortcut with auto-detected trigger condition:
+    /// - Trigger with only non-letter chars (like "->", "=>") → immediate
+    /// - Otherwise → word boundary (traditional abbreviations like "vn" → "Việt Nam")
+    ///
+    /// Used for user-provided shortcuts (FFI, import), so long replacements are kept;
+    /// they only expand in full if the host opted in (`Engine::set_long_output`).
+    pub fn auto(trigger: &str, replacement: &str) -> Self {
+        let shortcut = if trigger.chars().all(|c| !c.is_alphabetic()) {
+            Self::immediate(trigger, "")
+        } else {
+            Self::new(trigger, "")
+        };
+        shortcut.with_long_replacement(replacement)
+    }
+
+    /// Set replacement allowing up to MAX_LONG_REPLACEMENT_LEN codepoints
+    /// (multi-line canned replies). Long output is sent in chunks.
+    pub fn with_long_replacement(mut self, replacement: &str) -> Self {
+        self.replacement =
+            Self::validate_replacement_with_limit(replacement, MAX_LONG_REPLACEMENT_LEN);
+        self
+    }
+
     /// Set the input method for this shortcut
     pub fn for_method(mut self, method: InputMethod) -> Self {
         self.input_method = method;
         self
     }
 
+    /// Set how the replacement follows the trigger's case
+    pub fn with_case_mode(mut self, mode: CaseMode) -> Self {
+        self.case_mode = mode;
+        self
+    }
+
+    /// Match the trigger against raw keystrokes instead of composed text
+    pub fn on_raw_keys(mut self) -> Self {
+        self.match_source = MatchSource::RawKeys;
+        self
+    }
+
+    /// Put this shortcut in a named group (e.g. "code", "email")
+    pub fn in_group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
     /// Check if shortcut applies to given input method
     ///
     /// - If shortcut is for `All`: matches any method
@@ -162,6 +299,169 @@ pub struct ShortcutMatch {
     pub output: String,
     /// Whether to include the trigger key in output
     pub include_trigger_key: bool,
+    /// Chars after the cursor marker: host moves the caret left by this much (0 = no marker)
+    pub cursor_left: usize,
+}
+
+/// Uppercase the first letter: "việt nam" → "Việt nam"
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Word with internal capitals ("iPhone", "macOS", "UBND"): casing is part of the word
+fn has_inner_capital(word: &str) -> bool {
+    word.chars().skip(1).any(|c| c.is_uppercase())
+}
+
+/// Transform each whitespace-separated word, keeping whitespace and words with
+/// internal capitals untouched. The callback also gets whether it is the first word.
+fn map_words(text: &str, mut f: impl FnMut(&str, bool) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut first = true;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let ws = rest.len() - rest.trim_start().len();
+        out.push_str(&rest[..ws]);
+        rest = &rest[ws..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let word = &rest[..end];
+        if !word.is_empty() {
+            if has_inner_capital(word) {
+                out.push_str(word);
+            } else {
+                out.push_str(&f(word, first));
+            }
+            first = false;
+        }
+        rest = &rest[end..];
+    }
+    out
+}
+
+/// Strip cursor markers from output, returning the caret offset from the end
+///
+/// Only the first marker positions the caret; extra markers are removed.
+fn take_cursor_marker(output: String) -> (String, usize) {
+    match output.find(CURSOR_MARKER) {
+        Some(pos) => {
+            let after = output[pos + CURSOR_MARKER.len()..].replace(CURSOR_MARKER, "");
+            let cursor_left = after.chars().count();
+            (output.replace(CURSOR_MARKER, ""), cursor_left)
+        }
+        None => (output, 0),
+    }
+}
+
+/// Named shortcut group settings
+///
+/// A group is active when enabled and, if bound to contexts, the host's active
+/// context (bundle ID, window class…) is one of them.
+#[derive(Debug, Clone)]
+pub struct ShortcutGroup {
+    pub enabled: bool,
+    /// Contexts the group is bound to (empty = every context)
+    pub contexts: Vec<String>,
+}
+
+impl Default for ShortcutGroup {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            contexts: vec![],
+        }
+    }
+}
+
+/// Reversed-trigger trie: walking a text backwards from its end visits
+/// every trigger that is a suffix of the text, shortest first.
+///
+/// Cost per query is bounded by the longest trigger, not the table size.
+#[derive(Debug)]
+struct SuffixTrie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    /// Sorted by char for binary search
+    children: Vec<(char, u32)>,
+    /// Set if the path from the root spells a trigger (reversed)
+    terminal: bool,
+}
+
+impl Default for SuffixTrie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+}
+
+impl SuffixTrie {
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        let children = &self.nodes[node].children;
+        children
+            .binary_search_by_key(&c, |&(ch, _)| ch)
+            .ok()
+            .map(|i| children[i].1 as usize)
+    }
+
+    /// Insert a (lowercase) trigger
+    fn insert(&mut self, trigger: &str) {
+        let mut node = 0;
+        for c in trigger.chars().rev() {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&c, |&(ch, _)| ch)
+            {
+                Ok(i) => self.nodes[node].children[i].1 as usize,
+                Err(i) => {
+                    let next = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.insert(i, (c, next as u32));
+                    next
+                }
+            };
+        }
+        self.nodes[node].terminal = true;
+    }
+
+    /// Unmark a trigger (nodes are kept, they are reused if it is added again)
+    fn remove(&mut self, trigger: &str) {
+        let mut node = 0;
+        for c in trigger.chars().rev() {
+            match self.child(node, c) {
+                Some(next) => node = next,
+                None => return,
+            }
+        }
+        self.nodes[node].terminal = false;
+    }
+
+    /// Char lengths of all trigger suffixes of `text` (case-insensitive), longest first
+    fn suffix_lengths(&self, text: &str) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        let mut node = 0;
+        'walk: for (count, c) in text.chars().rev().enumerate() {
+            // Lowercase may expand to several chars: walk them reversed too
+            let lower: Vec<char> = c.to_lowercase().collect();
+            for &lc in lower.iter().rev() {
+                match self.child(node, lc) {
+                    Some(next) => node = next,
+                    None => break 'walk,
+                }
+            }
+            if self.nodes[node].terminal {
+                lengths.push(count + 1);
+            }
+        }
+        lengths.reverse();
+        lengths
+    }
 }
 
 /// Shortcut table manager
@@ -169,15 +469,27 @@ pub struct ShortcutMatch {
 pub struct ShortcutTable {
     /// Shortcuts indexed by trigger (lowercase)
     shortcuts: HashMap<String, Shortcut>,
-    /// Sorted triggers by length (longest first) for matching
+    /// Reversed-trigger index for suffix matching
+    suffix_trie: SuffixTrie,
+    /// Triggers in sorted order (enumeration, export, prefix queries)
     sorted_triggers: Vec<String>,
+    /// Group settings by name (groups without an entry are active)
+    groups: HashMap<String, ShortcutGroup>,
+    /// Host-supplied context (bundle ID, window class), None = unknown
+    active_context: Option<String>,
+    /// Placeholder evaluation ({date}, {time}, {thu}, {counter})
+    templates: Templates,
 }
 
 impl ShortcutTable {
     pub fn new() -> Self {
         Self {
             shortcuts: HashMap::new(),
-            sorted_triggers: vec![],
+            suffix_trie: SuffixTrie::default(),
+            sorted_triggers: Vec::new(),
+            groups: HashMap::new(),
+            active_context: None,
+            templates: Templates::new(),
         }
     }
 
@@ -229,20 +541,180 @@ impl ShortcutTable {
 
     /// Add a shortcut
     pub fn add(&mut self, shortcut: Shortcut) {
-        let trigger = shortcut.trigger.clone();
-        self.shortcuts.insert(trigger.clone(), shortcut);
-        self.rebuild_sorted_triggers();
+        self.suffix_trie.insert(&shortcut.trigger);
+        if let Err(pos) = self.sorted_triggers.binary_search(&shortcut.trigger) {
+            self.sorted_triggers.insert(pos, shortcut.trigger.clone());
+        }
+        self.shortcuts.insert(shortcut.trigger.clone(), shortcut);
     }
 
     /// Remove a shortcut (exact match, case-sensitive)
     pub fn remove(&mut self, trigger: &str) -> Option<Shortcut> {
         let result = self.shortcuts.remove(trigger);
         if result.is_some() {
-            self.rebuild_sorted_triggers();
+            self.suffix_trie.remove(trigger);
+            if let Ok(pos) = self
+                .sorted_triggers
+                .binary_search_by(|t| t.as_str().cmp(trigger))
+            {
+                self.sorted_triggers.remove(pos);
+            }
         }
         result
     }
 
+    /// Enable or disable a whole group
+    pub fn set_group_enabled(&mut self, group: &str, enabled: bool) {
+        self.groups.entry(group.to_string()).or_default().enabled = enabled;
+    }
+
+    /// Bind a group to a context: it is then only active in its bound contexts
+    pub fn bind_group_context(&mut self, group: &str, context: &str) {
+        let contexts = &mut self.groups.entry(group.to_string()).or_default().contexts;
+        if !contexts.iter().any(|c| c == context) {
+            contexts.push(context.to_string());
+        }
+    }
+
+    /// Remove all context bindings of a group (active everywhere again)
+    pub fn clear_group_contexts(&mut self, group: &str) {
+        if let Some(settings) = self.groups.get_mut(group) {
+            settings.contexts.clear();
+        }
+    }
+
+    /// Group settings (None if the group was never configured)
+    pub fn group(&self, group: &str) -> Option<&ShortcutGroup> {
+        self.groups.get(group)
+    }
+
+    /// Names of all groups used by shortcuts or configured, sorted
+    pub fn group_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .shortcuts
+            .values()
+            .filter_map(|s| s.group.as_deref())
+            .chain(self.groups.keys().map(String::as_str))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Set the host's active context (bundle ID, window class); empty = none
+    pub fn set_active_context(&mut self, context: &str) {
+        self.active_context = (!context.is_empty()).then(|| context.to_string());
+    }
+
+    /// Current active context
+    pub fn active_context(&self) -> Option<&str> {
+        self.active_context.as_deref()
+    }
+
+    /// Check if a group is active in the current context
+    pub fn is_group_active(&self, group: &str) -> bool {
+        match self.groups.get(group) {
+            None => true,
+            Some(settings) => {
+                settings.enabled
+                    && (settings.contexts.is_empty()
+                        || self
+                            .active_context
+                            .as_ref()
+                            .is_some_and(|ctx| settings.contexts.contains(ctx)))
+            }
+        }
+    }
+
+    fn is_shortcut_active(&self, shortcut: &Shortcut) -> bool {
+        shortcut
+            .group
+            .as_deref()
+            .is_none_or(|group| self.is_group_active(group))
+    }
+
+    /// Template state (clock, timezone, counters)
+    pub fn templates(&self) -> &Templates {
+        &self.templates
+    }
+
+    /// Mutable template state (e.g. to inject a clock)
+    pub fn templates_mut(&mut self) -> &mut Templates {
+        &mut self.templates
+    }
+
+    /// Get shortcut by trigger (case-insensitive), including disabled ones
+    pub fn get(&self, trigger: &str) -> Option<&Shortcut> {
+        self.shortcuts.get(&trigger.to_lowercase())
+    }
+
+    /// Enable or disable a shortcut without removing it
+    ///
+    /// Returns false if no shortcut has this trigger
+    pub fn set_enabled(&mut self, trigger: &str, enabled: bool) -> bool {
+        match self.shortcuts.get_mut(&trigger.to_lowercase()) {
+            Some(shortcut) => {
+                shortcut.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set what a shortcut's trigger is compared against (composed text or raw keys)
+    ///
+    /// Returns false if no shortcut has this trigger.
+    pub fn set_match_source(&mut self, trigger: &str, source: MatchSource) -> bool {
+        match self.shortcuts.get_mut(&trigger.to_lowercase()) {
+            Some(shortcut) => {
+                shortcut.match_source = source;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if any active shortcut trigger starts with `prefix` (case-insensitive)
+    pub fn has_trigger_prefix(&self, prefix: &str) -> bool {
+        self.has_trigger_prefix_for_method(prefix, InputMethod::All)
+    }
+
+    /// Check if any shortcut that could fire for `method` in the current context
+    /// has a trigger starting with `prefix` (case-insensitive)
+    ///
+    /// Binary search in the sorted triggers: cost does not grow with the table
+    /// when no trigger has this prefix.
+    pub fn has_trigger_prefix_for_method(&self, prefix: &str, method: InputMethod) -> bool {
+        let prefix = if prefix.chars().any(char::is_uppercase) {
+            Cow::Owned(prefix.to_lowercase())
+        } else {
+            Cow::Borrowed(prefix)
+        };
+        let start = self
+            .sorted_triggers
+            .partition_point(|trigger| trigger.as_str() < prefix.as_ref());
+        self.sorted_triggers[start..]
+            .iter()
+            .take_while(|trigger| trigger.starts_with(prefix.as_ref()))
+            .map(|trigger| &self.shortcuts[trigger])
+            .any(|s| s.enabled && s.applies_to(method) && self.is_shortcut_active(s))
+    }
+
+    /// All shortcuts sorted by trigger (stable order for enumeration and export)
+    pub fn sorted(&self) -> Vec<&Shortcut> {
+        self.sorted_triggers
+            .iter()
+            .map(|trigger| &self.shortcuts[trigger])
+            .collect()
+    }
+
+    /// Shortcut at `index` in trigger order (same order as `sorted`)
+    pub fn nth(&self, index: usize) -> Option<&Shortcut> {
+        self.sorted_triggers
+            .get(index)
+            .map(|trigger| &self.shortcuts[trigger])
+    }
+
     /// Check if buffer matches any shortcut (for any input method)
     ///
     /// Returns (trigger, shortcut) if match found
@@ -259,18 +731,13 @@ impl ShortcutTable {
         buffer: &str,
         method: InputMethod,
     ) -> Option<(&str, &Shortcut)> {
-        let buffer_lower = buffer.to_lowercase();
-        // Longest-match-first, case-insensitive match
-        for trigger in &self.sorted_triggers {
-            if buffer_lower == *trigger {
-                if let Some(shortcut) = self.shortcuts.get(trigger) {
-                    if shortcut.enabled && shortcut.applies_to(method) {
-                        return Some((trigger, shortcut));
-                    }
-                }
-            }
+        // Case-insensitive exact match (triggers are stored lowercase)
+        let (trigger, shortcut) = self.shortcuts.get_key_value(&buffer.to_lowercase())?;
+        if shortcut.enabled && shortcut.applies_to(method) && self.is_shortcut_active(shortcut) {
+            Some((trigger, shortcut))
+        } else {
+            None
         }
-        None
     }
 
     /// Try to match buffer with trigger key (for any input method)
@@ -307,75 +774,146 @@ impl ShortcutTable {
         key_char: Option<char>,
         is_word_boundary: bool,
         method: InputMethod,
+    ) -> Option<ShortcutMatch> {
+        self.try_match_filtered(buffer, key_char, is_word_boundary, method, None)
+    }
+
+    /// Try to match only shortcuts declared for `source`
+    ///
+    /// Used when composed text and raw keys differ (e.g. "đc" vs "ddc"):
+    /// the engine checks composed text against `Composed` shortcuts and
+    /// the raw key string against `RawKeys` shortcuts.
+    pub fn try_match_source_for_method(
+        &self,
+        text: &str,
+        source: MatchSource,
+        key_char: Option<char>,
+        is_word_boundary: bool,
+        method: InputMethod,
+    ) -> Option<ShortcutMatch> {
+        self.try_match_filtered(text, key_char, is_word_boundary, method, Some(source))
+    }
+
+    fn try_match_filtered(
+        &self,
+        buffer: &str,
+        key_char: Option<char>,
+        is_word_boundary: bool,
+        method: InputMethod,
+        source: Option<MatchSource>,
     ) -> Option<ShortcutMatch> {
         let (trigger, shortcut) = self.lookup_for_method(buffer, method)?;
+        if source.is_some_and(|source| shortcut.match_source != source) {
+            return None;
+        }
+        // Word shortcuts wait for a boundary (checked first so counters don't advance)
+        if shortcut.condition == TriggerCondition::OnWordBoundary && !is_word_boundary {
+            return None;
+        }
+        // Evaluate placeholders before case handling ("{thu}" with "VN" → "THỨ HAI")
+        let replacement = self.templates.expand(&shortcut.replacement);
 
         match shortcut.condition {
             TriggerCondition::Immediate => {
-                let output = self.apply_case(buffer, &shortcut.replacement, shortcut.case_mode);
+                let output = self.apply_case(buffer, &replacement, shortcut.case_mode);
+                let (output, cursor_left) = take_cursor_marker(output);
                 Some(ShortcutMatch {
                     // Use char count, not byte length (UTF-8 chars like đ are multi-byte)
                     backspace_count: trigger.chars().count(),
                     output,
                     include_trigger_key: false,
+                    cursor_left,
                 })
             }
             TriggerCondition::OnWordBoundary => {
-                if is_word_boundary {
-                    let mut output =
-                        self.apply_case(buffer, &shortcut.replacement, shortcut.case_mode);
-                    // Append the trigger key (space, etc.)
-                    if let Some(ch) = key_char {
-                        output.push(ch);
-                    }
-                    Some(ShortcutMatch {
-                        // Use char count, not byte length (UTF-8 chars like đ are multi-byte)
-                        backspace_count: trigger.chars().count(),
-                        output,
-                        include_trigger_key: true,
-                    })
-                } else {
-                    None
+                let mut output = self.apply_case(buffer, &replacement, shortcut.case_mode);
+                // Append the trigger key (space, etc.)
+                if let Some(ch) = key_char {
+                    output.push(ch);
                 }
+                let (output, cursor_left) = take_cursor_marker(output);
+                Some(ShortcutMatch {
+                    // Use char count, not byte length (UTF-8 chars like đ are multi-byte)
+                    backspace_count: trigger.chars().count(),
+                    output,
+                    include_trigger_key: true,
+                    cursor_left,
+                })
             }
         }
     }
 
+    /// Try to match the longest suffix of `text` for specific input method
+    ///
+    /// Used when other chars precede the trigger (e.g. "≈ç√√" matches "√√").
+    /// Uses the reversed-trigger trie, so cost depends on trigger length,
+    /// not on the number of shortcuts.
+    pub fn try_match_suffix_for_method(
+        &self,
+        text: &str,
+        key_char: Option<char>,
+        is_word_boundary: bool,
+        method: InputMethod,
+    ) -> Option<ShortcutMatch> {
+        let total = text.chars().count();
+        for len in self.suffix_trie.suffix_lengths(text) {
+            let start = text
+                .char_indices()
+                .nth(total - len)
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            let m = self.try_match_for_method(&text[start..], key_char, is_word_boundary, method);
+            if m.is_some() {
+                return m;
+            }
+        }
+        None
+    }
+
     /// Apply case transformation based on mode
     fn apply_case(&self, trigger: &str, replacement: &str, mode: CaseMode) -> String {
+        if mode == CaseMode::Exact {
+            return replacement.to_string();
+        }
+        if trigger.chars().all(|c| c.is_uppercase()) {
+            // All uppercase → replacement all uppercase
+            return match mode {
+                CaseMode::MatchCase => replacement.to_uppercase(),
+                _ => map_words(replacement, |word, _| word.to_uppercase()),
+            };
+        }
+        if !trigger
+            .chars()
+            .next()
+            .map(|c| c.is_uppercase())
+            .unwrap_or(false)
+        {
+            // Lowercase → keep replacement as-is
+            return replacement.to_string();
+        }
+
+        // First char uppercase
         match mode {
-            CaseMode::Exact => replacement.to_string(),
-            CaseMode::MatchCase => {
-                if trigger.chars().all(|c| c.is_uppercase()) {
-                    // All uppercase → replacement all uppercase
-                    replacement.to_uppercase()
-                } else if trigger
-                    .chars()
-                    .next()
-                    .map(|c| c.is_uppercase())
-                    .unwrap_or(false)
-                {
-                    // First char uppercase → capitalize replacement
-                    let mut chars = replacement.chars();
-                    match chars.next() {
-                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                        None => String::new(),
-                    }
+            // Capitalize replacement
+            CaseMode::MatchCase => capitalize(replacement),
+            CaseMode::TitleCase => map_words(replacement, |word, _| capitalize(word)),
+            CaseMode::SentenceCase => map_words(replacement, |word, first| {
+                if first {
+                    capitalize(&word.to_lowercase())
                 } else {
-                    // Lowercase → keep replacement as-is
-                    replacement.to_string()
+                    word.to_lowercase()
                 }
-            }
+            }),
+            _ => map_words(replacement, |word, first| {
+                if first {
+                    capitalize(word)
+                } else {
+                    word.to_string()
+                }
+            }),
         }
     }
 
-    /// Rebuild sorted triggers list (longest first)
-    fn rebuild_sorted_triggers(&mut self) {
-        self.sorted_triggers = self.shortcuts.keys().cloned().collect();
-        self.sorted_triggers
-            .sort_by_key(|s| std::cmp::Reverse(s.len()));
-    }
-
     /// Check if shortcut table is empty
     pub fn is_empty(&self) -> bool {
         self.shortcuts.is_empty()
@@ -386,11 +924,292 @@ impl ShortcutTable {
         self.shortcuts.len()
     }
 
-    /// Clear all shortcuts
+    /// Clear all shortcuts (group settings are kept, see `clear_groups`)
     pub fn clear(&mut self) {
         self.shortcuts.clear();
+        self.suffix_trie = SuffixTrie::default();
         self.sorted_triggers.clear();
     }
+
+    /// Reset all group settings (every group enabled and unbound again)
+    pub fn clear_groups(&mut self) {
+        self.groups.clear();
+    }
+}
+
+// ============================================================
+// Macro file formats (import/export)
+// ============================================================
+
+/// Shortcut (macro) file format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacroFormat {
+    /// Native format, preserves condition, case mode, input method, enabled,
+    /// group and match source:
+    /// `trigger<TAB>replacement<TAB>word|immediate<TAB>match|exact|title|sentence|smart<TAB>all|telex|vni<TAB>1|0[<TAB>group[<TAB>composed|raw]]`
+    /// Trigger, replacement and group escapes: `\n`, `\t`, `\r`, `\\`
+    Gonhanh,
+    /// Unikey macro file: header line + `trigger:replacement` lines
+    Unikey,
+    /// OpenKey macro export: Unikey layout with OpenKey's own header line.
+    /// OpenKey skips the first line on import, so the header is always written.
+    OpenKey,
+    /// EVKey macro file: EVKey imports and exports the Unikey file as is
+    EvKey,
+}
+
+const GONHANH_HEADER: &str = "# gonhanh shortcuts v2";
+const UNIKEY_HEADER: &str = ";DO NOT DELETE THIS LINE*** version=1 ***";
+const OPENKEY_HEADER: &str = ";Compatible OpenKey Macro Data file for UniKey*** version=1 ***";
+
+impl MacroFormat {
+    /// Convert FFI value (0=Gonhanh, 1=Unikey, 2=OpenKey, 3=EVKey)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MacroFormat::Gonhanh),
+            1 => Some(MacroFormat::Unikey),
+            2 => Some(MacroFormat::OpenKey),
+            3 => Some(MacroFormat::EvKey),
+            _ => None,
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            MacroFormat::Gonhanh => GONHANH_HEADER,
+            MacroFormat::Unikey | MacroFormat::EvKey => UNIKEY_HEADER,
+            MacroFormat::OpenKey => OPENKEY_HEADER,
+        }
+    }
+}
+
+/// Escape a field for the native format (tabs/newlines would break the line layout)
+fn escape_field(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape_field(s: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => return Err(format!("unknown escape '\\{}'", other)),
+            None => return Err("trailing '\\'".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+/// Parse one native-format line
+fn parse_gonhanh_line(line: &str) -> Result<Shortcut, String> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 2 {
+        return Err("expected trigger<TAB>replacement".to_string());
+    }
+    let trigger = unescape_field(fields[0])?;
+    if trigger.is_empty() {
+        return Err("empty trigger".to_string());
+    }
+    let replacement = unescape_field(fields[1])?;
+    let mut shortcut = Shortcut::auto(&trigger, &replacement);
+
+    if let Some(&condition) = fields.get(2) {
+        shortcut.condition = match condition {
+            "word" => TriggerCondition::OnWordBoundary,
+            "immediate" => TriggerCondition::Immediate,
+            other => return Err(format!("unknown condition '{}'", other)),
+        };
+    }
+    if let Some(&case) = fields.get(3) {
+        shortcut.case_mode = match CaseMode::from_name(case) {
+            Some(mode) => mode,
+            None => return Err(format!("unknown case mode '{}'", case)),
+        };
+    }
+    if let Some(&method) = fields.get(4) {
+        shortcut.input_method = match method {
+            "all" => InputMethod::All,
+            "telex" => InputMethod::Telex,
+            "vni" => InputMethod::Vni,
+            other => return Err(format!("unknown input method '{}'", other)),
+        };
+    }
+    if let Some(&enabled) = fields.get(5) {
+        shortcut.enabled = match enabled {
+            "1" => true,
+            "0" => false,
+            other => return Err(format!("invalid enabled flag '{}'", other)),
+        };
+    }
+    if let Some(&group) = fields.get(6) {
+        if !group.is_empty() {
+            shortcut.group = Some(unescape_field(group)?);
+        }
+    }
+    if let Some(&source) = fields.get(7) {
+        shortcut.match_source = match source {
+            "composed" => MatchSource::Composed,
+            "raw" => MatchSource::RawKeys,
+            other => return Err(format!("unknown match source '{}'", other)),
+        };
+    }
+    if fields.len() > 8 {
+        return Err("too many fields".to_string());
+    }
+    Ok(shortcut)
+}
+
+/// Parse one Unikey `trigger:replacement` line (line ending already stripped)
+///
+/// Spaces around the replacement are kept: they may be intentional.
+fn parse_macro_line(line: &str) -> Result<Shortcut, String> {
+    let (trigger, replacement) = line
+        .split_once(':')
+        .ok_or_else(|| "expected trigger:replacement".to_string())?;
+    let trigger = trigger.trim();
+    if trigger.is_empty() {
+        return Err("empty trigger".to_string());
+    }
+    Ok(Shortcut::auto(trigger, replacement))
+}
+
+/// Parse a macro file into shortcuts
+///
+/// Invalid lines are skipped and reported with their 1-based line number.
+/// Comment lines (`#` in native format, `;` in Unikey-style formats, which covers
+/// their header line) and blank lines are ignored.
+pub fn parse_macros(text: &str, format: MacroFormat) -> (Vec<Shortcut>, Vec<ParseError>) {
+    let mut shortcuts = Vec::new();
+    let mut errors = Vec::new();
+    // Strip UTF-8 BOM (common in files saved by Windows IMEs)
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let parsed = match format {
+            MacroFormat::Gonhanh => {
+                if line.trim().is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                parse_gonhanh_line(line)
+            }
+            MacroFormat::Unikey | MacroFormat::OpenKey | MacroFormat::EvKey => {
+                if line.trim().is_empty() || line.starts_with(';') {
+                    continue;
+                }
+                parse_macro_line(line)
+            }
+        };
+        match parsed {
+            Ok(shortcut) => shortcuts.push(shortcut),
+            Err(message) => errors.push(ParseError {
+                line: i + 1,
+                message,
+            }),
+        }
+    }
+
+    (shortcuts, errors)
+}
+
+/// Write shortcuts in a macro file format
+///
+/// Unikey-style formats cannot represent newlines or tabs in replacements,
+/// nor condition/case/method/enabled; such entries are skipped with an error
+/// (line number = 1-based position in `shortcuts`).
+pub fn write_macros<'a>(
+    shortcuts: impl IntoIterator<Item = &'a Shortcut>,
+    format: MacroFormat,
+) -> (String, Vec<ParseError>) {
+    let mut out = String::new();
+    let mut errors = Vec::new();
+    out.push_str(format.header());
+    out.push('\n');
+
+    for (i, shortcut) in shortcuts.into_iter().enumerate() {
+        match format {
+            MacroFormat::Gonhanh => {
+                let condition = match shortcut.condition {
+                    TriggerCondition::OnWordBoundary => "word",
+                    TriggerCondition::Immediate => "immediate",
+                };
+                let case = shortcut.case_mode.name();
+                let method = match shortcut.input_method {
+                    InputMethod::All => "all",
+                    InputMethod::Telex => "telex",
+                    InputMethod::Vni => "vni",
+                };
+                out.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    escape_field(&shortcut.trigger),
+                    escape_field(&shortcut.replacement),
+                    condition,
+                    case,
+                    method,
+                    if shortcut.enabled { "1" } else { "0" }
+                ));
+                if shortcut.group.is_some() || shortcut.match_source == MatchSource::RawKeys {
+                    out.push('\t');
+                    out.push_str(&escape_field(shortcut.group.as_deref().unwrap_or("")));
+                }
+                if shortcut.match_source == MatchSource::RawKeys {
+                    out.push_str("\traw");
+                }
+                out.push('\n');
+            }
+            MacroFormat::Unikey | MacroFormat::OpenKey | MacroFormat::EvKey => {
+                if shortcut.replacement.contains(['\n', '\r', '\t'])
+                    || shortcut.trigger.contains(':')
+                {
+                    errors.push(ParseError {
+                        line: i + 1,
+                        message: format!("'{}' cannot be written in this format", shortcut.trigger),
+                    });
+                    continue;
+                }
+                out.push_str(&format!("{}:{}\n", shortcut.trigger, shortcut.replacement));
+            }
+        }
+    }
+
+    (out, errors)
+}
+
+impl ShortcutTable {
+    /// Import shortcuts from a macro file, adding/overwriting by trigger
+    ///
+    /// Returns number of imported shortcuts and per-line errors.
+    pub fn import(&mut self, text: &str, format: MacroFormat) -> (usize, Vec<ParseError>) {
+        let (shortcuts, errors) = parse_macros(text, format);
+        let count = shortcuts.len();
+        for shortcut in shortcuts {
+            self.add(shortcut);
+        }
+        (count, errors)
+    }
+
+    /// Export all shortcuts to a macro file (sorted by trigger)
+    pub fn export(&self, format: MacroFormat) -> (String, Vec<ParseError>) {
+        write_macros(self.sorted(), format)
+    }
 }
 
 #[cfg(test)]
@@ -514,6 +1333,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extended_case_modes() {
+        let expand = |mode: CaseMode, replacement: &str, typed: &str| {
+            let mut table = ShortcutTable::new();
+            table.add(Shortcut::new("xy", replacement).with_case_mode(mode));
+            table
+                .try_match_for_method(typed, None, true, InputMethod::All)
+                .unwrap()
+                .output
+        };
+
+        // Title case: every word
+        assert_eq!(
+            expand(CaseMode::TitleCase, "thành phố hồ chí minh", "Xy"),
+            "Thành Phố Hồ Chí Minh"
+        );
+        assert_eq!(expand(CaseMode::TitleCase, "việt nam", "XY"), "VIỆT NAM");
+        assert_eq!(expand(CaseMode::TitleCase, "việt nam", "xy"), "việt nam");
+
+        // Sentence case: first word only, the rest lowercased
+        assert_eq!(expand(CaseMode::SentenceCase, "Việt Nam", "Xy"), "Việt nam");
+        assert_eq!(expand(CaseMode::SentenceCase, "Việt Nam", "xy"), "Việt Nam");
+
+        // Internal capitals are preserved by the new modes
+        assert_eq!(
+            expand(CaseMode::Smart, "iPhone của tôi", "XY"),
+            "iPhone CỦA TÔI"
+        );
+        assert_eq!(expand(CaseMode::Smart, "mua iPhone", "Xy"), "Mua iPhone");
+        assert_eq!(
+            expand(CaseMode::TitleCase, "gửi UBND quận", "Xy"),
+            "Gửi UBND Quận"
+        );
+        assert_eq!(
+            expand(CaseMode::SentenceCase, "Họp UBND Quận", "Xy"),
+            "Họp UBND quận"
+        );
+        // Whitespace (incl. new lines) is kept
+        assert_eq!(expand(CaseMode::TitleCase, " a\n\tb ", "Xy"), " A\n\tB ");
+
+        // MatchCase is unchanged
+        assert_eq!(expand(CaseMode::MatchCase, "iPhone", "XY"), "IPHONE");
+    }
+
+    #[test]
+    fn test_case_mode_from_u8_and_format() {
+        assert_eq!(CaseMode::from_u8(2), Some(CaseMode::TitleCase));
+        assert_eq!(CaseMode::from_u8(5), None);
+
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("ip", "iPhone").with_case_mode(CaseMode::Smart));
+        let (text, _) = table.export(MacroFormat::Gonhanh);
+        assert!(text.ends_with("ip\tiPhone\tword\tsmart\tall\t1\n"));
+
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::Gonhanh), (1, vec![]));
+        assert_eq!(copy.get("ip").unwrap().case_mode, CaseMode::Smart);
+    }
+
     #[test]
     fn test_immediate_shortcut() {
         let table = table_with_immediate("w", "ư");
@@ -894,4 +1772,413 @@ mod tests {
             InputMethod::All,
         );
     }
+
+    #[test]
+    fn test_import_unikey_macros() {
+        let mut table = ShortcutTable::new();
+        let text =
+            "\u{feff};DO NOT DELETE THIS LINE*** version=1 ***\r\nvn:Việt Nam\r\n->:→\r\n:oops\r\n";
+        let (count, errors) = table.import(text, MacroFormat::Unikey);
+        assert_eq!(count, 2);
+        assert_eq!(
+            errors,
+            vec![ParseError {
+                line: 4,
+                message: "empty trigger".to_string()
+            }]
+        );
+        assert_eq!(table.lookup("vn").unwrap().1.replacement, "Việt Nam");
+        // Symbol-only trigger is immediate, like ime_add_shortcut
+        assert_eq!(
+            table.lookup("->").unwrap().1.condition,
+            TriggerCondition::Immediate
+        );
+    }
+
+    #[test]
+    fn test_export_unikey_skips_multiline() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("sig", "Thanks,\nAn"));
+        table.add(Shortcut::new("hn", "Hà Nội"));
+        let (text, errors) = table.export(MacroFormat::Unikey);
+        assert_eq!(text, format!("{}\nhn:Hà Nội\n", UNIKEY_HEADER));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+    }
+
+    #[test]
+    fn test_gonhanh_format_roundtrip() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::telex("ko", "không"));
+        table.add(Shortcut {
+            case_mode: CaseMode::Exact,
+            enabled: false,
+            ..Shortcut::immediate("sig", "Thanks,\n\tAn \\o/")
+        });
+
+        let (text, errors) = table.export(MacroFormat::Gonhanh);
+        assert!(errors.is_empty());
+        assert_eq!(
+            text,
+            "# gonhanh shortcuts v2\n\
+             ko\tkhông\timmediate\tmatch\ttelex\t1\n\
+             sig\tThanks,\\n\\tAn \\\\o/\timmediate\texact\tall\t0\n"
+        );
+
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::Gonhanh), (2, vec![]));
+        let sig = &copy.shortcuts["sig"];
+        assert_eq!(sig.replacement, "Thanks,\n\tAn \\o/");
+        assert_eq!(sig.condition, TriggerCondition::Immediate);
+        assert_eq!(sig.case_mode, CaseMode::Exact);
+        assert!(!sig.enabled);
+        assert_eq!(
+            copy.lookup("ko").unwrap().1.input_method,
+            InputMethod::Telex
+        );
+        assert_eq!(copy.export(MacroFormat::Gonhanh).0, text);
+    }
+
+    #[test]
+    fn test_unikey_keeps_replacement_spaces() {
+        let (shortcuts, errors) = parse_macros(" sig : An \r\n", MacroFormat::Unikey);
+        assert!(errors.is_empty());
+        assert_eq!(shortcuts[0].trigger, "sig");
+        assert_eq!(shortcuts[0].replacement, " An ");
+    }
+
+    #[test]
+    fn test_gonhanh_format_escapes_trigger_and_group() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut {
+            group: Some("work\tmail".to_string()),
+            ..Shortcut::immediate("a\\b", "x")
+        });
+        let (text, _) = table.export(MacroFormat::Gonhanh);
+        assert_eq!(
+            text,
+            "# gonhanh shortcuts v2\na\\\\b\tx\timmediate\tmatch\tall\t1\twork\\tmail\n"
+        );
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::Gonhanh), (1, vec![]));
+        assert_eq!(copy.shortcuts["a\\b"].group.as_deref(), Some("work\tmail"));
+    }
+
+    #[test]
+    fn test_openkey_macros() {
+        // Layout written by OpenKey's macro export: header, then trigger:text lines
+        let text = ";Compatible OpenKey Macro Data file for UniKey*** version=1 ***\n\
+                    hcm:Hồ Chí Minh\n\
+                    kb:không biết\n";
+        let mut table = ShortcutTable::new();
+        assert_eq!(table.import(text, MacroFormat::OpenKey), (2, vec![]));
+        assert_eq!(table.lookup("kb").unwrap().1.replacement, "không biết");
+        // Export keeps the header: OpenKey drops the first line on import
+        assert_eq!(
+            table.export(MacroFormat::OpenKey),
+            (text.to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_evkey_macros_use_unikey_file() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        let (text, _) = table.export(MacroFormat::EvKey);
+        assert_eq!(text, format!("{}\nvn:Việt Nam\n", UNIKEY_HEADER));
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::EvKey), (1, vec![]));
+    }
+
+    #[test]
+    fn test_gonhanh_format_errors() {
+        let text = "# comment\nvn\tViệt Nam\nko\nx\ty\tsoon\nz\tbad\\q\n";
+        let (shortcuts, errors) = parse_macros(text, MacroFormat::Gonhanh);
+        assert_eq!(shortcuts.len(), 1);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_get_and_set_enabled() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        table.add(Shortcut::new("hn", "Hà Nội"));
+
+        assert!(table.set_enabled("VN", false));
+        assert!(!table.set_enabled("hcm", false));
+        assert!(table.lookup("vn").is_none());
+        assert!(!table.get("Vn").unwrap().enabled);
+
+        let triggers: Vec<&str> = table.sorted().iter().map(|s| s.trigger.as_str()).collect();
+        assert_eq!(triggers, vec!["hn", "vn"]);
+    }
+
+    #[test]
+    fn test_has_trigger_prefix() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        table.add(Shortcut::immediate("->", "→").for_method(InputMethod::Vni));
+        table.add(Shortcut::new("fnc", "function").in_group("code"));
+
+        assert!(table.has_trigger_prefix("V"));
+        assert!(table.has_trigger_prefix("vn"));
+        assert!(!table.has_trigger_prefix("vnx"));
+        assert!(!table.has_trigger_prefix("w"));
+
+        // Method scope
+        assert!(table.has_trigger_prefix_for_method("-", InputMethod::Vni));
+        assert!(!table.has_trigger_prefix_for_method("-", InputMethod::Telex));
+
+        // Group context
+        assert!(table.has_trigger_prefix("fn"));
+        table.bind_group_context("code", "com.microsoft.VSCode");
+        assert!(!table.has_trigger_prefix("fn"));
+        table.set_active_context("com.microsoft.VSCode");
+        assert!(table.has_trigger_prefix("fn"));
+
+        table.set_enabled("vn", false);
+        assert!(!table.has_trigger_prefix("v"));
+    }
+
+    #[test]
+    fn test_sorted_order_follows_mutations() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        table.add(Shortcut::new("hn", "Hà Nội"));
+        table.add(Shortcut::new("ko", "không"));
+        table.add(Shortcut::new("hn", "Hà Nội!"));
+        assert_eq!(table.nth(0).unwrap().replacement, "Hà Nội!");
+        assert_eq!(table.nth(1).unwrap().trigger, "ko");
+
+        table.remove("ko");
+        let triggers: Vec<&str> = table.sorted().iter().map(|s| s.trigger.as_str()).collect();
+        assert_eq!(triggers, vec!["hn", "vn"]);
+        assert!(table.nth(2).is_none());
+
+        table.clear();
+        assert!(table.nth(0).is_none());
+    }
+
+    #[test]
+    fn test_template_replacement() {
+        fn fixed_clock() -> i64 {
+            1_709_543_107 // 2024-03-04 16:05 UTC+7, Monday
+        }
+        let mut table = ShortcutTable::new();
+        table.templates_mut().set_clock(fixed_clock);
+        table.add(Shortcut::new("nay", "{thu}, {date}"));
+        table.add(Shortcut::new("so", "Số {counter:so}"));
+
+        assert_shortcut_match(
+            &table,
+            "nay",
+            Some(' '),
+            true,
+            "Thứ Hai, 04/03/2024 ",
+            3,
+            InputMethod::All,
+        );
+        assert_shortcut_match(
+            &table,
+            "NAY",
+            Some(' '),
+            true,
+            "THỨ HAI, 04/03/2024 ",
+            3,
+            InputMethod::All,
+        );
+        // No boundary yet: counter must not advance
+        assert_no_match(&table, "so", Some('a'), false, InputMethod::All);
+        assert_shortcut_match(&table, "so", Some(' '), true, "Số 1 ", 2, InputMethod::All);
+        assert_shortcut_match(&table, "so", Some(' '), true, "Số 2 ", 2, InputMethod::All);
+    }
+
+    #[test]
+    fn test_cursor_marker() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("div", "<div>$|</div>"));
+        table.add(Shortcut::immediate("()", "($|)"));
+
+        let m = table.try_match("div", Some(' '), true).unwrap();
+        assert_eq!(m.output, "<div></div> ");
+        assert_eq!(m.cursor_left, 7);
+
+        let m = table.try_match("()", None, false).unwrap();
+        assert_eq!(m.output, "()");
+        assert_eq!(m.cursor_left, 1);
+
+        // Only the first marker counts, the rest are dropped
+        assert_eq!(
+            take_cursor_marker("a$|b$|c".to_string()),
+            ("abc".to_string(), 2)
+        );
+        assert_eq!(
+            take_cursor_marker("abc".to_string()),
+            ("abc".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn test_long_replacement() {
+        let long_text = "Dạ, em chào anh/chị.\r\n".repeat(30);
+        assert!(long_text.chars().count() > MAX_REPLACEMENT_LEN);
+
+        let shortcut = Shortcut::new("rep", "").with_long_replacement(&long_text);
+        assert_eq!(shortcut.replacement, "Dạ, em chào anh/chị.\n".repeat(30));
+
+        // User-provided shortcuts keep the full text
+        let shortcut = Shortcut::auto("rep", &long_text);
+        assert_eq!(shortcut.replacement.lines().count(), 30);
+
+        let huge = "a".repeat(MAX_LONG_REPLACEMENT_LEN + 1);
+        let shortcut = Shortcut::auto("big", &huge);
+        assert_eq!(shortcut.replacement.len(), MAX_LONG_REPLACEMENT_LEN);
+    }
+
+    #[test]
+    fn test_suffix_match_longest_first() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::immediate("√√", "✅"));
+        table.add(Shortcut::immediate("√", "check"));
+        table.add(Shortcut::new("zz", "word only"));
+
+        let m = table
+            .try_match_suffix_for_method("≈ç√√", None, false, InputMethod::All)
+            .unwrap();
+        assert_eq!(m.output, "✅");
+        assert_eq!(m.backspace_count, 2);
+
+        // Longest suffix disabled → next longest wins
+        table.set_enabled("√√", false);
+        let m = table
+            .try_match_suffix_for_method("≈ç√√", None, false, InputMethod::All)
+            .unwrap();
+        assert_eq!(m.output, "check");
+
+        // Word-boundary shortcut does not fire as an immediate suffix
+        assert!(table
+            .try_match_suffix_for_method("azz", None, false, InputMethod::All)
+            .is_none());
+    }
+
+    #[test]
+    fn test_suffix_index_tracks_add_remove() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::immediate("->", "→"));
+        assert!(table
+            .try_match_suffix_for_method("a->", None, false, InputMethod::All)
+            .is_some());
+        table.remove("->");
+        assert!(table
+            .try_match_suffix_for_method("a->", None, false, InputMethod::All)
+            .is_none());
+        table.add(Shortcut::immediate("->", "⟶"));
+        let m = table
+            .try_match_suffix_for_method("A->", None, false, InputMethod::All)
+            .unwrap();
+        assert_eq!(m.output, "⟶");
+        table.clear();
+        assert!(table
+            .try_match_suffix_for_method("->", None, false, InputMethod::All)
+            .is_none());
+    }
+
+    #[test]
+    fn test_groups_and_context() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("vn", "Việt Nam"));
+        table.add(Shortcut::new("fnc", "function $|() {}").in_group("code"));
+        table.add(Shortcut::new("cs", "Cảm ơn anh/chị").in_group("email"));
+
+        // Unconfigured groups are active everywhere
+        assert!(table.lookup("fnc").is_some());
+
+        table.bind_group_context("code", "com.microsoft.VSCode");
+        table.bind_group_context("email", "com.apple.mail");
+        assert!(table.lookup("fnc").is_none());
+        assert!(table.lookup("vn").is_some());
+
+        table.set_active_context("com.microsoft.VSCode");
+        assert!(table.lookup("fnc").is_some());
+        assert!(table.lookup("cs").is_none());
+
+        table.set_group_enabled("code", false);
+        assert!(table.lookup("fnc").is_none());
+        table.set_group_enabled("code", true);
+
+        table.set_active_context("");
+        assert_eq!(table.active_context(), None);
+        table.clear_group_contexts("email");
+        assert!(table.lookup("cs").is_some());
+        assert_eq!(table.group_names(), vec!["code", "email"]);
+
+        // Reloading shortcuts keeps group settings
+        table.set_group_enabled("code", false);
+        table.clear();
+        table.add(Shortcut::new("fnc", "function $|() {}").in_group("code"));
+        assert!(table.lookup("fnc").is_none());
+        table.clear_groups();
+        assert!(table.lookup("fnc").is_some());
+    }
+
+    #[test]
+    fn test_gonhanh_format_group_field() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("fnc", "function").in_group("code"));
+        let (text, _) = table.export(MacroFormat::Gonhanh);
+        assert!(text.ends_with("fnc\tfunction\tword\tmatch\tall\t1\tcode\n"));
+
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::Gonhanh), (1, vec![]));
+        assert_eq!(copy.get("fnc").unwrap().group.as_deref(), Some("code"));
+    }
+
+    #[test]
+    fn test_match_source_filter() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("ddc", "được").on_raw_keys());
+        table.add(Shortcut::new("đc", "được"));
+
+        let raw = |table: &ShortcutTable, text: &str| {
+            table
+                .try_match_source_for_method(
+                    text,
+                    MatchSource::RawKeys,
+                    None,
+                    true,
+                    InputMethod::All,
+                )
+                .map(|m| m.output)
+        };
+        assert_eq!(raw(&table, "ddc").as_deref(), Some("được"));
+        assert_eq!(raw(&table, "đc"), None);
+        assert!(table
+            .try_match_source_for_method("ddc", MatchSource::Composed, None, true, InputMethod::All)
+            .is_none());
+        // Unfiltered matching (text typed with IME off) accepts both
+        assert!(table.try_match("ddc", None, true).is_some());
+
+        assert!(table.set_match_source("DDC", MatchSource::Composed));
+        assert!(!table.set_match_source("xyz", MatchSource::Composed));
+        assert_eq!(raw(&table, "ddc"), None);
+    }
+
+    #[test]
+    fn test_gonhanh_format_match_source() {
+        let mut table = ShortcutTable::new();
+        table.add(Shortcut::new("ddc", "được").on_raw_keys());
+        let (text, _) = table.export(MacroFormat::Gonhanh);
+        assert!(text.ends_with("ddc\tđược\tword\tmatch\tall\t1\t\traw\n"));
+
+        let mut copy = ShortcutTable::new();
+        assert_eq!(copy.import(&text, MacroFormat::Gonhanh), (1, vec![]));
+        let shortcut = copy.get("ddc").unwrap();
+        assert_eq!(shortcut.match_source, MatchSource::RawKeys);
+        assert_eq!(shortcut.group, None);
+
+        let (count, errors) =
+            copy.import("a\tb\tword\tmatch\tall\t1\t\tkeys\n", MacroFormat::Gonhanh);
+        assert_eq!((count, errors.len()), (0, 1));
+    }
 }
@@ -0,0 +1,274 @@
+//! Shortcut Templates - dynamic placeholders in replacements
+//!
+//! Evaluated at expansion time, before case handling:
+//! - `{date}` / `{date:dd/MM/yyyy}` - current date
+//! - `{time}` / `{time:HH:mm}` - current time
+//! - `{thu}` - Vietnamese weekday name ("Thứ Hai" … "Chủ Nhật")
+//! - `{counter}` / `{counter:name}` - auto-incrementing counter (1, 2, 3…)
+//!
+//! Format tokens: `yyyy yy MM M dd d HH H hh h mm m ss s`, anything else is literal.
+//! Unknown placeholders are kept as-is, so plain braces in replacements still work.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Source of the current time: Unix seconds (UTC)
+///
+/// A plain function pointer, so tests can inject a fixed time.
+pub type Clock = fn() -> i64;
+
+/// Default clock: system time
+pub fn system_clock() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Default UTC offset: Vietnam (UTC+7), until the host provides its own
+pub const DEFAULT_UTC_OFFSET_MINUTES: i32 = 7 * 60;
+
+const WEEKDAYS: [&str; 7] = [
+    "Chủ Nhật",
+    "Thứ Hai",
+    "Thứ Ba",
+    "Thứ Tư",
+    "Thứ Năm",
+    "Thứ Sáu",
+    "Thứ Bảy",
+];
+
+/// Broken-down local time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    /// 0 = Sunday … 6 = Saturday
+    pub weekday: u32,
+}
+
+impl DateTime {
+    /// Convert Unix seconds (already shifted to local time) to calendar fields
+    pub fn from_unix(secs: i64) -> Self {
+        let days = secs.div_euclid(86_400);
+        let rem = secs.rem_euclid(86_400) as u32;
+
+        // Civil-from-days (proleptic Gregorian), era = 400 years
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: rem / 3600,
+            minute: rem / 60 % 60,
+            second: rem % 60,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7) as u32,
+        }
+    }
+
+    /// Format with `yyyy yy MM M dd d HH H hh h mm m ss s` tokens
+    pub fn format(&self, pattern: &str) -> String {
+        let hour12 = match self.hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        let mut out = String::new();
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let run = chars[i..].iter().take_while(|&&x| x == c).count();
+            // Longest token wins: yyyy/yy, otherwise 2-letter (padded) or 1-letter
+            let width = match c {
+                'y' if run >= 4 => 4,
+                'y' => 2,
+                _ => run.min(2),
+            };
+            let value = match c {
+                'y' if width == 4 => self.year,
+                'y' => self.year.rem_euclid(100),
+                'M' => self.month as i64,
+                'd' => self.day as i64,
+                'H' => self.hour as i64,
+                'h' => hour12 as i64,
+                'm' => self.minute as i64,
+                's' => self.second as i64,
+                _ => {
+                    out.push(c);
+                    i += 1;
+                    continue;
+                }
+            };
+            out.push_str(&format!("{:0width$}", value, width = width));
+            i += width.min(run);
+        }
+        out
+    }
+}
+
+/// Template evaluation state: clock, timezone and counters
+#[derive(Debug)]
+pub struct Templates {
+    clock: Clock,
+    utc_offset_minutes: i32,
+    /// Counters are bumped during matching, which only has `&self`
+    counters: RefCell<HashMap<String, u64>>,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self {
+            clock: system_clock,
+            utc_offset_minutes: DEFAULT_UTC_OFFSET_MINUTES,
+            counters: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Templates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the time source (for deterministic tests)
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Set local timezone offset from UTC in minutes (e.g. 420 for UTC+7)
+    pub fn set_utc_offset(&mut self, minutes: i32) {
+        self.utc_offset_minutes = minutes;
+    }
+
+    /// Reset all counters (next expansion starts at 1 again)
+    pub fn reset_counters(&self) {
+        self.counters.borrow_mut().clear();
+    }
+
+    /// Current local time
+    pub fn now(&self) -> DateTime {
+        DateTime::from_unix((self.clock)() + i64::from(self.utc_offset_minutes) * 60)
+    }
+
+    /// Evaluate placeholders in a replacement
+    pub fn expand(&self, replacement: &str) -> String {
+        if !replacement.contains('{') {
+            return replacement.to_string();
+        }
+
+        let mut out = String::with_capacity(replacement.len());
+        let mut rest = replacement;
+        let mut cached_now = None;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let body = &after[..end];
+            let (name, arg) = match body.split_once(':') {
+                Some((name, arg)) => (name, Some(arg)),
+                None => (body, None),
+            };
+            let mut now = || *cached_now.get_or_insert_with(|| self.now());
+            match (name, arg) {
+                ("date", arg) => out.push_str(&now().format(arg.unwrap_or("dd/MM/yyyy"))),
+                ("time", arg) => out.push_str(&now().format(arg.unwrap_or("HH:mm"))),
+                ("thu", None) => out.push_str(WEEKDAYS[now().weekday as usize]),
+                ("counter", arg) => {
+                    let mut counters = self.counters.borrow_mut();
+                    let value = counters.entry(arg.unwrap_or("").to_string()).or_insert(0);
+                    *value += 1;
+                    out.push_str(&value.to_string());
+                }
+                // Unknown placeholder: keep literally
+                _ => {
+                    out.push('{');
+                    out.push_str(body);
+                    out.push('}');
+                }
+            }
+            rest = &after[end + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-04 09:05:07 UTC (Monday)
+    fn fixed_clock() -> i64 {
+        1_709_543_107
+    }
+
+    fn templates() -> Templates {
+        let mut t = Templates::new();
+        t.set_clock(fixed_clock);
+        t
+    }
+
+    #[test]
+    fn test_date_conversion() {
+        let dt = DateTime::from_unix(0);
+        assert_eq!((dt.year, dt.month, dt.day, dt.weekday), (1970, 1, 1, 4));
+        let dt = DateTime::from_unix(951_782_400); // 2000-02-29
+        assert_eq!((dt.year, dt.month, dt.day), (2000, 2, 29));
+        let dt = DateTime::from_unix(-1);
+        assert_eq!((dt.year, dt.month, dt.day, dt.hour), (1969, 12, 31, 23));
+    }
+
+    #[test]
+    fn test_date_time_placeholders() {
+        let t = templates();
+        assert_eq!(t.expand("{date}"), "04/03/2024");
+        assert_eq!(t.expand("{date:yyyy-MM-dd}"), "2024-03-04");
+        assert_eq!(t.expand("{date:d/M/yy}"), "4/3/24");
+        // UTC+7 by default
+        assert_eq!(t.expand("{time}"), "16:05");
+        assert_eq!(t.expand("{time:hh:mm:ss}"), "04:05:07");
+    }
+
+    #[test]
+    fn test_weekday_and_timezone() {
+        let mut t = templates();
+        assert_eq!(t.expand("{thu}, {date}"), "Thứ Hai, 04/03/2024");
+        t.set_utc_offset(-10 * 60);
+        assert_eq!(t.expand("{thu}, {date}"), "Chủ Nhật, 03/03/2024");
+    }
+
+    #[test]
+    fn test_counters() {
+        let t = templates();
+        assert_eq!(t.expand("#{counter}"), "#1");
+        assert_eq!(t.expand("#{counter}"), "#2");
+        assert_eq!(t.expand("{counter:hd}-{counter:hd}"), "1-2");
+        t.reset_counters();
+        assert_eq!(t.expand("#{counter}"), "#1");
+    }
+
+    #[test]
+    fn test_unknown_placeholders_kept() {
+        let t = templates();
+        assert_eq!(t.expand("fn() {}"), "fn() {}");
+        assert_eq!(t.expand("{name} {date"), "{name} {date");
+        assert_eq!(t.expand("{thu:x}"), "{thu:x}");
+    }
+}
@@ -0,0 +1,177 @@
+//! Token Class - text that must be typed verbatim
+//!
+//! Recognizes, from the text typed since the last whitespace:
+//! - URLs: "http:", "https:", "ftp:", "www.", anything with "://"
+//! - E-mail addresses: a letter or digit followed by '@'
+//! - Paths: "/usr" and other known roots, "/x/", "~/", "./", "../", "C:\",
+//!   anything with '\'
+//! - Identifiers: snake_case ("my_var", "_init")
+//! - Numbers with units or hex: "100kg", "1080p", "0xff"
+//!
+//! Units are a fixed list spelled without Telex/VNI modifier keys, so money and
+//! words after digits still compose ("100dd" → "100đ").
+//!
+//! camelCase needs the engine's syllable state (see `Engine::starts_camel_hump`).
+//! Once a token is classified, Vietnamese transforms are suppressed until the
+//! next whitespace.
+
+/// Kind of verbatim token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenClass {
+    Url,
+    Email,
+    Path,
+    Identifier,
+    Number,
+}
+
+/// Class of the text since the last whitespace, None for ordinary words
+pub fn classify(token: &str) -> Option<TokenClass> {
+    if is_url(token) {
+        Some(TokenClass::Url)
+    } else if is_email(token) {
+        Some(TokenClass::Email)
+    } else if is_path(token) {
+        Some(TokenClass::Path)
+    } else if is_snake_case(token) {
+        Some(TokenClass::Identifier)
+    } else if is_number_with_unit(token) {
+        Some(TokenClass::Number)
+    } else {
+        None
+    }
+}
+
+fn is_url(token: &str) -> bool {
+    let lower = token.to_ascii_lowercase();
+    ["http:", "https:", "ftp:", "www."]
+        .iter()
+        .any(|p| lower.starts_with(p))
+        || lower.contains("://")
+}
+
+fn is_email(token: &str) -> bool {
+    token
+        .split_once('@')
+        .is_some_and(|(local, _)| local.chars().last().is_some_and(char::is_alphanumeric))
+}
+
+/// Unix and macOS top-level directories recognized without a second '/'
+const PATH_ROOTS: &[&str] = &[
+    "Applications",
+    "Library",
+    "System",
+    "Users",
+    "Volumes",
+    "bin",
+    "dev",
+    "etc",
+    "home",
+    "lib",
+    "mnt",
+    "opt",
+    "private",
+    "proc",
+    "root",
+    "sbin",
+    "srv",
+    "sys",
+    "tmp",
+    "usr",
+    "var",
+];
+
+/// Absolute Unix path: a known root ("/usr") or a second separator ("/x/")
+///
+/// "/" followed by a word ("/viết", "/hoặc") is ordinary text.
+fn is_unix_path(token: &str) -> bool {
+    let Some(rest) = token.strip_prefix('/') else {
+        return false;
+    };
+    match rest.split_once('/') {
+        Some((first, _)) => !first.is_empty(),
+        None => PATH_ROOTS.contains(&rest),
+    }
+}
+
+fn is_path(token: &str) -> bool {
+    let mut chars = token.chars();
+    let drive = matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (Some(d), Some(':'), Some('\\' | '/')) if d.is_ascii_alphabetic()
+    );
+    is_unix_path(token)
+        || ["~/", "./", "../"].iter().any(|p| token.starts_with(p))
+        || token.contains('\\')
+        || drive
+}
+
+fn is_snake_case(token: &str) -> bool {
+    token.contains('_') && token.chars().any(|c| c.is_ascii_alphanumeric())
+}
+
+/// Unit suffixes after a number; none contains a Telex modifier
+/// (a, d, e, o, w, s, f, r, x, j, z) or a VNI one (digits)
+const UNITS: &[&str] = &[
+    "b", "kb", "mb", "gb", "tb", "g", "kg", "mg", "m", "cm", "mm", "km", "l", "ml", "h", "min",
+    "p", "k", "v", "kv",
+];
+
+fn is_number_with_unit(token: &str) -> bool {
+    let lower = token.to_ascii_lowercase();
+    if let Some(hex) = lower.strip_prefix("0x") {
+        return hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    let unit = lower.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.' || c == ',');
+    unit.len() < lower.len()
+        && lower.starts_with(|c: char| c.is_ascii_digit())
+        && UNITS.contains(&unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("https:"), Some(TokenClass::Url));
+        assert_eq!(classify("WWW."), Some(TokenClass::Url));
+        assert_eq!(classify("nhatkha1407@"), Some(TokenClass::Email));
+        assert_eq!(classify("/usr"), Some(TokenClass::Path));
+        assert_eq!(classify("/Users"), Some(TokenClass::Path));
+        assert_eq!(classify("/src/"), Some(TokenClass::Path));
+        assert_eq!(classify("C:\\"), Some(TokenClass::Path));
+        assert_eq!(classify("my_"), Some(TokenClass::Identifier));
+        assert_eq!(classify("0x"), Some(TokenClass::Number));
+        assert_eq!(classify("100k"), Some(TokenClass::Number));
+        assert_eq!(classify("1080p"), Some(TokenClass::Number));
+        assert_eq!(classify("2,5KG"), Some(TokenClass::Number));
+    }
+
+    #[test]
+    fn test_ordinary_text() {
+        for token in [
+            "",
+            "xin",
+            "tiếng",
+            "/",
+            "//",
+            "/u",
+            "/vieets",
+            "/users",
+            "và/hoặc",
+            "@",
+            "_",
+            "2024",
+            "v.v.",
+            "1.5",
+            // Telex "dd" → đ, "s" → sắc: still composed
+            "100d",
+            "100dd",
+            "5s",
+            "0xg",
+        ] {
+            assert_eq!(classify(token), None, "{token}");
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Typography - optional smart punctuation on the break-key path
+//!
+//! Each rule is enabled separately:
+//! - Smart quotes: `"` → “ ”, `'` → ‘ ’ (opening after whitespace or a bracket)
+//! - Dashes: " --" → " –", " ---" → " —" (only after whitespace: "a--b" is kept)
+//! - Punctuation spacing: "xin ," → "xin," (space before , . ; : ! ? removed)
+//! - Double space: "xin  " → "xin. " (the next word is capitalized)
+//!
+//! Text that looks like a URL, e-mail address or path is never touched.
+
+/// Enabled typography rules (all off by default)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Typography {
+    pub smart_quotes: bool,
+    pub dashes: bool,
+    pub punctuation_spacing: bool,
+    pub double_space_period: bool,
+}
+
+impl Typography {
+    /// FFI bit for `smart_quotes`
+    pub const SMART_QUOTES: u8 = 0x01;
+    /// FFI bit for `dashes`
+    pub const DASHES: u8 = 0x02;
+    /// FFI bit for `punctuation_spacing`
+    pub const PUNCTUATION_SPACING: u8 = 0x04;
+    /// FFI bit for `double_space_period`
+    pub const DOUBLE_SPACE_PERIOD: u8 = 0x08;
+
+    /// Rules from FFI bits (unknown bits are ignored)
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            smart_quotes: bits & Self::SMART_QUOTES != 0,
+            dashes: bits & Self::DASHES != 0,
+            punctuation_spacing: bits & Self::PUNCTUATION_SPACING != 0,
+            double_space_period: bits & Self::DOUBLE_SPACE_PERIOD != 0,
+        }
+    }
+
+    /// Rules as FFI bits
+    pub fn bits(self) -> u8 {
+        (u8::from(self.smart_quotes) * Self::SMART_QUOTES)
+            | (u8::from(self.dashes) * Self::DASHES)
+            | (u8::from(self.punctuation_spacing) * Self::PUNCTUATION_SPACING)
+            | (u8::from(self.double_space_period) * Self::DOUBLE_SPACE_PERIOD)
+    }
+
+    /// Any rule enabled
+    pub fn is_enabled(self) -> bool {
+        self.bits() != 0
+    }
+}
+
+/// Curly form of a straight quote, from the char before it (None = line start)
+pub fn smart_quote(quote: char, prev: Option<char>) -> char {
+    let opening = prev.is_none_or(|c| c.is_whitespace() || "([{“‘–—".contains(c));
+    match (quote, opening) {
+        ('"', true) => '“',
+        ('"', false) => '”',
+        ('\'', true) => '‘',
+        ('\'', false) => '’',
+        (c, _) => c,
+    }
+}
+
+/// Dash replacing the text since the last whitespace when '-' is typed
+///
+/// "-" → en dash, "–" → em dash; anything else (e.g. "a-") keeps a hyphen.
+pub fn dash(token: &str) -> Option<char> {
+    match token {
+        "-" => Some('–'),
+        "–" => Some('—'),
+        _ => None,
+    }
+}
+
+/// Punctuation that never follows a space in Vietnamese text
+pub fn is_closing_punctuation(c: char) -> bool {
+    matches!(c, ',' | '.' | ';' | ':' | '!' | '?')
+}
+
+/// Text since the last whitespace looks like a URL, e-mail address or path
+pub fn is_url_like(token: &str) -> bool {
+    token.contains("://")
+        || token.starts_with("www.")
+        || token.contains('@')
+        || token.contains('/')
+        || token.contains('\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_round_trip() {
+        let rules =
+            Typography::from_bits(Typography::SMART_QUOTES | Typography::DOUBLE_SPACE_PERIOD);
+        assert!(rules.smart_quotes && rules.double_space_period);
+        assert!(!rules.dashes && !rules.punctuation_spacing);
+        assert_eq!(rules.bits(), 0x09);
+        assert!(!Typography::from_bits(0xF0).is_enabled());
+    }
+
+    #[test]
+    fn test_smart_quote() {
+        assert_eq!(smart_quote('"', None), '“');
+        assert_eq!(smart_quote('"', Some(' ')), '“');
+        assert_eq!(smart_quote('"', Some('a')), '”');
+        assert_eq!(smart_quote('\'', Some('n')), '’');
+        assert_eq!(smart_quote('\'', Some('(')), '‘');
+    }
+
+    #[test]
+    fn test_dash_and_url() {
+        assert_eq!(dash("-"), Some('–'));
+        assert_eq!(dash("–"), Some('—'));
+        assert_eq!(dash("a-"), None);
+        assert!(is_url_like("https://vi.wikipedia.org"));
+        assert!(is_url_like("ban@gmail.com"));
+        assert!(!is_url_like("xin"));
+    }
+}
@@ -0,0 +1,299 @@
+//! User Dictionary - runtime word layers
+//!
+//! Lets users extend or override the embedded dictionaries used by auto-restore:
+//! - Vietnamese layer: words accepted/rejected on top of `vietnamese_spellcheck`
+//! - English layer: words accepted/rejected on top of `english_dict`
+//!
+//! The user layer takes precedence: an added word is always found, a blocked word
+//! is never found, anything else falls through to the embedded dictionary.
+//!
+//! ## Text Format
+//!
+//! ```text
+//! # comment
+//! [vi]
+//! ko
+//! -hòa        (leading '-' blocks the word)
+//! [en]
+//! gonhanh
+//! ```
+
+use super::ParseError;
+use std::collections::HashSet;
+
+/// Language layer of the user dictionary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Language {
+    Vietnamese = 0,
+    English = 1,
+}
+
+impl Language {
+    /// Convert FFI value (0=Vietnamese, 1=English)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Language::Vietnamese),
+            1 => Some(Language::English),
+            _ => None,
+        }
+    }
+
+    fn section(self) -> &'static str {
+        match self {
+            Language::Vietnamese => "vi",
+            Language::English => "en",
+        }
+    }
+}
+
+/// Words of one language layer (stored lowercase)
+#[derive(Debug, Default)]
+struct Layer {
+    added: HashSet<String>,
+    blocked: HashSet<String>,
+}
+
+impl Layer {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.blocked.is_empty()
+    }
+}
+
+/// Runtime user dictionary (Vietnamese + English layers)
+#[derive(Debug, Default)]
+pub struct UserDictionary {
+    vietnamese: Layer,
+    english: Layer,
+}
+
+impl UserDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn layer(&self, lang: Language) -> &Layer {
+        match lang {
+            Language::Vietnamese => &self.vietnamese,
+            Language::English => &self.english,
+        }
+    }
+
+    fn layer_mut(&mut self, lang: Language) -> &mut Layer {
+        match lang {
+            Language::Vietnamese => &mut self.vietnamese,
+            Language::English => &mut self.english,
+        }
+    }
+
+    /// Add a word: it is always considered part of the language
+    pub fn add(&mut self, lang: Language, word: &str) {
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            return;
+        }
+        let layer = self.layer_mut(lang);
+        layer.blocked.remove(&word);
+        layer.added.insert(word);
+    }
+
+    /// Block a word: it is never considered part of the language,
+    /// even if the embedded dictionary contains it
+    pub fn block(&mut self, lang: Language, word: &str) {
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            return;
+        }
+        let layer = self.layer_mut(lang);
+        layer.added.remove(&word);
+        layer.blocked.insert(word);
+    }
+
+    /// Remove a word from the user layer (falls back to the embedded dictionary)
+    ///
+    /// Returns true if the word was in the user layer
+    pub fn remove(&mut self, lang: Language, word: &str) -> bool {
+        let word = word.trim().to_lowercase();
+        let layer = self.layer_mut(lang);
+        let added = layer.added.remove(&word);
+        let blocked = layer.blocked.remove(&word);
+        added || blocked
+    }
+
+    /// Look up a word in the user layer (case-insensitive)
+    ///
+    /// - `Some(true)`: user added the word
+    /// - `Some(false)`: user blocked the word
+    /// - `None`: not in user layer, use embedded dictionary
+    pub fn lookup(&self, lang: Language, word: &str) -> Option<bool> {
+        let layer = self.layer(lang);
+        if layer.is_empty() {
+            return None;
+        }
+        let word = word.to_lowercase();
+        if layer.added.contains(&word) {
+            Some(true)
+        } else if layer.blocked.contains(&word) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Number of entries (added + blocked) in a language layer
+    pub fn len(&self, lang: Language) -> usize {
+        let layer = self.layer(lang);
+        layer.added.len() + layer.blocked.len()
+    }
+
+    /// Check if both layers are empty
+    pub fn is_empty(&self) -> bool {
+        self.vietnamese.is_empty() && self.english.is_empty()
+    }
+
+    /// Clear both layers
+    pub fn clear(&mut self) {
+        self.vietnamese = Layer::default();
+        self.english = Layer::default();
+    }
+
+    /// Import words from text format, merging into existing layers
+    ///
+    /// Valid lines are applied even if other lines fail.
+    /// Returns number of imported words and per-line errors.
+    pub fn import(&mut self, text: &str) -> (usize, Vec<ParseError>) {
+        let mut lang = None;
+        let mut count = 0;
+        let mut errors = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                lang = match section.trim() {
+                    "vi" => Some(Language::Vietnamese),
+                    "en" => Some(Language::English),
+                    other => {
+                        errors.push(ParseError {
+                            line: i + 1,
+                            message: format!("unknown section [{}]", other),
+                        });
+                        None
+                    }
+                };
+                continue;
+            }
+
+            let Some(lang) = lang else {
+                errors.push(ParseError {
+                    line: i + 1,
+                    message: "word outside of [vi] or [en] section".to_string(),
+                });
+                continue;
+            };
+
+            let (blocked, word) = match line.strip_prefix('-') {
+                Some(rest) => (true, rest.trim()),
+                None => (false, line),
+            };
+            if word.is_empty() || word.chars().any(char::is_whitespace) {
+                errors.push(ParseError {
+                    line: i + 1,
+                    message: format!("invalid word '{}'", line),
+                });
+                continue;
+            }
+
+            if blocked {
+                self.block(lang, word);
+            } else {
+                self.add(lang, word);
+            }
+            count += 1;
+        }
+
+        (count, errors)
+    }
+
+    /// Export both layers to text format (sorted for stable output)
+    pub fn export(&self) -> String {
+        let mut out = String::new();
+        for lang in [Language::Vietnamese, Language::English] {
+            let layer = self.layer(lang);
+            if layer.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("[{}]\n", lang.section()));
+
+            let mut added: Vec<&String> = layer.added.iter().collect();
+            added.sort();
+            for word in added {
+                out.push_str(word);
+                out.push('\n');
+            }
+
+            let mut blocked: Vec<&String> = layer.blocked.iter().collect();
+            blocked.sort();
+            for word in blocked {
+                out.push('-');
+                out.push_str(word);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_block_remove() {
+        let mut dict = UserDictionary::new();
+        assert_eq!(dict.lookup(Language::English, "gonhanh"), None);
+
+        dict.add(Language::English, "GoNhanh");
+        assert_eq!(dict.lookup(Language::English, "gonhanh"), Some(true));
+        assert_eq!(dict.lookup(Language::Vietnamese, "gonhanh"), None);
+
+        dict.block(Language::English, "gonhanh");
+        assert_eq!(dict.lookup(Language::English, "GONHANH"), Some(false));
+        assert_eq!(dict.len(Language::English), 1);
+
+        assert!(dict.remove(Language::English, "gonhanh"));
+        assert_eq!(dict.lookup(Language::English, "gonhanh"), None);
+        assert!(dict.is_empty());
+    }
+
+    #[test]
+    fn test_import_export_roundtrip() {
+        let mut dict = UserDictionary::new();
+        let (count, errors) = dict.import("# team words\n[vi]\nko\n-hòa\n\n[en]\nfix\n");
+        assert_eq!(count, 3);
+        assert!(errors.is_empty());
+        assert_eq!(dict.lookup(Language::Vietnamese, "ko"), Some(true));
+        assert_eq!(dict.lookup(Language::Vietnamese, "hòa"), Some(false));
+        assert_eq!(dict.lookup(Language::English, "fix"), Some(true));
+
+        let text = dict.export();
+        assert_eq!(text, "[vi]\nko\n-hòa\n[en]\nfix\n");
+
+        let mut copy = UserDictionary::new();
+        assert_eq!(copy.import(&text), (3, vec![]));
+        assert_eq!(copy.export(), text);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let mut dict = UserDictionary::new();
+        let (count, errors) = dict.import("orphan\n[fr]\nmot\n[en]\ntwo words\nok\n");
+        assert_eq!(count, 1);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![1, 2, 3, 5]);
+        assert_eq!(dict.lookup(Language::English, "ok"), Some(true));
+    }
+}
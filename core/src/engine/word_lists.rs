@@ -0,0 +1,130 @@
+//! Word Lists - explicit per-user transform/restore exceptions
+//!
+//! Separate from the dictionaries, these lists are absolute:
+//! - Never-transform: raw keystroke sequences that never get Vietnamese transforms
+//!   (e.g. "fix", "mass", internal codenames)
+//! - Never-restore: Vietnamese words that are never auto-restored to English,
+//!   even if their keystrokes look like an English word
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Never-transform and never-restore lists (entries stored lowercase)
+#[derive(Debug, Default)]
+pub struct WordLists {
+    never_transform: HashSet<String>,
+    never_restore: HashSet<String>,
+}
+
+impl WordLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn normalize(word: &str) -> Option<String> {
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            None
+        } else {
+            Some(word)
+        }
+    }
+
+    /// Add raw keystroke sequence that must never be transformed
+    pub fn add_never_transform(&mut self, raw: &str) {
+        if let Some(raw) = Self::normalize(raw) {
+            self.never_transform.insert(raw);
+        }
+    }
+
+    /// Remove raw keystroke sequence, returns true if it was present
+    pub fn remove_never_transform(&mut self, raw: &str) -> bool {
+        self.never_transform.remove(&raw.trim().to_lowercase())
+    }
+
+    /// Check raw keystroke sequence (case-insensitive)
+    pub fn is_never_transform(&self, raw: &str) -> bool {
+        if self.never_transform.is_empty() {
+            return false;
+        }
+        let raw = if raw.chars().any(char::is_uppercase) {
+            Cow::Owned(raw.to_lowercase())
+        } else {
+            Cow::Borrowed(raw)
+        };
+        self.never_transform.contains(raw.as_ref())
+    }
+
+    /// True if no never-transform entries exist (lets callers skip building the key string)
+    pub fn never_transform_is_empty(&self) -> bool {
+        self.never_transform.is_empty()
+    }
+
+    /// Add Vietnamese word that must never be auto-restored
+    pub fn add_never_restore(&mut self, word: &str) {
+        if let Some(word) = Self::normalize(word) {
+            self.never_restore.insert(word);
+        }
+    }
+
+    /// Remove Vietnamese word, returns true if it was present
+    pub fn remove_never_restore(&mut self, word: &str) -> bool {
+        self.never_restore.remove(&word.trim().to_lowercase())
+    }
+
+    /// Check composed Vietnamese word (case-insensitive)
+    pub fn is_never_restore(&self, word: &str) -> bool {
+        !self.never_restore.is_empty() && self.never_restore.contains(&word.to_lowercase())
+    }
+
+    /// Never-transform entries, sorted
+    pub fn never_transform(&self) -> Vec<&str> {
+        let mut list: Vec<&str> = self.never_transform.iter().map(String::as_str).collect();
+        list.sort_unstable();
+        list
+    }
+
+    /// Never-restore entries, sorted
+    pub fn never_restore(&self) -> Vec<&str> {
+        let mut list: Vec<&str> = self.never_restore.iter().map(String::as_str).collect();
+        list.sort_unstable();
+        list
+    }
+
+    /// Clear both lists
+    pub fn clear(&mut self) {
+        self.never_transform.clear();
+        self.never_restore.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never_transform() {
+        let mut lists = WordLists::new();
+        assert!(!lists.is_never_transform("fix"));
+        assert!(lists.never_transform_is_empty());
+        lists.add_never_transform(" Fix ");
+        lists.add_never_transform("");
+        assert!(!lists.never_transform_is_empty());
+        assert!(lists.is_never_transform("fix"));
+        assert!(lists.is_never_transform("FIX"));
+        assert!(!lists.is_never_transform("fi"));
+        assert_eq!(lists.never_transform(), vec!["fix"]);
+        assert!(lists.remove_never_transform("FIX"));
+        assert!(!lists.is_never_transform("fix"));
+    }
+
+    #[test]
+    fn test_never_restore() {
+        let mut lists = WordLists::new();
+        lists.add_never_restore("Tôt");
+        assert!(lists.is_never_restore("tôt"));
+        assert!(!lists.is_never_transform("tôt"));
+        lists.clear();
+        assert!(!lists.is_never_restore("tôt"));
+    }
+}
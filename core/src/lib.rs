@@ -70,6 +70,11 @@ pub extern "C" fn ime_init() {
 /// * `backspace`: number of characters to delete
 /// * `chars`: UTF-32 codepoints to insert
 /// * `count`: number of valid chars
+/// * `flags`: `FLAG_KEY_CONSUMED`, `FLAG_MORE_OUTPUT`, `FLAG_MISSPELLED`
+/// * `cursor_left`: after inserting `chars`, the host must send this many
+///   Left-arrow key presses (shortcut `$|` cursor marker); 0 = none
+///
+/// The struct is 1032 bytes; host mirrors must keep this layout.
 ///
 /// # Note
 /// For VNI mode with Shift+number keys (to type @, #, $ etc.),
@@ -240,6 +245,9 @@ pub extern "C" fn ime_free_tone(enabled: bool) {
 ///
 /// When `modern` is true: hoà, thuý (tone on second vowel - new style)
 /// When `modern` is false (default): hòa, thúy (tone on first vowel - traditional)
+///
+/// Applies to all rhymes and resets overrides made with `ime_rhyme_tone`:
+/// call `ime_modern` first, then `ime_rhyme_tone` for each overridden rhyme.
 /// No-op if engine not initialized.
 #[no_mangle]
 pub extern "C" fn ime_modern(modern: bool) {
@@ -249,6 +257,29 @@ pub extern "C" fn ime_modern(modern: bool) {
     }
 }
 
+/// Set tone placement for one rhyme (house style).
+///
+/// Overrides `ime_modern` for a single rhyme, e.g. modern "oa"/"oe" with
+/// traditional "uy" (hoà, khoẻ, thúy). Used for typing and dictionary checks.
+/// A later `ime_modern` call resets the override, so call this after it.
+///
+/// # Arguments
+/// * `rhyme` - 0=oa, 1=oe, 2=uy (invalid values are ignored)
+/// * `modern` - true: tone on the second vowel (oà), false: on the first (òa)
+///
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_rhyme_tone(rhyme: u8, modern: bool) {
+    let Some(rhyme) = data::StyledRhyme::from_u8(rhyme) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        let placement = e.tone_placement().with(rhyme, modern);
+        e.set_tone_placement(placement);
+    }
+}
+
 /// Enable/disable English auto-restore (experimental feature).
 ///
 /// When `enabled` is true, automatically restores English words that were
@@ -263,6 +294,153 @@ pub extern "C" fn ime_english_auto_restore(enabled: bool) {
     }
 }
 
+/// Enable/disable context-aware auto-restore (bigram model).
+///
+/// When `enabled` is true, English auto-restore also considers the previous
+/// committed word (e.g., "cool mix" → "mix", "chẳng bõ" → "bõ"), and word pairs
+/// added with `ime_add_bigram` (none are built in).
+/// Has no effect unless English auto-restore is enabled. Default: false.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_context_restore(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_context_restore(enabled);
+    }
+}
+
+/// Enable/disable undoing a shortcut expansion with Backspace.
+///
+/// When `enabled` is true, Backspace right after an expansion replaces it with
+/// the trigger as typed (e.g., "Việt Nam " → "vn "). Default: false.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_shortcut_undo(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_shortcut_undo(enabled);
+    }
+}
+
+/// Enable/disable dictionary autocorrect of invalid committed words.
+///
+/// When `enabled` is true, a word typed with diacritics that is not in the
+/// dictionary is replaced on Space, punctuation or Enter by its unique nearest valid word
+/// (e.g., "tòan " → "toàn "). Backspace right after restores the word as typed.
+/// Default: false. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_autocorrect(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_autocorrect(enabled);
+    }
+}
+
+/// Set strict spelling for committed words.
+///
+/// A word committed by Space or punctuation that is neither in the Vietnamese
+/// dictionary nor an English word is handled by `mode`:
+/// * 0 - off: kept as composed (default)
+/// * 1 - restore: replaced by the raw keys as typed ("tiéng " → "tiengs ")
+/// * 2 - flag: kept, and the commit result has `FLAG_MISSPELLED` (0x04) set
+///
+/// Invalid modes are ignored. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_strict_spelling(mode: u8) {
+    let Some(mode) = engine::StrictSpelling::from_u8(mode) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_strict_spelling(mode);
+    }
+}
+
+/// Set the key that undoes the current word's transforms one at a time.
+///
+/// Each press steps back one transform: "được" → "đươc" → "đuoc" → "duoc".
+/// When no transform is left, the key is processed normally.
+///
+/// # Arguments
+/// * `key` - macOS virtual keycode (e.g., 53 for ESC)
+/// * `enabled` - false disables the undo key (default)
+///
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_undo_key(key: u16, enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_undo_key(enabled.then_some(key));
+    }
+}
+
+/// Set the key that forces the language of one word.
+///
+/// English commits the word as typed (raw keys), Vietnamese keeps it composed
+/// even when auto-restore would turn it into English.
+///
+/// # Arguments
+/// * `language` - 0 = Vietnamese, 1 = English
+/// * `key` - macOS virtual keycode (a key typing a character, not Space,
+///   for mode 2)
+/// * `mode` - 0 = off (default), 1 = prefix pressed before the word (pressed
+///   twice types the key), 2 = double-tapped right after the word
+///
+/// No-op if engine not initialized or `language` is invalid.
+#[no_mangle]
+pub extern "C" fn ime_word_escape(language: u8, key: u16, mode: u8) {
+    let Some(language) = engine::user_dict::Language::from_u8(language) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_word_escape(language, engine::WordEscape::from_u8(mode, key));
+    }
+}
+
+/// Enable or disable learning from forced words.
+///
+/// When enabled, a word forced to English is added to the never-transform
+/// list (raw keys) and a word forced to Vietnamese to the never-restore list.
+/// Default: false. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_learn_forced_words(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_learn_forced_words(enabled);
+    }
+}
+
+/// Undo the last transform of the current word (for a host-side undo action).
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`),
+///   with action None if the word has no transform left
+/// * `null` if engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_undo_transform() -> *mut Result {
+    let mut guard = lock_engine();
+    match *guard {
+        Some(ref mut e) => Box::into_raw(Box::new(e.undo_transform())),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Enable/disable cursor-aware editing of the current line.
+///
+/// When `enabled` is true, the engine mirrors the current line and re-enters
+/// a committed word when the caret lands right after it (Left/Right arrow or
+/// Backspace), so its tone can be changed in place. The host must then pass
+/// Left/Right arrow keys to `ime_key` instead of calling `ime_clear_all`.
+/// Default: false. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_line_editing(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_line_editing(enabled);
+    }
+}
+
 /// Enable/disable auto-capitalize after sentence-ending punctuation.
 ///
 /// When `enabled` is true, automatically capitalizes the first letter
@@ -277,6 +455,101 @@ pub extern "C" fn ime_auto_capitalize(enabled: bool) {
     }
 }
 
+/// Enable/disable capitalizing the first letter of a new line (after Enter).
+///
+/// Only applies while auto-capitalize is on. Default: true.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_capitalize_new_line(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_capitalize_new_line(enabled);
+    }
+}
+
+/// Set the typography rules applied on break keys.
+///
+/// # Arguments
+/// * `rules` - bit set, 0 = all off (default):
+///   * 0x01 - smart quotes: `"` → “ ”, `'` → ‘ ’
+///   * 0x02 - dashes: " --" → " –", " ---" → " —"
+///   * 0x04 - remove the space before , . ; : ! ?
+///   * 0x08 - double space → ". " (capitalizes the next word with auto-capitalize)
+///
+/// Shortcut triggers and URL-like text are never changed.
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_typography(rules: u8) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_typography(engine::typography::Typography::from_bits(rules));
+    }
+}
+
+/// Enable or disable verbatim token detection.
+///
+/// When enabled (default), URLs ("www.", "https:"), e-mail addresses, paths,
+/// snake_case/camelCase identifiers and numbers with units ("100kg", "0xff")
+/// are typed without Vietnamese transforms until the next whitespace.
+///
+/// # Arguments
+/// * `enabled` - true to detect tokens, false to transform everything
+///
+/// No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_token_detection(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_token_detection(enabled);
+    }
+}
+
+/// Add a proper noun capitalized mid-sentence by auto-capitalize.
+///
+/// # Arguments
+/// * `phrase` - C string in display form, up to 4 words (e.g., "Hà Nội")
+///
+/// # Returns
+/// true if added, false if empty, too long or the pointer is invalid.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_proper_noun(phrase: *const std::os::raw::c_char) -> bool {
+    let Some(phrase) = c_str_arg(phrase) else {
+        return false;
+    };
+    let mut guard = lock_engine();
+    match *guard {
+        Some(ref mut e) => e.proper_nouns_mut().add(phrase),
+        None => false,
+    }
+}
+
+/// Remove a proper noun (any case).
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_proper_noun(phrase: *const std::os::raw::c_char) {
+    let Some(phrase) = c_str_arg(phrase) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.proper_nouns_mut().remove(phrase);
+    }
+}
+
+/// Clear all proper nouns.
+#[no_mangle]
+pub extern "C" fn ime_clear_proper_nouns() {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.proper_nouns_mut().clear();
+    }
+}
+
 /// Enable/disable foreign consonants (z, w, j, f) as valid initial consonants.
 ///
 /// When `enabled` is true, allows z, w, j, f as valid Vietnamese consonants
@@ -362,6 +635,36 @@ pub unsafe extern "C" fn ime_free(r: *mut Result) {
     }
 }
 
+/// Enable/disable chunked delivery of long shortcut expansions.
+///
+/// Only enable if the host calls `ime_next_chunk` while a result has
+/// `FLAG_MORE_OUTPUT` (0x02) set. When disabled, expansions are cut to one
+/// result (255 chars). Default: false. No-op if engine not initialized.
+#[no_mangle]
+pub extern "C" fn ime_long_output(enabled: bool) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_long_output(enabled);
+    }
+}
+
+/// Fetch the next chunk of a long shortcut expansion.
+///
+/// Call while the previous result has `FLAG_MORE_OUTPUT` (0x02) set, and inject
+/// each chunk right after the previous one (chunks never have backspaces).
+///
+/// # Returns
+/// * Pointer to `Result` struct (caller must free with `ime_free`)
+/// * `null` if there is no pending output or engine not initialized
+#[no_mangle]
+pub extern "C" fn ime_next_chunk() -> *mut Result {
+    let mut guard = lock_engine();
+    match guard.as_mut().and_then(|e| e.next_output_chunk()) {
+        Some(r) => Box::into_raw(Box::new(r)),
+        None => std::ptr::null_mut(),
+    }
+}
+
 // ============================================================
 // Shortcut FFI
 // ============================================================
@@ -370,7 +673,8 @@ pub unsafe extern "C" fn ime_free(r: *mut Result) {
 ///
 /// # Arguments
 /// * `trigger` - C string for trigger (e.g., "vn")
-/// * `replacement` - C string for replacement (e.g., "Việt Nam")
+/// * `replacement` - C string for replacement (e.g., "Việt Nam"), may span
+///   several lines ('\n', '\t'); beyond one result it needs `ime_long_output`
 ///
 /// # Safety
 /// Both pointers must be valid null-terminated UTF-8 strings.
@@ -394,16 +698,11 @@ pub unsafe extern "C" fn ime_add_shortcut(
 
     let mut guard = lock_engine();
     if let Some(ref mut e) = *guard {
-        // Auto-detect shortcut type:
-        // - If trigger contains only non-letter chars (like "->", "=>"), use immediate trigger
-        // - Otherwise use word boundary trigger (traditional abbreviations like "vn" → "Việt Nam")
-        let is_symbol_trigger = trigger_str.chars().all(|c| !c.is_alphabetic());
-        let shortcut = if is_symbol_trigger {
-            engine::shortcut::Shortcut::immediate(trigger_str, replacement_str)
-        } else {
-            engine::shortcut::Shortcut::new(trigger_str, replacement_str)
-        };
-        e.shortcuts_mut().add(shortcut);
+        // Auto-detect shortcut type (symbol-only trigger → immediate, else word boundary)
+        e.shortcuts_mut().add(engine::shortcut::Shortcut::auto(
+            trigger_str,
+            replacement_str,
+        ));
     }
 }
 
@@ -432,6 +731,9 @@ pub unsafe extern "C" fn ime_remove_shortcut(trigger: *const std::os::raw::c_cha
 }
 
 /// Clear all shortcuts from the engine.
+///
+/// Group settings (enabled, context bindings) are kept; reset them with
+/// `ime_clear_shortcut_groups`.
 #[no_mangle]
 pub extern "C" fn ime_clear_shortcuts() {
     let mut guard = lock_engine();
@@ -440,36 +742,763 @@ pub extern "C" fn ime_clear_shortcuts() {
     }
 }
 
-// ============================================================
-// Word Restore FFI
-// ============================================================
+/// Add a shortcut with an explicit case mode.
+///
+/// # Arguments
+/// * `trigger` - C string for trigger (e.g., "vn")
+/// * `replacement` - C string for replacement (e.g., "Việt Nam")
+/// * `case_mode` - 0=Exact, 1=MatchCase (default of `ime_add_shortcut`),
+///   2=TitleCase ("Vn" → "Việt Nam"), 3=SentenceCase ("Vn" → "Việt nam"),
+///   4=Smart (like MatchCase, keeps "iPhone")
+///
+/// Invalid case modes are ignored (no shortcut is added).
+///
+/// # Safety
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_shortcut_with_case(
+    trigger: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+    case_mode: u8,
+) {
+    let (Some(trigger), Some(replacement)) = (c_str_arg(trigger), c_str_arg(replacement)) else {
+        return;
+    };
+    let Some(case_mode) = engine::shortcut::CaseMode::from_u8(case_mode) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut()
+            .add(engine::shortcut::Shortcut::auto(trigger, replacement).with_case_mode(case_mode));
+    }
+}
 
-/// Restore buffer from a Vietnamese word string.
+/// Add a shortcut to a named group (e.g. "code", "email").
 ///
-/// Used when native app detects cursor at word boundary and user
-/// wants to continue editing (e.g., backspace into previous word).
-/// Parses Vietnamese characters back to buffer components.
+/// Same as `ime_add_shortcut`, but the shortcut only fires while its group
+/// is enabled and matches the active context (see `ime_set_active_context`).
 ///
-/// # Arguments
-/// * `word` - C string containing the Vietnamese word to restore
+/// # Safety
+/// All pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_shortcut_to_group(
+    trigger: *const std::os::raw::c_char,
+    replacement: *const std::os::raw::c_char,
+    group: *const std::os::raw::c_char,
+) {
+    let (Some(trigger), Some(replacement), Some(group)) =
+        (c_str_arg(trigger), c_str_arg(replacement), c_str_arg(group))
+    else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut()
+            .add(engine::shortcut::Shortcut::auto(trigger, replacement).in_group(group));
+    }
+}
+
+/// Enable or disable a whole shortcut group.
 ///
 /// # Safety
 /// Pointer must be a valid null-terminated UTF-8 string.
 #[no_mangle]
-pub unsafe extern "C" fn ime_restore_word(word: *const std::os::raw::c_char) {
-    if word.is_null() {
+pub unsafe extern "C" fn ime_shortcut_group_enabled(
+    group: *const std::os::raw::c_char,
+    enabled: bool,
+) {
+    let Some(group) = c_str_arg(group) else {
         return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut().set_group_enabled(group, enabled);
     }
-    let word_str = match std::ffi::CStr::from_ptr(word).to_str() {
-        Ok(s) => s,
-        Err(_) => return,
+}
+
+/// Restrict a shortcut group to a context (e.g. an app bundle id).
+///
+/// A group bound to one or more contexts is only active while one of them
+/// is the active context. Unbound groups are active everywhere.
+///
+/// # Safety
+/// Both pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_shortcut_group_bind_context(
+    group: *const std::os::raw::c_char,
+    context: *const std::os::raw::c_char,
+) {
+    let (Some(group), Some(context)) = (c_str_arg(group), c_str_arg(context)) else {
+        return;
     };
     let mut guard = lock_engine();
     if let Some(ref mut e) = *guard {
-        e.restore_word(word_str);
+        e.shortcuts_mut().bind_group_context(group, context);
+    }
+}
+
+/// Remove all context bindings of a group (active everywhere again).
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_shortcut_group_clear_contexts(group: *const std::os::raw::c_char) {
+    let Some(group) = c_str_arg(group) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut().clear_group_contexts(group);
+    }
+}
+
+/// Reset all shortcut group settings (every group enabled and unbound again).
+#[no_mangle]
+pub extern "C" fn ime_clear_shortcut_groups() {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut().clear_groups();
     }
 }
 
+/// Set the active context (e.g. frontmost app bundle id) for shortcut groups.
+///
+/// # Arguments
+/// * `context` - C string context id, or null / "" for no context
+///
+/// # Safety
+/// Pointer must be null or a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_active_context(context: *const std::os::raw::c_char) {
+    let context = c_str_arg(context).unwrap_or("");
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.set_active_context(context);
+    }
+}
+
+/// Set local timezone for `{date}`, `{time}` and `{thu}` shortcut templates.
+///
+/// Hosts should call this after `ime_init` with the system offset, and again
+/// when the system timezone changes; until then UTC+7 is assumed.
+///
+/// # Arguments
+/// * `minutes` - Offset from UTC in minutes (e.g., 420 for UTC+7, -300 for UTC-5)
+#[no_mangle]
+pub extern "C" fn ime_shortcut_utc_offset(minutes: i32) {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.shortcuts_mut().templates_mut().set_utc_offset(minutes);
+    }
+}
+
+/// Reset `{counter}` shortcut templates (next expansion starts at 1).
+#[no_mangle]
+pub extern "C" fn ime_reset_shortcut_counters() {
+    let guard = lock_engine();
+    if let Some(ref e) = *guard {
+        e.shortcuts().templates().reset_counters();
+    }
+}
+
+/// Shortcut details for the host (see `ime_shortcut_get`).
+///
+/// Strings are owned by the struct; free the whole struct with `ime_shortcut_free`.
+#[repr(C)]
+pub struct ShortcutInfo {
+    /// Trigger (lowercase), null-terminated UTF-8
+    pub trigger: *mut std::os::raw::c_char,
+    /// Replacement, null-terminated UTF-8
+    pub replacement: *mut std::os::raw::c_char,
+    /// 0=Immediate, 1=OnWordBoundary
+    pub condition: u8,
+    /// 0=Exact, 1=MatchCase, 2=TitleCase, 3=SentenceCase, 4=Smart
+    pub case_mode: u8,
+    /// 0=All, 1=Telex, 2=VNI
+    pub input_method: u8,
+    pub enabled: bool,
+    /// Group name, null-terminated UTF-8, or null if ungrouped
+    pub group: *mut std::os::raw::c_char,
+    /// 0=Composed text, 1=Raw keys
+    pub match_source: u8,
+}
+
+impl ShortcutInfo {
+    fn new(shortcut: &engine::shortcut::Shortcut) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            trigger: into_c_string(shortcut.trigger.clone()),
+            replacement: into_c_string(shortcut.replacement.clone()),
+            condition: shortcut.condition as u8,
+            case_mode: shortcut.case_mode as u8,
+            input_method: shortcut.input_method as u8,
+            enabled: shortcut.enabled,
+            group: match &shortcut.group {
+                Some(group) => into_c_string(group.clone()),
+                None => std::ptr::null_mut(),
+            },
+            match_source: shortcut.match_source as u8,
+        }))
+    }
+}
+
+/// Get number of shortcuts (including disabled ones).
+#[no_mangle]
+pub extern "C" fn ime_shortcut_count() -> u32 {
+    let guard = lock_engine();
+    match *guard {
+        Some(ref e) => e.shortcuts().len() as u32,
+        None => 0,
+    }
+}
+
+/// Get shortcut at index, in trigger order.
+///
+/// Indices are stable until the shortcut table is modified.
+///
+/// # Returns
+/// Pointer to `ShortcutInfo` (free with `ime_shortcut_free`), or null if out of range.
+#[no_mangle]
+pub extern "C" fn ime_shortcut_get(index: u32) -> *mut ShortcutInfo {
+    let guard = lock_engine();
+    match *guard {
+        Some(ref e) => match e.shortcuts().nth(index as usize) {
+            Some(shortcut) => ShortcutInfo::new(shortcut),
+            None => std::ptr::null_mut(),
+        },
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Get shortcut by trigger (case-insensitive, including disabled ones).
+///
+/// # Returns
+/// Pointer to `ShortcutInfo` (free with `ime_shortcut_free`), or null if not found.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_shortcut_lookup(
+    trigger: *const std::os::raw::c_char,
+) -> *mut ShortcutInfo {
+    let Some(trigger) = c_str_arg(trigger) else {
+        return std::ptr::null_mut();
+    };
+    let guard = lock_engine();
+    match *guard {
+        Some(ref e) => match e.shortcuts().get(trigger) {
+            Some(shortcut) => ShortcutInfo::new(shortcut),
+            None => std::ptr::null_mut(),
+        },
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Free a `ShortcutInfo` returned by `ime_shortcut_get` / `ime_shortcut_lookup`.
+///
+/// # Safety
+/// * `info` must be a pointer returned by one of those functions, or null
+/// * Must be called exactly once per non-null pointer
+#[no_mangle]
+pub unsafe extern "C" fn ime_shortcut_free(info: *mut ShortcutInfo) {
+    if info.is_null() {
+        return;
+    }
+    let info = Box::from_raw(info);
+    ime_free_string(info.trigger);
+    ime_free_string(info.replacement);
+    ime_free_string(info.group);
+}
+
+/// Enable or disable a shortcut without removing it.
+///
+/// # Returns
+/// false if no shortcut has this trigger.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_shortcut_enabled(
+    trigger: *const std::os::raw::c_char,
+    enabled: bool,
+) -> bool {
+    let Some(trigger) = c_str_arg(trigger) else {
+        return false;
+    };
+    let mut guard = lock_engine();
+    match *guard {
+        Some(ref mut e) => e.shortcuts_mut().set_enabled(trigger, enabled),
+        None => false,
+    }
+}
+
+/// Set what a shortcut's trigger is compared against.
+///
+/// # Arguments
+/// * `trigger` - C string for trigger
+/// * `source` - 0=Composed text ("đc" fires for "ddc"), 1=Raw keys ("ddc" fires for "ddc")
+///
+/// # Returns
+/// false if no shortcut has this trigger or `source` is invalid.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_set_shortcut_match_source(
+    trigger: *const std::os::raw::c_char,
+    source: u8,
+) -> bool {
+    let Some(trigger) = c_str_arg(trigger) else {
+        return false;
+    };
+    let Some(source) = engine::shortcut::MatchSource::from_u8(source) else {
+        return false;
+    };
+    let mut guard = lock_engine();
+    match *guard {
+        Some(ref mut e) => e.shortcuts_mut().set_match_source(trigger, source),
+        None => false,
+    }
+}
+
+/// Format line errors as "line: message" lines for the host.
+fn format_parse_errors(errors: &[engine::ParseError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}\n", e.line, e.message))
+        .collect()
+}
+
+/// Write an error report to an optional out-pointer (null if no errors).
+///
+/// # Safety
+/// `errors_out` must be null or a valid pointer to writable storage.
+unsafe fn set_errors_out(
+    errors_out: *mut *mut std::os::raw::c_char,
+    errors: &[engine::ParseError],
+) {
+    if errors_out.is_null() {
+        return;
+    }
+    *errors_out = if errors.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        into_c_string(format_parse_errors(errors))
+    };
+}
+
+/// Bulk-load shortcuts from a macro file, adding/overwriting by trigger.
+///
+/// # Arguments
+/// * `text` - File contents as C string
+/// * `format` - 0=Gonhanh (native), 1=Unikey, 2=OpenKey, 3=EVKey
+/// * `errors_out` - Optional out-pointer for a per-line error report
+///   ("line: message" per line); set to null when there are no errors.
+///   Free with `ime_free_string`.
+///
+/// # Returns
+/// Number of imported shortcuts, or -1 if the text pointer or format is invalid.
+///
+/// # Safety
+/// * `text` must be a valid null-terminated UTF-8 string
+/// * `errors_out` must be null or a valid pointer to writable storage
+#[no_mangle]
+pub unsafe extern "C" fn ime_import_shortcuts(
+    text: *const std::os::raw::c_char,
+    format: u8,
+    errors_out: *mut *mut std::os::raw::c_char,
+) -> i64 {
+    set_errors_out(errors_out, &[]);
+    let (Some(text), Some(format)) = (
+        c_str_arg(text),
+        engine::shortcut::MacroFormat::from_u8(format),
+    ) else {
+        return -1;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        let (count, errors) = e.shortcuts_mut().import(text, format);
+        set_errors_out(errors_out, &errors);
+        count as i64
+    } else {
+        0
+    }
+}
+
+/// Save all shortcuts as a macro file.
+///
+/// Shortcuts the format cannot represent (e.g. multi-line replacements in Unikey
+/// format) are skipped and reported through `errors_out`.
+///
+/// # Arguments
+/// * `format` - 0=Gonhanh (native), 1=Unikey, 2=OpenKey, 3=EVKey
+/// * `errors_out` - Optional out-pointer for the error report (see `ime_import_shortcuts`)
+///
+/// # Returns
+/// Newly allocated C string (free with `ime_free_string`), or null if not
+/// initialized or the format is invalid.
+///
+/// # Safety
+/// `errors_out` must be null or a valid pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn ime_export_shortcuts(
+    format: u8,
+    errors_out: *mut *mut std::os::raw::c_char,
+) -> *mut std::os::raw::c_char {
+    set_errors_out(errors_out, &[]);
+    let Some(format) = engine::shortcut::MacroFormat::from_u8(format) else {
+        return std::ptr::null_mut();
+    };
+    let guard = lock_engine();
+    match *guard {
+        Some(ref e) => {
+            let (text, errors) = e.shortcuts().export(format);
+            set_errors_out(errors_out, &errors);
+            into_c_string(text)
+        }
+        None => std::ptr::null_mut(),
+    }
+}
+
+// ============================================================
+// User Dictionary FFI
+// ============================================================
+
+/// Read a C string argument as UTF-8.
+///
+/// # Safety
+/// Pointer must be null or a valid null-terminated string.
+unsafe fn c_str_arg<'a>(s: *const std::os::raw::c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    std::ffi::CStr::from_ptr(s).to_str().ok()
+}
+
+/// Add a word pair for context-aware auto-restore (see `ime_context_restore`).
+///
+/// # Arguments
+/// * `lang` - 0=Vietnamese (typed in the current tone placement), 1=English
+/// * `prev` - C string for the previous word (case-insensitive)
+/// * `next` - C string for the word that follows it
+///
+/// # Safety
+/// Pointers must be valid null-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_bigram(
+    lang: u8,
+    prev: *const std::os::raw::c_char,
+    next: *const std::os::raw::c_char,
+) {
+    let (Some(lang), Some(prev), Some(next)) = (
+        engine::user_dict::Language::from_u8(lang),
+        c_str_arg(prev),
+        c_str_arg(next),
+    ) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.add_bigram(lang, prev, next);
+    }
+}
+
+/// Remove all word pairs added with `ime_add_bigram`.
+#[no_mangle]
+pub extern "C" fn ime_clear_bigrams() {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.bigrams_mut().clear();
+    }
+}
+
+/// Add a word to a user dictionary layer.
+///
+/// User words take precedence over the embedded dictionaries in auto-restore.
+///
+/// # Arguments
+/// * `lang` - 0=Vietnamese, 1=English
+/// * `word` - C string for the word (case-insensitive)
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_user_word(lang: u8, word: *const std::os::raw::c_char) {
+    let (Some(lang), Some(word)) = (engine::user_dict::Language::from_u8(lang), c_str_arg(word))
+    else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.add_user_word(lang, word);
+    }
+}
+
+/// Block a word in a user dictionary layer.
+///
+/// A blocked word is never recognized, even if the embedded dictionary has it.
+///
+/// # Arguments
+/// * `lang` - 0=Vietnamese, 1=English
+/// * `word` - C string for the word (case-insensitive)
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_block_user_word(lang: u8, word: *const std::os::raw::c_char) {
+    let (Some(lang), Some(word)) = (engine::user_dict::Language::from_u8(lang), c_str_arg(word))
+    else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.block_user_word(lang, word);
+    }
+}
+
+/// Remove a word (added or blocked) from a user dictionary layer.
+///
+/// # Arguments
+/// * `lang` - 0=Vietnamese, 1=English
+/// * `word` - C string for the word (case-insensitive)
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_user_word(lang: u8, word: *const std::os::raw::c_char) {
+    let (Some(lang), Some(word)) = (engine::user_dict::Language::from_u8(lang), c_str_arg(word))
+    else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.remove_user_word(lang, word);
+    }
+}
+
+/// Clear both user dictionary layers.
+#[no_mangle]
+pub extern "C" fn ime_clear_user_words() {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.user_dict_mut().clear();
+    }
+}
+
+/// Import user words from text format (`[vi]` / `[en]` sections, `-word` blocks).
+///
+/// Merges into the existing layers. Invalid lines are skipped and reported.
+///
+/// # Arguments
+/// * `text` - Word list as C string
+/// * `errors_out` - Optional out-pointer for a per-line error report
+///   ("line: message" per line); set to null when there are no errors.
+///   Free with `ime_free_string`.
+///
+/// # Returns
+/// Number of imported words, or -1 if the text pointer is invalid.
+///
+/// # Safety
+/// * `text` must be a valid null-terminated UTF-8 string
+/// * `errors_out` must be null or a valid pointer to writable storage
+#[no_mangle]
+pub unsafe extern "C" fn ime_import_user_words(
+    text: *const std::os::raw::c_char,
+    errors_out: *mut *mut std::os::raw::c_char,
+) -> i64 {
+    set_errors_out(errors_out, &[]);
+    let Some(text) = c_str_arg(text) else {
+        return -1;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        let (count, errors) = e.user_dict_mut().import(text);
+        set_errors_out(errors_out, &errors);
+        count as i64
+    } else {
+        0
+    }
+}
+
+/// Export user words in text format.
+///
+/// # Returns
+/// Newly allocated C string (free with `ime_free_string`), or null if not initialized.
+#[no_mangle]
+pub extern "C" fn ime_export_user_words() -> *mut std::os::raw::c_char {
+    let guard = lock_engine();
+    match *guard {
+        Some(ref e) => into_c_string(e.user_dict().export()),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Convert an owned string into a C string for the host (interior NULs are dropped).
+fn into_c_string(s: String) -> *mut std::os::raw::c_char {
+    let bytes: Vec<u8> = s.into_bytes().into_iter().filter(|&b| b != 0).collect();
+    std::ffi::CString::new(bytes)
+        .map(|c| c.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Free a string returned by the engine (e.g. `ime_export_user_words`).
+///
+/// # Safety
+/// * `s` must be a pointer returned by an `ime_*` function documented to need this, or null
+/// * Must be called exactly once per non-null pointer
+#[no_mangle]
+pub unsafe extern "C" fn ime_free_string(s: *mut std::os::raw::c_char) {
+    if !s.is_null() {
+        drop(std::ffi::CString::from_raw(s));
+    }
+}
+
+// ============================================================
+// Word Lists FFI
+// ============================================================
+
+/// Add a raw keystroke sequence that must never get Vietnamese transforms.
+///
+/// # Arguments
+/// * `raw` - C string of raw keys as typed (e.g., "fix", "mass")
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_never_transform(raw: *const std::os::raw::c_char) {
+    let Some(raw) = c_str_arg(raw) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.word_lists_mut().add_never_transform(raw);
+    }
+}
+
+/// Remove a raw keystroke sequence from the never-transform list.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_never_transform(raw: *const std::os::raw::c_char) {
+    let Some(raw) = c_str_arg(raw) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.word_lists_mut().remove_never_transform(raw);
+    }
+}
+
+/// Add a Vietnamese word that must never be auto-restored to English.
+///
+/// # Arguments
+/// * `word` - C string of the composed word (e.g., "tôt")
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_add_never_restore(word: *const std::os::raw::c_char) {
+    let Some(word) = c_str_arg(word) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.word_lists_mut().add_never_restore(word);
+    }
+}
+
+/// Remove a Vietnamese word from the never-restore list.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_remove_never_restore(word: *const std::os::raw::c_char) {
+    let Some(word) = c_str_arg(word) else {
+        return;
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.word_lists_mut().remove_never_restore(word);
+    }
+}
+
+/// Clear both never-transform and never-restore lists.
+#[no_mangle]
+pub extern "C" fn ime_clear_word_lists() {
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.word_lists_mut().clear();
+    }
+}
+
+// ============================================================
+// Word Restore FFI
+// ============================================================
+
+/// Restore buffer from a Vietnamese word string.
+///
+/// Used when native app detects cursor at word boundary and user
+/// wants to continue editing (e.g., backspace into previous word).
+/// Parses Vietnamese characters back to buffer components.
+///
+/// # Arguments
+/// * `word` - C string containing the Vietnamese word to restore
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_restore_word(word: *const std::os::raw::c_char) {
+    if word.is_null() {
+        return;
+    }
+    let word_str = match std::ffi::CStr::from_ptr(word).to_str() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let mut guard = lock_engine();
+    if let Some(ref mut e) = *guard {
+        e.restore_word(word_str);
+    }
+}
+
+// ============================================================
+// Spelling FFI
+// ============================================================
+
+/// Ranked spelling suggestions for a Vietnamese word.
+///
+/// Vietnamese slips (tone, tone position, missing horn/circumflex) rank first,
+/// then confusions (ch/tr, s/x, d/gi/r, n/ng, i/y), then keystroke typos.
+/// Suggestions follow the engine's tone placement (`ime_modern`, `ime_rhyme_tone`);
+/// modern placement is used if the engine is not initialized.
+///
+/// # Arguments
+/// * `word` - C string of the word to correct (e.g., "tiéng")
+/// * `max` - maximum number of suggestions
+///
+/// # Returns
+/// Newly allocated C string with one suggestion per line, best first (free with
+/// `ime_free_string`); empty if none, null if the pointer is invalid.
+///
+/// # Safety
+/// Pointer must be a valid null-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn ime_suggest(
+    word: *const std::os::raw::c_char,
+    max: u32,
+) -> *mut std::os::raw::c_char {
+    let Some(word) = c_str_arg(word) else {
+        return std::ptr::null_mut();
+    };
+    let placement = match *lock_engine() {
+        Some(ref e) => e.tone_placement(),
+        None => data::TonePlacement::default(),
+    };
+    let suggestions = data::vietnamese_spellcheck::suggest(word, placement, max as usize);
+    into_c_string(suggestions.join("\n"))
+}
+
 // ============================================================
 // Tests
 // ============================================================
@@ -502,6 +1531,13 @@ mod tests {
         ime_clear();
     }
 
+    #[test]
+    fn test_result_layout() {
+        // Mirrored by the platform bridges (RustBridge.h, .swift, .cs)
+        assert_eq!(std::mem::size_of::<Result>(), 1032);
+        assert_eq!(std::mem::offset_of!(Result, cursor_left), 1028);
+    }
+
     #[test]
     #[serial]
     fn test_shortcut_ffi_add_and_clear() {
@@ -913,4 +1949,619 @@ mod tests {
         ime_clear_shortcuts();
         ime_clear();
     }
+
+    #[test]
+    #[serial]
+    fn test_user_words_ffi_import_export() {
+        ime_init();
+        ime_clear_user_words();
+
+        let word = CString::new("Vuex").unwrap();
+        let text = CString::new("[vi]\ntôt\n[en]\n-box\n[fr]\n").unwrap();
+        unsafe {
+            ime_add_user_word(1, word.as_ptr());
+            ime_add_user_word(9, word.as_ptr()); // Unknown language: ignored
+            let mut errors: *mut std::os::raw::c_char = std::ptr::null_mut();
+            assert_eq!(ime_import_user_words(text.as_ptr(), &mut errors), 2);
+            assert!(!errors.is_null());
+            let report = std::ffi::CStr::from_ptr(errors)
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert_eq!(report, "5: unknown section [fr]\n");
+            ime_free_string(errors);
+            assert_eq!(
+                ime_import_user_words(std::ptr::null(), std::ptr::null_mut()),
+                -1
+            );
+        }
+
+        let exported = ime_export_user_words();
+        assert!(!exported.is_null());
+        let exported_str = unsafe { std::ffi::CStr::from_ptr(exported) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { ime_free_string(exported) };
+        assert_eq!(exported_str, "[vi]\ntôt\n[en]\nvuex\n-box\n");
+
+        unsafe { ime_remove_user_word(1, word.as_ptr()) };
+        ime_clear_user_words();
+        let exported = ime_export_user_words();
+        assert_eq!(
+            unsafe { std::ffi::CStr::from_ptr(exported) }.to_bytes(),
+            b""
+        );
+        unsafe { ime_free_string(exported) };
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcuts_ffi_import_export() {
+        ime_init();
+        ime_clear_shortcuts();
+
+        let text = CString::new(
+            ";DO NOT DELETE THIS LINE*** version=1 ***\nvn:Việt Nam\nbad line\n->:→\n",
+        )
+        .unwrap();
+        let mut errors: *mut std::os::raw::c_char = std::ptr::null_mut();
+        unsafe {
+            assert_eq!(ime_import_shortcuts(text.as_ptr(), 1, &mut errors), 2);
+            assert!(!errors.is_null());
+            assert_eq!(
+                std::ffi::CStr::from_ptr(errors).to_str().unwrap(),
+                "3: expected trigger:replacement\n"
+            );
+            ime_free_string(errors);
+            assert_eq!(ime_import_shortcuts(text.as_ptr(), 9, &mut errors), -1);
+            assert!(errors.is_null());
+        }
+
+        let exported = unsafe { ime_export_shortcuts(0, std::ptr::null_mut()) };
+        assert!(!exported.is_null());
+        let exported_str = unsafe { std::ffi::CStr::from_ptr(exported) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { ime_free_string(exported) };
+        assert_eq!(
+            exported_str,
+            "# gonhanh shortcuts v2\n->\t→\timmediate\tmatch\tall\t1\nvn\tViệt Nam\tword\tmatch\tall\t1\n"
+        );
+
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_ffi_enumerate() {
+        ime_init();
+        ime_clear_shortcuts();
+        ime_method(0); // Telex
+
+        let trigger1 = CString::new("vn").unwrap();
+        let replacement1 = CString::new("Việt Nam").unwrap();
+        let trigger2 = CString::new("->").unwrap();
+        let replacement2 = CString::new("→").unwrap();
+        unsafe {
+            ime_add_shortcut(trigger1.as_ptr(), replacement1.as_ptr());
+            ime_add_shortcut(trigger2.as_ptr(), replacement2.as_ptr());
+        }
+        assert_eq!(ime_shortcut_count(), 2);
+
+        // Index order is trigger order: "->" before "vn"
+        let info = ime_shortcut_get(0);
+        assert!(!info.is_null());
+        unsafe {
+            assert_eq!(std::ffi::CStr::from_ptr((*info).trigger).to_bytes(), b"->");
+            assert_eq!((*info).condition, 0); // Immediate
+            ime_shortcut_free(info);
+        }
+        assert!(ime_shortcut_get(2).is_null());
+
+        let upper = CString::new("VN").unwrap();
+        unsafe {
+            assert!(ime_set_shortcut_enabled(upper.as_ptr(), false));
+            let info = ime_shortcut_lookup(upper.as_ptr());
+            assert!(!info.is_null());
+            assert_eq!(
+                std::ffi::CStr::from_ptr((*info).replacement)
+                    .to_str()
+                    .unwrap(),
+                "Việt Nam"
+            );
+            assert_eq!((*info).condition, 1); // OnWordBoundary
+            assert_eq!((*info).case_mode, 1); // MatchCase
+            assert_eq!((*info).input_method, 0); // All
+            assert!(!(*info).enabled);
+            ime_shortcut_free(info);
+
+            let missing = CString::new("hn").unwrap();
+            assert!(ime_shortcut_lookup(missing.as_ptr()).is_null());
+            assert!(!ime_set_shortcut_enabled(missing.as_ptr(), true));
+
+            assert!(ime_set_shortcut_match_source(upper.as_ptr(), 1));
+            assert!(!ime_set_shortcut_match_source(upper.as_ptr(), 2));
+            let info = ime_shortcut_lookup(upper.as_ptr());
+            assert_eq!((*info).match_source, 1); // Raw keys
+            ime_shortcut_free(info);
+        }
+
+        // Disabled shortcut stays in the table but no longer expands
+        assert_eq!(ime_shortcut_count(), 2);
+        unsafe {
+            ime_free(ime_key(keys::V, false, false));
+            ime_free(ime_key(keys::N, false, false));
+            let r = ime_key(keys::SPACE, false, false);
+            assert_eq!((*r).action, 0);
+            ime_free(r);
+        }
+
+        ime_clear_shortcuts();
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_long_shortcut_ffi_chunks() {
+        ime_init();
+        ime_clear_shortcuts();
+        ime_method(0); // Telex
+        ime_long_output(true);
+
+        let reply = "Cảm ơn anh/chị đã liên hệ.\n\tChúng tôi sẽ phản hồi sớm.\n".repeat(12);
+        let trigger = CString::new("cm").unwrap();
+        let replacement = CString::new(reply.clone()).unwrap();
+        unsafe { ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr()) };
+
+        unsafe {
+            ime_free(ime_key(keys::C, false, false));
+            ime_free(ime_key(keys::M, false, false));
+        }
+        let mut r = ime_key(keys::SPACE, false, false);
+        let mut output = String::new();
+        let mut chunks = 0;
+        while !r.is_null() {
+            let result = unsafe { &*r };
+            output.extend(
+                result.chars[..result.count as usize]
+                    .iter()
+                    .filter_map(|&c| char::from_u32(c)),
+            );
+            chunks += 1;
+            let more = result.flags & engine::FLAG_MORE_OUTPUT != 0;
+            unsafe { ime_free(r) };
+            r = if more {
+                ime_next_chunk()
+            } else {
+                std::ptr::null_mut()
+            };
+        }
+        assert!(chunks > 1);
+        assert_eq!(output, format!("{} ", reply));
+        assert!(ime_next_chunk().is_null());
+
+        ime_clear_shortcuts();
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_never_transform_ffi() {
+        ime_init();
+        ime_method(0); // Telex
+        ime_clear_word_lists();
+
+        let raw = CString::new("mix").unwrap();
+        unsafe { ime_add_never_transform(raw.as_ptr()) };
+
+        // m, i, x → 'x' must not apply ngã
+        for key in [keys::M, keys::I] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key(keys::X, false, false);
+        unsafe {
+            assert_eq!((*r).action, 0, "x should pass through as a letter");
+            ime_free(r);
+        }
+
+        unsafe { ime_remove_never_transform(raw.as_ptr()) };
+        ime_clear_word_lists();
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_groups_ffi() {
+        ime_init();
+        ime_clear_shortcuts();
+        ime_method(0); // Telex
+
+        let trigger = CString::new("fnc").unwrap();
+        let replacement = CString::new("function").unwrap();
+        let group = CString::new("code").unwrap();
+        let vscode = CString::new("com.microsoft.VSCode").unwrap();
+        unsafe {
+            ime_add_shortcut_to_group(trigger.as_ptr(), replacement.as_ptr(), group.as_ptr());
+            ime_shortcut_group_bind_context(group.as_ptr(), vscode.as_ptr());
+
+            let info = ime_shortcut_lookup(trigger.as_ptr());
+            assert_eq!(std::ffi::CStr::from_ptr((*info).group).to_bytes(), b"code");
+            ime_shortcut_free(info);
+        }
+
+        let type_fnc = || {
+            for key in [keys::F, keys::N, keys::C] {
+                let r = ime_key(key, false, false);
+                unsafe { ime_free(r) };
+            }
+            let r = ime_key(keys::SPACE, false, false);
+            let backspace = unsafe { (*r).backspace };
+            unsafe { ime_free(r) };
+            ime_clear();
+            backspace
+        };
+
+        // Not in the bound context: no expansion
+        assert_eq!(type_fnc(), 0);
+        unsafe { ime_set_active_context(vscode.as_ptr()) };
+        assert_eq!(type_fnc(), 3);
+        unsafe { ime_shortcut_group_enabled(group.as_ptr(), false) };
+        assert_eq!(type_fnc(), 0);
+        unsafe {
+            ime_shortcut_group_enabled(group.as_ptr(), true);
+            ime_set_active_context(std::ptr::null());
+            ime_shortcut_group_clear_contexts(group.as_ptr());
+        }
+        assert_eq!(type_fnc(), 3);
+
+        // Group settings survive a shortcut reload
+        unsafe {
+            ime_shortcut_group_enabled(group.as_ptr(), false);
+            ime_clear_shortcuts();
+            ime_add_shortcut_to_group(trigger.as_ptr(), replacement.as_ptr(), group.as_ptr());
+        }
+        assert_eq!(type_fnc(), 0);
+        ime_clear_shortcut_groups();
+        assert_eq!(type_fnc(), 3);
+
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_utc_offset_ffi() {
+        /// 2024-03-04 09:05:07 UTC
+        fn fixed_clock() -> i64 {
+            1_709_543_107
+        }
+
+        ime_init();
+        ime_clear_shortcuts();
+        ime_method(0); // Telex
+        if let Some(ref mut e) = *lock_engine() {
+            e.shortcuts_mut().templates_mut().set_clock(fixed_clock);
+        }
+        let trigger = CString::new("tg").unwrap();
+        let replacement = CString::new("{time} {date}").unwrap();
+        unsafe { ime_add_shortcut(trigger.as_ptr(), replacement.as_ptr()) };
+
+        let type_tg = || {
+            for key in [keys::T, keys::G] {
+                unsafe { ime_free(ime_key(key, false, false)) };
+            }
+            let r = ime_key(keys::SPACE, false, false);
+            let result = unsafe { &*r };
+            let text: String = result.chars[..result.count as usize]
+                .iter()
+                .filter_map(|&c| char::from_u32(c))
+                .collect();
+            unsafe { ime_free(r) };
+            ime_clear();
+            text
+        };
+
+        assert_eq!(type_tg(), "16:05 04/03/2024 ");
+        ime_shortcut_utc_offset(-5 * 60); // UTC-5
+        assert_eq!(type_tg(), "04:05 04/03/2024 ");
+        ime_shortcut_utc_offset(-10 * 60); // UTC-10: previous day
+        assert_eq!(type_tg(), "23:05 03/03/2024 ");
+
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shortcut_with_case_ffi() {
+        ime_init();
+        ime_clear_shortcuts();
+        ime_method(0); // Telex
+
+        let trigger = CString::new("tp").unwrap();
+        let replacement = CString::new("thành phố").unwrap();
+        unsafe {
+            ime_add_shortcut_with_case(trigger.as_ptr(), replacement.as_ptr(), 9);
+            assert!(ime_shortcut_lookup(trigger.as_ptr()).is_null());
+
+            ime_add_shortcut_with_case(trigger.as_ptr(), replacement.as_ptr(), 2);
+            let info = ime_shortcut_lookup(trigger.as_ptr());
+            assert_eq!((*info).case_mode, 2); // TitleCase
+            ime_shortcut_free(info);
+        }
+
+        // "Tp" → "Thành Phố"
+        let r = ime_key(keys::T, true, false);
+        unsafe { ime_free(r) };
+        let r = ime_key(keys::P, false, false);
+        unsafe { ime_free(r) };
+        let r = ime_key(keys::SPACE, false, false);
+        let result = unsafe { &*r };
+        let text: String = (0..result.count as usize)
+            .filter_map(|i| char::from_u32(result.chars[i]))
+            .collect();
+        unsafe { ime_free(r) };
+        assert_eq!(text, "Thành Phố ");
+
+        ime_clear();
+        ime_clear_shortcuts();
+    }
+
+    #[test]
+    #[serial]
+    fn test_suggest_ffi() {
+        let suggest = |word: &str| {
+            let word = CString::new(word).unwrap();
+            let r = unsafe { ime_suggest(word.as_ptr(), 2) };
+            assert!(!r.is_null());
+            let text = unsafe { std::ffi::CStr::from_ptr(r) }
+                .to_str()
+                .unwrap()
+                .to_string();
+            unsafe { ime_free_string(r) };
+            text
+        };
+
+        ime_init();
+        ime_modern(false);
+        let text = suggest("tiéng");
+        assert_eq!(text.lines().count(), 2);
+        assert_eq!(text.lines().next(), Some("tiếng"));
+        assert_eq!(suggest("hoà").lines().next(), Some("hòa"));
+
+        // Per-rhyme placement: modern oa, traditional uy
+        ime_modern(true);
+        ime_rhyme_tone(2, false);
+        assert_eq!(suggest("hòa").lines().next(), Some("hoà"));
+        assert_eq!(suggest("thuý").lines().next(), Some("thúy"));
+
+        assert!(unsafe { ime_suggest(std::ptr::null(), 2) }.is_null());
+        ime_modern(true);
+    }
+
+    #[test]
+    #[serial]
+    fn test_strict_spelling_ffi() {
+        ime_init();
+        ime_clear();
+        ime_method(0); // Telex
+        ime_strict_spelling(2);
+        ime_strict_spelling(9); // Invalid: ignored
+
+        // "tiéng" + Space → kept, flagged
+        for key in [keys::T, keys::I, keys::E, keys::N, keys::G, keys::S] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key(keys::SPACE, false, false);
+        let result = unsafe { &*r };
+        assert_eq!(result.action, engine::Action::None as u8);
+        assert_eq!(
+            result.flags & engine::FLAG_MISSPELLED,
+            engine::FLAG_MISSPELLED
+        );
+        unsafe { ime_free(r) };
+
+        ime_strict_spelling(0);
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_rhyme_tone_ffi() {
+        ime_init();
+        ime_clear();
+        ime_method(0); // Telex
+        ime_modern(true);
+        ime_rhyme_tone(2, false); // Traditional uy
+        ime_rhyme_tone(7, false); // Invalid: ignored
+
+        // "thuys" → "thúy"
+        let mut screen = String::new();
+        for key in [keys::T, keys::H, keys::U, keys::Y, keys::S] {
+            let r = ime_key(key, false, false);
+            let result = unsafe { &*r };
+            if result.action == engine::Action::Send as u8 {
+                for _ in 0..result.backspace {
+                    screen.pop();
+                }
+                screen.extend(
+                    result.chars[..result.count as usize]
+                        .iter()
+                        .filter_map(|&c| char::from_u32(c)),
+                );
+            } else {
+                screen.push(utils::key_to_char(key, false).unwrap());
+            }
+            unsafe { ime_free(r) };
+        }
+        assert_eq!(screen, "thúy");
+
+        ime_modern(true);
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_proper_nouns_ffi() {
+        ime_init();
+        ime_clear_proper_nouns();
+
+        let noun = CString::new("Hà Nội").unwrap();
+        let too_long = CString::new("a b c d e").unwrap();
+        unsafe {
+            assert!(ime_add_proper_noun(noun.as_ptr()));
+            assert!(!ime_add_proper_noun(too_long.as_ptr()));
+            assert!(!ime_add_proper_noun(std::ptr::null()));
+        }
+        let count = |g: &std::sync::MutexGuard<Option<Engine>>| {
+            g.as_ref().map_or(0, |e| e.proper_nouns().len())
+        };
+        assert_eq!(count(&lock_engine()), 1);
+
+        let lower = CString::new("hà nội").unwrap();
+        unsafe { ime_remove_proper_noun(lower.as_ptr()) };
+        assert_eq!(count(&lock_engine()), 0);
+        ime_clear_proper_nouns();
+    }
+
+    #[test]
+    #[serial]
+    fn test_typography_ffi() {
+        ime_init();
+        ime_clear();
+        ime_typography(engine::typography::Typography::SMART_QUOTES);
+
+        // '"' at line start → opening quote, key consumed
+        let r = ime_key_ext(keys::QUOTE, false, false, true);
+        let result = unsafe { &*r };
+        assert_eq!(result.action, engine::Action::Send as u8);
+        assert_ne!(result.flags & engine::FLAG_KEY_CONSUMED, 0);
+        assert_eq!(result.chars[0], '“' as u32);
+        unsafe { ime_free(r) };
+
+        ime_typography(0);
+        ime_clear_all();
+    }
+
+    #[test]
+    #[serial]
+    fn test_token_detection_ffi() {
+        ime_init();
+        ime_clear();
+        ime_method(0); // Telex
+
+        // "my_a" is an identifier: the second 'a' stays a letter
+        let last_action = || {
+            for (key, shift) in [(keys::M, false), (keys::Y, false), (keys::MINUS, true)] {
+                unsafe { ime_free(ime_key_ext(key, false, false, shift)) };
+            }
+            unsafe { ime_free(ime_key(keys::A, false, false)) };
+            let r = ime_key(keys::A, false, false);
+            let action = unsafe { (*r).action };
+            unsafe { ime_free(r) };
+            ime_clear_all();
+            action
+        };
+        assert_eq!(last_action(), engine::Action::None as u8);
+
+        ime_token_detection(false);
+        assert_eq!(last_action(), engine::Action::Send as u8);
+
+        ime_token_detection(true);
+        ime_clear_all();
+    }
+
+    #[test]
+    #[serial]
+    fn test_word_escape_ffi() {
+        ime_init();
+        ime_clear_all();
+        ime_word_escape(1, keys::BACKQUOTE, 1); // English prefix
+        ime_learn_forced_words(true);
+
+        // Prefix is consumed without output
+        let r = ime_key(keys::BACKQUOTE, false, false);
+        let result = unsafe { &*r };
+        assert_eq!(result.action, engine::Action::Send as u8);
+        assert_eq!(result.count, 0);
+        assert_ne!(result.flags & engine::FLAG_KEY_CONSUMED, 0);
+        unsafe { ime_free(r) };
+
+        for key in [keys::M, keys::I, keys::X, keys::SPACE] {
+            unsafe { ime_free(ime_key(key, false, false)) };
+        }
+        let learned = lock_engine()
+            .as_ref()
+            .is_some_and(|e| e.word_lists().is_never_transform("mix"));
+        assert!(learned);
+
+        ime_word_escape(1, keys::BACKQUOTE, 0);
+        ime_learn_forced_words(false);
+        if let Some(ref mut e) = *lock_engine() {
+            e.word_lists_mut().clear();
+        }
+        ime_clear_all();
+    }
+
+    #[test]
+    #[serial]
+    fn test_autocorrect_ffi() {
+        ime_init();
+        ime_clear();
+        ime_method(0); // Telex
+        ime_autocorrect(true);
+
+        // "tiéng" + Space → "tiếng "
+        for key in [keys::T, keys::I, keys::E, keys::N, keys::G, keys::S] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key(keys::SPACE, false, false);
+        let result = unsafe { &*r };
+        assert_eq!(result.action, engine::Action::Send as u8);
+        assert_eq!(result.backspace, 5);
+        let text: String = result.chars[..result.count as usize]
+            .iter()
+            .filter_map(|&c| char::from_u32(c))
+            .collect();
+        assert_eq!(text, "tiếng ");
+        unsafe { ime_free(r) };
+
+        ime_autocorrect(false);
+        ime_clear();
+    }
+
+    #[test]
+    #[serial]
+    fn test_undo_transform_ffi() {
+        ime_init();
+        ime_clear();
+        ime_method(0); // Telex
+        ime_undo_key(keys::ESC, true);
+
+        // "dd" → "đ", "ESC" → "d"
+        for key in [keys::D, keys::D] {
+            let r = ime_key(key, false, false);
+            unsafe { ime_free(r) };
+        }
+        let r = ime_key(keys::ESC, false, false);
+        unsafe {
+            assert_eq!((*r).action, engine::Action::Send as u8);
+            assert_eq!((*r).backspace, 1);
+            assert_eq!((*r).chars[0], 'd' as u32);
+            ime_free(r);
+        }
+
+        // Nothing left to undo
+        let r = ime_undo_transform();
+        unsafe {
+            assert_eq!((*r).action, engine::Action::None as u8);
+            ime_free(r);
+        }
+
+        ime_undo_key(keys::ESC, false);
+        ime_clear();
+    }
 }
@@ -111,6 +111,82 @@ fn enter_basic() {
     assert_eq!(first_char, 'D', "Expected 'D' but got '{}'", first_char);
 }
 
+#[test]
+fn enter_capitalize_can_be_disabled() {
+    let mut e = Engine::new();
+    e.set_auto_capitalize(true);
+    e.set_capitalize_new_line(false);
+    for &key in &[keys::X, keys::I, keys::N] {
+        e.on_key_ext(key, false, false, false);
+    }
+    e.on_key_ext(keys::RETURN, false, false, false);
+    assert_eq!(type_word(&mut e, "di"), "di");
+
+    // Sentence punctuation still capitalizes
+    assert_eq!(type_word(&mut e, ". di"), ". Di");
+}
+
+// ============================================================
+// PROPER NOUNS
+// ============================================================
+
+fn proper_noun_engine() -> Engine {
+    let mut e = Engine::new();
+    e.set_auto_capitalize(true);
+    e.proper_nouns_mut().add("Việt Nam");
+    e.proper_nouns_mut().add("Hà Nội");
+    e.proper_nouns_mut().add("Huế");
+    e
+}
+
+#[test]
+fn proper_noun_capitalized_mid_sentence() {
+    let mut e = proper_noun_engine();
+    assert_eq!(
+        type_word(&mut e, "toi yeeu vieetj nam "),
+        "toi yêu Việt Nam "
+    );
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "ddi huees, "), "đi Huế, ");
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "owr haf nooij."), "ở Hà Nội.");
+}
+
+#[test]
+fn proper_noun_needs_single_spaces() {
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "haf  nooij "), "hà  nội ");
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "haf, nooij "), "hà, nội ");
+}
+
+#[test]
+fn proper_noun_backspace_restores_capitalized_word() {
+    let mut e = proper_noun_engine();
+    // Word history keeps the capital: editing "Nam" again stays capitalized
+    assert_eq!(type_word(&mut e, "vieetj nam <s"), "Việt Nám");
+}
+
+#[test]
+fn proper_noun_keeps_typed_capitals() {
+    // Capitals are only added: all-caps stays all-caps
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "HAF NOOIJ "), "HÀ NỘI ");
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "VIEETJ NAM, "), "VIỆT NAM, ");
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "vieetj NAM "), "việt NAM ");
+    let mut e = proper_noun_engine();
+    assert_eq!(type_word(&mut e, "haf NOOIJ "), "hà NỘI ");
+}
+
+#[test]
+fn proper_noun_requires_auto_capitalize() {
+    let mut e = proper_noun_engine();
+    e.set_auto_capitalize(false);
+    assert_eq!(type_word(&mut e, "vieetj nam "), "việt nam ");
+}
+
 // ============================================================
 // NUMBER AFTER DOT (NO CAPITALIZE)
 // ============================================================
@@ -232,11 +308,18 @@ fn no_space_no_capitalize() {
 }
 
 #[test]
-fn abbreviations_known_tradeoff() {
+fn abbreviations_no_capitalize() {
     // Issue #185: Abbreviations like "v.v." should NOT auto-capitalize
-    // Previously this was a known trade-off, but now fixed
+    // Known Vietnamese abbreviations don't end the sentence
     telex_auto_capitalize(&[
-        ("v.v. tieeps", "v.v. Tiếp"), // Fixed: no capitalize without space
+        ("v.v. tieeps", "v.v. tiếp"),
+        ("TP. hoof", "TP. hồ"),
+        ("TS. nam", "TS. nam"),
+        ("PGS. nam", "PGS. nam"),
+        ("(TP. hoof", "(TP. hồ"),
+        // Not an abbreviation: still a sentence end
+        ("vv.v. nam", "vv.v. Nam"),
+        ("ddi. nam", "đi. Nam"),
     ]);
 }
 
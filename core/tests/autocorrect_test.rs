@@ -0,0 +1,68 @@
+//! Autocorrect tests - invalid committed words replaced by the nearest valid word
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine() -> Engine {
+    let mut e = Engine::new();
+    e.set_autocorrect(true);
+    e
+}
+
+#[test]
+fn corrects_missing_circumflex_on_space() {
+    let mut e = engine();
+    // "tiéng" is not a word: the only valid neighbour is "tiếng"
+    assert_eq!(type_word(&mut e, "tiengs "), "tiếng ");
+}
+
+#[test]
+fn keeps_case() {
+    let mut e = engine();
+    assert_eq!(type_word(&mut e, "Tiengs "), "Tiếng ");
+}
+
+#[test]
+fn valid_and_plain_words_untouched() {
+    let mut e = engine();
+    assert_eq!(type_word(&mut e, "toans "), "toán ");
+    // No diacritics: not typed as Vietnamese, never corrected
+    assert_eq!(type_word(&mut e, "hcao "), "hcao ");
+}
+
+#[test]
+fn backspace_restores_word_as_typed() {
+    let mut e = engine();
+    assert_eq!(type_word(&mut e, "tiengs <"), "tiéng ");
+}
+
+#[test]
+fn disabled_by_default() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "tiengs "), "tiéng ");
+}
+
+#[test]
+fn corrects_on_punctuation() {
+    let mut e = engine();
+    assert_eq!(type_word(&mut e, "tiengs, "), "tiếng, ");
+    // The comma is typed by the platform after the correction
+    let mut e = engine();
+    assert_eq!(type_word(&mut e, "tiengs,<"), "tiéng,");
+}
+
+#[test]
+fn corrects_on_enter() {
+    let mut e = engine();
+    type_word(&mut e, "tiengs");
+    let r = e.on_key(keys::RETURN, false, false);
+    assert_eq!(r.backspace, 5);
+    let output: String = r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    // Enter itself passes through
+    assert_eq!(output, "tiếng");
+    assert!(!r.key_consumed());
+}
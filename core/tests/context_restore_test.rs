@@ -0,0 +1,75 @@
+//! Bigram context auto-restore tests - previous committed word biases the decision
+
+use gonhanh_core::engine::user_dict::Language;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine_context() -> Engine {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e.set_context_restore(true);
+    e
+}
+
+#[test]
+fn context_off_keeps_per_word_decision() {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "the box "), "the bõ ");
+}
+
+#[test]
+fn english_pair_restores() {
+    let mut e = engine_context();
+    e.add_bigram(Language::English, "the", "box");
+    assert_eq!(type_word(&mut e, "the box "), "the box ");
+    let mut e = engine_context();
+    e.add_bigram(Language::English, "it", "is");
+    assert_eq!(type_word(&mut e, "it is "), "it is ");
+}
+
+#[test]
+fn no_pairs_built_in() {
+    // "the" is in both dictionaries: without a pair, no context
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "the box "), "the bõ ");
+}
+
+#[test]
+fn vietnamese_pair_keeps_word() {
+    // Overrides the lean towards the previous English word
+    let mut e = engine_context();
+    e.add_bigram(Language::Vietnamese, "cool", "mĩ");
+    assert_eq!(type_word(&mut e, "cool mix "), "cool mĩ ");
+}
+
+#[test]
+fn after_english_word_leans_english() {
+    // "mix" is not in the pair table, but "cool" is English-only
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "cool mix "), "cool mix ");
+}
+
+#[test]
+fn after_vietnamese_word_leans_vietnamese() {
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "chawngr box "), "chẳng bõ ");
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "nuwowcs mix "), "nước mĩ ");
+}
+
+#[test]
+fn no_context_at_start() {
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "box "), "bõ ");
+    // Punctuation clears the word history
+    let mut e = engine_context();
+    assert_eq!(type_word(&mut e, "the. box "), "the. bõ ");
+}
+
+#[test]
+fn context_requires_auto_restore() {
+    let mut e = Engine::new();
+    e.set_context_restore(true);
+    assert_eq!(type_word(&mut e, "the box "), "the bõ ");
+}
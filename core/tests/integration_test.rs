@@ -900,6 +900,208 @@ fn shortcut_no_partial_match() {
     assert_eq!(result, "vn ", "partial match should not trigger shortcut");
 }
 
+#[test]
+fn shortcut_cursor_marker_reports_cursor_left() {
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("fn", "fn $|() {}"));
+
+    for key in [keys::F, keys::N] {
+        e.on_key(key, false, false);
+    }
+    let r = e.on_key(keys::SPACE, false, false);
+    let output: String = r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect();
+    assert_eq!(output, "fn () {} ");
+    // Caret goes back before "() {} " (6 chars incl. the trailing space)
+    assert_eq!(r.cursor_left, 6);
+}
+
+#[test]
+fn shortcut_long_expansion_is_chunked() {
+    use gonhanh_core::engine::shortcut::MAX_REPLACEMENT_LEN;
+
+    let mut e = Engine::new();
+    e.set_long_output(true);
+    let body = "Nội dung dài.\n".repeat(40);
+    e.shortcuts_mut()
+        .add(Shortcut::new("mail", "").with_long_replacement(&format!("Chào $|,\n{}", body)));
+
+    for key in [keys::M, keys::A, keys::I, keys::L] {
+        e.on_key(key, false, false);
+    }
+    let first = e.on_key(keys::SPACE, false, false);
+    assert_eq!(first.backspace, 4);
+    assert_eq!(first.count as usize, MAX_REPLACEMENT_LEN);
+    assert!(first.has_more_output());
+    assert_eq!(first.cursor_left, 0);
+
+    let mut last = e.next_output_chunk().unwrap();
+    while last.has_more_output() {
+        assert_eq!(last.backspace, 0);
+        last = e.next_output_chunk().unwrap();
+    }
+    assert!(!e.has_pending_output());
+    // Caret offset comes with the last chunk: back over ",\n" + body + " "
+    assert_eq!(last.cursor_left as usize, 2 + body.chars().count() + 1);
+
+    // Unfetched chunks are dropped on the next key
+    for key in [keys::M, keys::A, keys::I, keys::L, keys::SPACE] {
+        e.on_key(key, false, false);
+    }
+    assert!(e.has_pending_output());
+    e.on_key(keys::A, false, false);
+    assert!(!e.has_pending_output());
+}
+
+#[test]
+fn shortcut_long_expansion_cut_without_opt_in() {
+    use gonhanh_core::engine::shortcut::MAX_REPLACEMENT_LEN;
+
+    let mut e = Engine::new();
+    let body = "Nội dung dài.\n".repeat(40);
+    e.shortcuts_mut()
+        .add(Shortcut::new("mail", "").with_long_replacement(&body));
+
+    for key in [keys::M, keys::A, keys::I, keys::L] {
+        e.on_key(key, false, false);
+    }
+    let r = e.on_key(keys::SPACE, false, false);
+    assert_eq!(r.count as usize, MAX_REPLACEMENT_LEN);
+    assert!(!r.has_more_output());
+    assert!(e.next_output_chunk().is_none());
+}
+
+/// Collect a result's chars as a string
+fn result_text(r: &gonhanh_core::engine::Result) -> String {
+    r.chars[..r.count as usize]
+        .iter()
+        .filter_map(|&c| char::from_u32(c))
+        .collect()
+}
+
+#[test]
+fn shortcut_undo_word_boundary_with_backspace() {
+    let mut e = Engine::new();
+    e.set_shortcut_undo(true);
+    e.shortcuts_mut().add(Shortcut::new("vn", "Việt Nam"));
+
+    assert_eq!(type_word(&mut e, "vn "), "Việt Nam ");
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_eq!(r.action, Action::Send as u8);
+    assert_eq!(r.backspace, 9, "delete 'Việt Nam '");
+    assert_eq!(result_text(&r), "vn ");
+
+    // Second Backspace is a normal one
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_eq!(r.action, Action::None as u8);
+}
+
+#[test]
+fn shortcut_undo_punctuation_boundary() {
+    let mut e = Engine::new();
+    e.set_shortcut_undo(true);
+    e.shortcuts_mut().add(Shortcut::new("ko", "không"));
+
+    for key in [keys::K, keys::O] {
+        e.on_key(key, false, false);
+    }
+    let r = e.on_key(keys::COMMA, false, false);
+    assert_eq!(result_text(&r), "không");
+    // The comma is typed by the platform after the replacement
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_eq!(r.backspace, 6);
+    assert_eq!(result_text(&r), "ko,");
+}
+
+#[test]
+fn shortcut_undo_immediate() {
+    let mut e = Engine::new();
+    e.set_shortcut_undo(true);
+    e.shortcuts_mut().add(Shortcut::immediate("->", "→"));
+
+    e.on_key_ext(keys::MINUS, false, false, false);
+    let r = e.on_key_ext(keys::DOT, false, false, true); // Shift+. = >
+    assert_eq!(result_text(&r), "→");
+    assert!(r.key_consumed());
+
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_eq!(r.backspace, 1);
+    assert_eq!(result_text(&r), "->");
+}
+
+#[test]
+fn shortcut_undo_only_immediately_after() {
+    let mut e = Engine::new();
+    e.set_shortcut_undo(true);
+    e.shortcuts_mut().add(Shortcut::new("vn", "Việt Nam"));
+
+    assert_eq!(type_word(&mut e, "vn a"), "Việt Nam a");
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_eq!(r.action, Action::None as u8);
+
+    // Disabled (the default): Backspace after expansion is a normal Backspace
+    e.clear_all();
+    e.set_shortcut_undo(false);
+    assert_eq!(type_word(&mut e, "vn "), "Việt Nam ");
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_ne!(result_text(&r), "vn ");
+
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("vn", "Việt Nam"));
+    assert_eq!(type_word(&mut e, "vn "), "Việt Nam ");
+    let r = e.on_key(keys::DELETE, false, false);
+    assert_ne!(result_text(&r), "vn ");
+}
+
+#[test]
+fn shortcut_template_date_and_counter() {
+    fn fixed_clock() -> i64 {
+        1_709_543_107 // 2024-03-04 16:05 UTC+7, Monday
+    }
+    let mut e = Engine::new();
+    e.shortcuts_mut().templates_mut().set_clock(fixed_clock);
+    e.shortcuts_mut()
+        .add(Shortcut::new("ngay", "{thu}, ngày {date:dd/MM/yyyy}"));
+    e.shortcuts_mut().add(Shortcut::new("stt", "{counter}."));
+
+    let result = type_word(&mut e, "ngay ");
+    assert_eq!(result, "Thứ Hai, ngày 04/03/2024 ");
+
+    e.clear();
+    assert_eq!(type_word(&mut e, "stt "), "1. ");
+    e.clear();
+    assert_eq!(type_word(&mut e, "stt "), "2. ");
+}
+
+#[test]
+fn shortcut_composed_trigger_matches_any_key_sequence() {
+    let mut e = Engine::new();
+    e.shortcuts_mut().add(Shortcut::new("đc", "được"));
+
+    assert_eq!(type_word(&mut e, "ddc "), "được ");
+    e.clear();
+    e.set_method(1); // VNI
+    assert_eq!(type_word(&mut e, "d9c "), "được ");
+}
+
+#[test]
+fn shortcut_raw_key_trigger() {
+    let mut e = Engine::new();
+    e.shortcuts_mut()
+        .add(Shortcut::new("ddc", "được").on_raw_keys());
+    // Composed shortcut with the same keys never fires: "ddc" shows as "đc"
+    e.shortcuts_mut().add(Shortcut::new("ddk", "đã khám"));
+
+    assert_eq!(type_word(&mut e, "ddc "), "được ");
+    e.clear();
+    assert_eq!(type_word(&mut e, "ddk "), "đk ");
+    e.clear();
+    // Case follows the raw keys
+    assert_eq!(type_word(&mut e, "DDC "), "ĐƯỢC ");
+}
+
 #[test]
 fn shortcut_removed_does_not_trigger() {
     let mut e = Engine::new();
@@ -2478,6 +2680,7 @@ fn restore_word_then_extend() {
 /// After backspacing into "shortcuts", typing "Nuw" should produce:
 /// - Internal buffer: "Nư" (buffer cleared on consonant 'N', then fresh typing)
 /// - Screen: "shortcutsNư" (screen keeps restored word, adds transformed output)
+///
 /// The key fix: "uw" → "ư" transformation now works after restore
 #[test]
 fn restore_word_non_vietnamese_then_type_new() {
@@ -0,0 +1,100 @@
+//! Line editing tests - Left/Right/Backspace re-enter committed words
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::{Action, Engine};
+use gonhanh_core::utils::char_to_key;
+
+/// Minimal editor: applies results at the caret like a host app
+///
+/// Input: letters, ' ', '<' (Backspace), '←' / '→' (arrows)
+fn edit(e: &mut Engine, input: &str) -> (String, usize) {
+    let mut text: Vec<char> = Vec::new();
+    let mut cursor = 0;
+    for c in input.chars() {
+        let key = match c {
+            '←' => keys::LEFT,
+            '→' => keys::RIGHT,
+            _ => char_to_key(c),
+        };
+        let r = e.on_key_ext(key, c.is_uppercase(), false, false);
+        if r.action == Action::Send as u8 {
+            for _ in 0..r.backspace {
+                cursor -= 1;
+                text.remove(cursor);
+            }
+            for i in 0..r.count as usize {
+                text.insert(cursor, char::from_u32(r.chars[i]).unwrap());
+                cursor += 1;
+            }
+        }
+        match key {
+            keys::LEFT => cursor -= 1,
+            keys::RIGHT => cursor += 1,
+            keys::DELETE if r.action != Action::Send as u8 => {
+                cursor -= 1;
+                text.remove(cursor);
+            }
+            keys::DELETE => {}
+            _ if r.action != Action::Send as u8 => {
+                text.insert(cursor, c);
+                cursor += 1;
+            }
+            _ => {}
+        }
+    }
+    (text.into_iter().collect(), cursor)
+}
+
+fn engine() -> Engine {
+    let mut e = Engine::new();
+    e.set_line_editing(true);
+    e
+}
+
+#[test]
+fn left_arrow_reenters_previous_word() {
+    let mut e = engine();
+    // Caret back after "viet", add the nặng mark and circumflex
+    let (text, cursor) = edit(&mut e, "viet nam ←←←←←ej");
+    assert_eq!(text, "việt nam ");
+    assert_eq!(cursor, 4);
+    assert_eq!(e.line().text(), text);
+}
+
+#[test]
+fn right_arrow_reenters_word() {
+    let mut e = engine();
+    let (text, _) = edit(&mut e, "toi di ←←←←←→s");
+    assert_eq!(text, "tói di ");
+}
+
+#[test]
+fn caret_inside_word_does_not_reenter() {
+    let mut e = engine();
+    let (text, _) = edit(&mut e, "nam ←←←s");
+    assert_eq!(text, "nsam ");
+}
+
+#[test]
+fn backspace_reenters_word_after_punctuation() {
+    let mut e = engine();
+    let (text, _) = edit(&mut e, "chao, ban<<<<<f");
+    assert_eq!(text, "chào");
+}
+
+#[test]
+fn disabled_by_default() {
+    let mut e = Engine::new();
+    let (text, _) = edit(&mut e, "viet nam ←←←←←j");
+    assert_eq!(text, "vietj nam ");
+    assert_eq!(e.line().text(), "");
+}
+
+#[test]
+fn clear_all_forgets_line() {
+    let mut e = engine();
+    edit(&mut e, "viet nam ");
+    assert_eq!(e.line().text(), "viet nam ");
+    e.clear_all();
+    assert_eq!(e.line().text(), "");
+}
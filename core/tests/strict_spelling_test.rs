@@ -0,0 +1,54 @@
+//! Strict spelling tests - committed words must be valid Vietnamese or English
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::{Engine, StrictSpelling, FLAG_MISSPELLED};
+use gonhanh_core::utils::type_word;
+
+fn engine(mode: StrictSpelling) -> Engine {
+    let mut e = Engine::new();
+    e.set_strict_spelling(mode);
+    e
+}
+
+#[test]
+fn restore_mode_puts_back_raw_keys() {
+    let mut e = engine(StrictSpelling::Restore);
+    assert_eq!(type_word(&mut e, "tiengs "), "tiengs ");
+    let mut e = engine(StrictSpelling::Restore);
+    assert_eq!(type_word(&mut e, "tiengs,"), "tiengs,");
+}
+
+#[test]
+fn valid_words_are_kept() {
+    let mut e = engine(StrictSpelling::Restore);
+    assert_eq!(type_word(&mut e, "tieengs vieetj "), "tiếng việt ");
+    // English words pass too
+    assert_eq!(type_word(&mut e, "hello "), "hello ");
+}
+
+#[test]
+fn never_restore_words_are_kept() {
+    let mut e = engine(StrictSpelling::Restore);
+    e.word_lists_mut().add_never_restore("tiéng");
+    assert_eq!(type_word(&mut e, "tiengs "), "tiéng ");
+}
+
+#[test]
+fn flag_mode_keeps_word_and_sets_flag() {
+    let mut e = engine(StrictSpelling::Flag);
+    assert_eq!(type_word(&mut e, "tiengs"), "tiéng");
+    let r = e.on_key(keys::SPACE, false, false);
+    assert_eq!(r.action, 0);
+    assert_eq!(r.flags & FLAG_MISSPELLED, FLAG_MISSPELLED);
+
+    // Valid word: no flag
+    type_word(&mut e, "tieengs");
+    let r = e.on_key(keys::SPACE, false, false);
+    assert_eq!(r.flags & FLAG_MISSPELLED, 0);
+}
+
+#[test]
+fn off_by_default() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "tiengs "), "tiéng ");
+}
@@ -0,0 +1,96 @@
+//! Token class tests - URLs, e-mail addresses, paths, identifiers and numbers
+//! are typed verbatim until the next whitespace
+
+use gonhanh_core::engine::token_class::TokenClass;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+#[test]
+fn urls_typed_verbatim() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "https://hoaf.vn"), "https://hoaf.vn");
+    assert_eq!(e.token_class(), Some(TokenClass::Url));
+    let mut e = Engine::new();
+    assert_eq!(
+        type_word(&mut e, "xem www.google.com "),
+        "xem www.google.com "
+    );
+}
+
+#[test]
+fn email_restores_local_part() {
+    let mut e = Engine::new();
+    assert_eq!(
+        type_word(&mut e, "nhatkha1407@gmail.com "),
+        "nhatkha1407@gmail.com "
+    );
+    // The word before '@' goes back to its keys
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "toanf@hoaf.vn"), "toanf@hoaf.vn");
+    assert_eq!(e.token_class(), Some(TokenClass::Email));
+}
+
+#[test]
+fn paths_typed_verbatim() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "/usr/loaf"), "/usr/loaf");
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "~/taif"), "~/taif");
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "/src/loaf"), "/src/loaf");
+    // "và/hoặc" is not a path
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "vaf/hoacwj"), "và/hoặc");
+}
+
+#[test]
+fn words_after_slash_still_transform() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "/vieets "), "/viết ");
+    assert_eq!(e.token_class(), None);
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "vaf /hoacwj "), "và /hoặc ");
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "nam/nuwx "), "nam/nữ ");
+}
+
+#[test]
+fn identifiers_typed_verbatim() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "my_toanf"), "my_toanf");
+    assert_eq!(e.token_class(), Some(TokenClass::Identifier));
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "onClick"), "onClick");
+    assert_eq!(e.token_class(), Some(TokenClass::Identifier));
+    // Mixed case Vietnamese is not camelCase
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "viEets"), "viẾt");
+    assert_eq!(e.token_class(), None);
+}
+
+#[test]
+fn numbers_with_units_typed_verbatim() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "0xaa"), "0xaa");
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "100kg "), "100kg ");
+    assert_eq!(e.token_class(), None);
+    // Units with modifier keys are not numbers: money still gets its đ
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "100dd "), "100đ ");
+}
+
+#[test]
+fn transforms_resume_after_whitespace() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "www.abc.vn toanf "), "www.abc.vn toàn ");
+    assert_eq!(e.token_class(), None);
+}
+
+#[test]
+fn detection_can_be_disabled() {
+    let mut e = Engine::new();
+    e.set_token_detection(false);
+    assert_eq!(type_word(&mut e, "my_toanf"), "my_toàn");
+    assert_eq!(e.token_class(), None);
+}
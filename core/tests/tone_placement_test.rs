@@ -0,0 +1,49 @@
+//! Tone placement tests - per-rhyme house styles for oa, oe, uy
+
+use gonhanh_core::data::{StyledRhyme, TonePlacement};
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+/// Modern oa/oe, traditional uy
+fn house_style() -> Engine {
+    let mut e = Engine::new();
+    e.set_tone_placement(TonePlacement::MODERN.with(StyledRhyme::Uy, false));
+    e
+}
+
+#[test]
+fn mark_follows_rhyme_style() {
+    let mut e = house_style();
+    assert_eq!(type_word(&mut e, "hoaf khoer thuys "), "hoà khoẻ thúy ");
+}
+
+#[test]
+fn mark_typed_early_is_repositioned() {
+    // Mark lands on the first vowel, then moves when the rhyme is complete
+    let mut e = house_style();
+    assert_eq!(type_word(&mut e, "hofa "), "hoà ");
+    let mut e = house_style();
+    assert_eq!(type_word(&mut e, "thusy "), "thúy ");
+}
+
+#[test]
+fn closed_syllables_and_qu_unaffected() {
+    let mut e = Engine::new();
+    e.set_tone_placement(TonePlacement::TRADITIONAL);
+    assert_eq!(type_word(&mut e, "hoanf quys "), "hoàn quý ");
+}
+
+#[test]
+fn modern_tone_sets_every_rhyme() {
+    let mut e = house_style();
+    e.set_modern_tone(false);
+    assert_eq!(e.tone_placement(), TonePlacement::TRADITIONAL);
+    assert_eq!(type_word(&mut e, "hoaf "), "hòa ");
+}
+
+#[test]
+fn house_style_words_are_not_auto_restored() {
+    let mut e = house_style();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "thuys "), "thúy ");
+}
@@ -0,0 +1,86 @@
+//! Transform undo tests - undo key steps back through a word's transforms
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine_with_undo() -> Engine {
+    let mut e = Engine::new();
+    e.set_undo_key(Some(keys::ESC));
+    e
+}
+
+#[test]
+fn undo_steps_back_one_transform_at_a_time() {
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "dduowcj"), "được");
+
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "dduowcj\x1b"), "đươc");
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "dduowcj\x1b\x1b"), "đuoc");
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "dduowcj\x1b\x1b\x1b"), "duoc");
+}
+
+#[test]
+fn undo_result_rewrites_only_changed_chars() {
+    let mut e = engine_with_undo();
+    type_word(&mut e, "dduowcj");
+
+    // "được" → "đươc": only the mark changed, from 'ợ' on
+    let r = e.undo_transform();
+    assert_eq!(r.backspace, 2);
+    let chars: String = (0..r.count as usize)
+        .filter_map(|i| char::from_u32(r.chars[i]))
+        .collect();
+    assert_eq!(chars, "ơc");
+}
+
+#[test]
+fn undo_keeps_letters_typed_after_transform() {
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "ddang\x1b"), "dang");
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "vieetj\x1b"), "viêt");
+}
+
+#[test]
+fn undo_created_char_restores_key() {
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "nhw\x1b"), "nhw");
+}
+
+#[test]
+fn undo_then_continue_typing() {
+    let mut e = engine_with_undo();
+    // Undone word is not auto-restored or re-transformed on commit
+    assert_eq!(type_word(&mut e, "dduowcj\x1b "), "đươc ");
+    // Next word starts with an empty undo stack: nothing of "được" is undone
+    assert_eq!(type_word(&mut e, "an\x1b"), "an");
+    assert_eq!(e.undo_transform().action, 0);
+}
+
+#[test]
+fn undo_key_falls_through_without_transforms() {
+    let mut e = engine_with_undo();
+    e.set_esc_restore(true);
+    // Nothing left to undo: ESC keeps its restore behavior
+    assert_eq!(type_word(&mut e, "as\x1b\x1b"), "a");
+    assert_eq!(e.undo_transform().action, 0);
+}
+
+#[test]
+fn reverted_transform_is_not_undone_twice() {
+    let mut e = engine_with_undo();
+    // "ass" → "as": the revert already removed the mark
+    assert_eq!(type_word(&mut e, "dass\x1b"), "das");
+    let mut e = engine_with_undo();
+    assert_eq!(type_word(&mut e, "ddass\x1b"), "das");
+}
+
+#[test]
+fn undo_disabled_by_default() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "dduowcj\x1b"), "được");
+}
@@ -0,0 +1,77 @@
+//! Typography tests - smart quotes, dashes and punctuation spacing
+
+use gonhanh_core::engine::shortcut::Shortcut;
+use gonhanh_core::engine::typography::Typography;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine(bits: u8) -> Engine {
+    let mut e = Engine::new();
+    e.set_typography(Typography::from_bits(bits));
+    e
+}
+
+#[test]
+fn smart_quotes_follow_context() {
+    let mut e = engine(Typography::SMART_QUOTES);
+    assert_eq!(type_word(&mut e, "\"xin chaof\" "), "“xin chào” ");
+    let mut e = engine(Typography::SMART_QUOTES);
+    assert_eq!(type_word(&mut e, "noi 'ok' "), "noi ‘ok’ ");
+    let mut e = engine(Typography::SMART_QUOTES);
+    assert_eq!(type_word(&mut e, "don't"), "don’t");
+}
+
+#[test]
+fn dashes_after_whitespace() {
+    let mut e = engine(Typography::DASHES);
+    assert_eq!(type_word(&mut e, "a -- b"), "a – b");
+    let mut e = engine(Typography::DASHES);
+    assert_eq!(type_word(&mut e, "a --- b"), "a — b");
+    // Inside a word: plain hyphens
+    let mut e = engine(Typography::DASHES);
+    assert_eq!(type_word(&mut e, "a--b"), "a--b");
+}
+
+#[test]
+fn space_before_punctuation_removed() {
+    let mut e = engine(Typography::PUNCTUATION_SPACING);
+    assert_eq!(type_word(&mut e, "xin , chaof ."), "xin, chào.");
+    assert_eq!(type_word(&mut e, " sao ?"), " sao?");
+    // Only a single space right after a word
+    let mut e = engine(Typography::PUNCTUATION_SPACING);
+    assert_eq!(type_word(&mut e, "xin  ,"), "xin  ,");
+}
+
+#[test]
+fn double_space_becomes_period() {
+    let mut e = engine(Typography::DOUBLE_SPACE_PERIOD);
+    e.set_auto_capitalize(true);
+    assert_eq!(type_word(&mut e, "xin chaof  ban"), "xin chào. Ban");
+    // Three spaces: the third is a plain space
+    let mut e = engine(Typography::DOUBLE_SPACE_PERIOD);
+    assert_eq!(type_word(&mut e, "ok   "), "ok.  ");
+}
+
+#[test]
+fn urls_untouched() {
+    let mut e = engine(0x0F);
+    assert_eq!(
+        type_word(&mut e, "https://a.com/x--y'z"),
+        "https://a.com/x--y'z"
+    );
+    let mut e = engine(0x0F);
+    assert_eq!(type_word(&mut e, "ban@mail.com'"), "ban@mail.com'");
+}
+
+#[test]
+fn shortcuts_take_precedence() {
+    let mut e = engine(Typography::DASHES);
+    e.shortcuts_mut().add(Shortcut::immediate("--", "→"));
+    assert_eq!(type_word(&mut e, "--"), "→");
+}
+
+#[test]
+fn disabled_by_default() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "\"a\" -- b ,  "), "\"a\" -- b ,  ");
+}
@@ -0,0 +1,59 @@
+//! User dictionary tests - runtime word layers take precedence over embedded dictionaries
+
+use gonhanh_core::engine::user_dict::Language;
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+fn engine_auto_restore() -> Engine {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e
+}
+
+#[test]
+fn user_english_word_restores() {
+    // Not in embedded English dictionary → stays Vietnamese
+    let mut e = engine_auto_restore();
+    assert_eq!(type_word(&mut e, "vuex "), "vuẽ ");
+
+    // User English word → restored to raw keystrokes
+    let mut e = engine_auto_restore();
+    e.add_user_word(Language::English, "vuex");
+    assert_eq!(type_word(&mut e, "vuex "), "vuex ");
+    assert_eq!(type_word(&mut e, "box "), "bõ ");
+}
+
+#[test]
+fn user_vietnamese_word_prevents_restore() {
+    let mut e = engine_auto_restore();
+    assert_eq!(type_word(&mut e, "toto "), "toto ");
+
+    let mut e = engine_auto_restore();
+    e.add_user_word(Language::Vietnamese, "tôt");
+    assert_eq!(type_word(&mut e, "toto "), "tôt ");
+}
+
+#[test]
+fn removed_user_word_falls_back_to_embedded() {
+    let mut e = engine_auto_restore();
+    e.add_user_word(Language::English, "vuex");
+    assert!(e.remove_user_word(Language::English, "VUEX"));
+    assert_eq!(type_word(&mut e, "vuex "), "vuẽ ");
+}
+
+#[test]
+fn imported_words_apply() {
+    let mut e = engine_auto_restore();
+    let (count, errors) = e.user_dict_mut().import("[en]\nvuex\n[vi]\ntôt\n");
+    assert_eq!(count, 2);
+    assert!(errors.is_empty());
+    assert_eq!(type_word(&mut e, "vuex "), "vuex ");
+    assert_eq!(type_word(&mut e, "toto "), "tôt ");
+}
+
+#[test]
+fn user_words_ignored_when_auto_restore_off() {
+    let mut e = Engine::new();
+    e.add_user_word(Language::English, "vuex");
+    assert_eq!(type_word(&mut e, "vuex "), "vuẽ ");
+}
@@ -0,0 +1,74 @@
+//! Word escape tests - keys forcing one word to English (raw keys) or
+//! Vietnamese (composed), with optional learning into the word lists
+
+use gonhanh_core::data::keys;
+use gonhanh_core::engine::user_dict::Language;
+use gonhanh_core::engine::{Engine, WordEscape};
+use gonhanh_core::utils::type_word;
+
+fn engine(language: Language, escape: WordEscape) -> Engine {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e.set_word_escape(language, Some(escape));
+    e
+}
+
+#[test]
+fn prefix_forces_english() {
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    assert_eq!(type_word(&mut e, "`tieengs tieengs "), "tieengs tiếng ");
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    assert_eq!(type_word(&mut e, "`tieengs."), "tieengs.");
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    type_word(&mut e, "`");
+    assert_eq!(e.forced_word(), Some(Language::English));
+}
+
+#[test]
+fn prefix_forces_vietnamese() {
+    let mut e = engine(Language::Vietnamese, WordEscape::Prefix(keys::BACKQUOTE));
+    assert_eq!(type_word(&mut e, "`door door "), "dổ door ");
+}
+
+#[test]
+fn prefix_twice_types_key() {
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    assert_eq!(type_word(&mut e, "``tieengs "), "`tiếng ");
+}
+
+#[test]
+fn double_tapped_suffix() {
+    let mut e = engine(Language::English, WordEscape::DoubleSuffix(keys::SEMICOLON));
+    assert_eq!(type_word(&mut e, "tieengs;; "), "tieengs ");
+    let mut e = engine(
+        Language::Vietnamese,
+        WordEscape::DoubleSuffix(keys::SEMICOLON),
+    );
+    assert_eq!(type_word(&mut e, "door;; "), "dổ ");
+    // A single tap is typed as usual
+    let mut e = engine(Language::English, WordEscape::DoubleSuffix(keys::SEMICOLON));
+    assert_eq!(type_word(&mut e, "tieengs; "), "tiếng; ");
+}
+
+#[test]
+fn forced_words_learned() {
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    e.set_word_escape(
+        Language::Vietnamese,
+        Some(WordEscape::DoubleSuffix(keys::SEMICOLON)),
+    );
+    e.set_learn_forced_words(true);
+    assert_eq!(
+        type_word(&mut e, "`tieengs tieengs door;; door "),
+        "tieengs tieengs dổ dổ "
+    );
+    assert!(e.word_lists().is_never_transform("tieengs"));
+    assert!(e.word_lists().is_never_restore("dổ"));
+}
+
+#[test]
+fn learning_off_by_default() {
+    let mut e = engine(Language::English, WordEscape::Prefix(keys::BACKQUOTE));
+    type_word(&mut e, "`tieengs ");
+    assert!(e.word_lists().never_transform().is_empty());
+}
@@ -0,0 +1,49 @@
+//! Never-transform / never-restore word list tests
+
+use gonhanh_core::engine::Engine;
+use gonhanh_core::utils::type_word;
+
+#[test]
+fn never_transform_keeps_raw_keys() {
+    let mut e = Engine::new();
+    assert_eq!(type_word(&mut e, "mix "), "mĩ ");
+
+    let mut e = Engine::new();
+    e.word_lists_mut().add_never_transform("mix");
+    assert_eq!(type_word(&mut e, "mix"), "mix");
+    assert_eq!(type_word(&mut e, "MIX "), "MIX ");
+    // Only the exact sequence is protected
+    assert_eq!(type_word(&mut e, "mis "), "mí ");
+}
+
+#[test]
+fn never_transform_undoes_earlier_transforms_on_commit() {
+    let mut e = Engine::new();
+    e.word_lists_mut().add_never_transform("mass");
+    assert_eq!(type_word(&mut e, "mass "), "mass ");
+
+    let mut e = Engine::new();
+    e.word_lists_mut().add_never_transform("gonhanhs");
+    assert_eq!(type_word(&mut e, "gonhanhs "), "gonhanhs ");
+}
+
+#[test]
+fn never_transform_other_words_unaffected() {
+    let mut e = Engine::new();
+    e.word_lists_mut().add_never_transform("mix");
+    assert_eq!(type_word(&mut e, "vieetj "), "việt ");
+    assert_eq!(type_word(&mut e, "box "), "bõ ");
+}
+
+#[test]
+fn never_restore_keeps_vietnamese() {
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    assert_eq!(type_word(&mut e, "toto "), "toto ");
+
+    let mut e = Engine::new();
+    e.set_english_auto_restore(true);
+    e.word_lists_mut().add_never_restore("tôt");
+    assert_eq!(type_word(&mut e, "toto "), "tôt ");
+    assert_eq!(type_word(&mut e, "data "), "data ");
+}
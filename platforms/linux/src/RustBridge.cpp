@@ -1,5 +1,6 @@
 #include "RustBridge.h"
 #include <codecvt>
+#include <ctime>
 #include <locale>
 
 bool RustBridge::initialized_ = false;
@@ -7,9 +8,17 @@ bool RustBridge::initialized_ = false;
 void RustBridge::initialize() {
     if (initialized_) return;
     ime_init();
+    syncTimeZone();
     initialized_ = true;
 }
 
+void RustBridge::syncTimeZone() {
+    std::time_t now = std::time(nullptr);
+    std::tm local{};
+    localtime_r(&now, &local);
+    ime_shortcut_utc_offset(static_cast<int32_t>(local.tm_gmtoff / 60));
+}
+
 std::pair<int, std::string> RustBridge::processKey(
     uint16_t keyCode,
     bool caps,
@@ -31,7 +40,7 @@ std::pair<int, std::string> RustBridge::processKey(
         output.first = result->backspace;
 
         // Convert UTF-32 chars to UTF-8 string
-        for (uint8_t i = 0; i < result->count && i < 32; ++i) {
+        for (uint8_t i = 0; i < result->count && i < 256; ++i) {
             if (result->chars[i] > 0) {
                 output.second += codePointToUtf8(result->chars[i]);
             }
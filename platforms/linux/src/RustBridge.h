@@ -8,26 +8,28 @@
 // FFI Result structure - must match core/src/engine/mod.rs
 // #[repr(C)]
 // pub struct Result {
-//     pub chars: [u32; 32],
+//     pub chars: [u32; 256],
 //     pub action: u8,
 //     pub backspace: u8,
 //     pub count: u8,
-//     pub _pad: u8,
+//     pub flags: u8,
+//     pub cursor_left: u16,
 // }
 //
 // Note: Rust #[repr(C)] uses C ABI layout, which matches C++ struct layout
-// for this specific arrangement. The array (128 bytes) is followed by
-// 4 bytes of u8 fields = 132 bytes total with no implicit padding needed.
+// for this specific arrangement. The array (1024 bytes) is followed by
+// 4 bytes of u8 fields and a u16, padded to the 4-byte alignment = 1032 bytes.
 struct ImeResult {
-    uint32_t chars[32];  // 128 bytes
-    uint8_t action;      // 1 byte
-    uint8_t backspace;   // 1 byte
-    uint8_t count;       // 1 byte
-    uint8_t _pad;        // 1 byte (explicit padding to 4-byte boundary)
+    uint32_t chars[256];   // 1024 bytes
+    uint8_t action;        // 1 byte
+    uint8_t backspace;     // 1 byte
+    uint8_t count;         // 1 byte
+    uint8_t flags;         // 1 byte (bit 0: key consumed, bit 1: more output, bit 2: misspelled)
+    uint16_t cursor_left;  // 2 bytes: Left-arrow presses to send after inserting chars
 };
 
 // Verify struct size matches Rust at compile time
-static_assert(sizeof(ImeResult) == 132, "ImeResult size mismatch with Rust core");
+static_assert(sizeof(ImeResult) == 1032, "ImeResult size mismatch with Rust core");
 
 // Action types
 enum class ImeAction : uint8_t {
@@ -50,6 +52,7 @@ extern "C" {
     void ime_enabled(bool enabled);
     void ime_clear();
     void ime_free(ImeResult* result);
+    void ime_shortcut_utc_offset(int32_t minutes);
 }
 
 // C++ wrapper class for Rust bridge
@@ -58,6 +61,9 @@ public:
     // Initialize the IME engine (call once at startup)
     static void initialize();
 
+    // Pass the system UTC offset to shortcut date/time templates
+    static void syncTimeZone();
+
     // Process a keystroke and return result
     // Returns: (backspace_count, output_text) or empty if no action needed
     static std::pair<int, std::string> processKey(
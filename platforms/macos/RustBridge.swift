@@ -495,7 +495,7 @@ private class TextInjector {
 // MARK: - FFI (Rust Bridge)
 
 /// FFI result struct - must match Rust `Result` struct layout exactly
-/// Size: 256 UInt32 chars (1024 bytes) + 4 bytes = 1028 bytes
+/// Size: 256 UInt32 chars (1024 bytes) + 4 bytes + UInt16 cursorLeft, padded = 1032 bytes
 /// Max replacement: 255 UTF-32 codepoints (Vietnamese diacritics = 1 each)
 private struct ImeResult {
     // 256 UInt32 values for UTF-32 codepoints (matches core/src/engine/buffer.rs MAX)
@@ -538,6 +538,7 @@ private struct ImeResult {
     var backspace: UInt8
     var count: UInt8
     var flags: UInt8  // bit 0: key_consumed
+    var cursorLeft: UInt16  // Left-arrow presses to send after inserting chars
 }
 
 private let FLAG_KEY_CONSUMED: UInt8 = 0x01  // Key was consumed by shortcut, don't pass through
@@ -555,6 +556,7 @@ private let FLAG_KEY_CONSUMED: UInt8 = 0x01  // Key was consumed by shortcut, do
 @_silgen_name("ime_english_auto_restore") private func ime_english_auto_restore(_ enabled: Bool)
 @_silgen_name("ime_auto_capitalize") private func ime_auto_capitalize(_ enabled: Bool)
 @_silgen_name("ime_allow_foreign_consonants") private func ime_allow_foreign_consonants(_ enabled: Bool)
+@_silgen_name("ime_shortcut_utc_offset") private func ime_shortcut_utc_offset(_ minutes: Int32)
 @_silgen_name("ime_clear") private func ime_clear()
 @_silgen_name("ime_clear_all") private func ime_clear_all()
 @_silgen_name("ime_free") private func ime_free(_ result: UnsafeMutablePointer<ImeResult>?)
@@ -578,9 +580,18 @@ class RustBridge {
     static func initialize() {
         guard !isInitialized else { return }
         ime_init()
+        syncTimeZone()
+        NotificationCenter.default.addObserver(
+            forName: .NSSystemTimeZoneDidChange, object: nil, queue: .main
+        ) { _ in syncTimeZone() }
         isInitialized = true
     }
 
+    /// Pass the system UTC offset to shortcut date/time templates
+    static func syncTimeZone() {
+        ime_shortcut_utc_offset(Int32(TimeZone.current.secondsFromGMT() / 60))
+    }
+
     /// Process a keystroke. Returns (backspace, chars, keyConsumed) or nil if no action.
     ///
     /// - Parameters:
@@ -19,6 +19,9 @@ public static class RustBridge
     [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
     private static extern void ime_clear();
 
+    [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
+    private static extern void ime_shortcut_utc_offset(int minutes);
+
     [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
     private static extern void ime_free(IntPtr result);
 
@@ -44,6 +47,18 @@ public static class RustBridge
     public static void Initialize()
     {
         ime_init();
+        SyncTimeZone();
+    }
+
+    /// <summary>
+    /// Pass the system UTC offset to shortcut date/time templates.
+    /// Call again when the system time zone changes.
+    /// </summary>
+    public static void SyncTimeZone()
+    {
+        TimeZoneInfo.ClearCachedData();
+        var offset = TimeZoneInfo.Local.GetUtcOffset(DateTime.Now);
+        ime_shortcut_utc_offset((int)offset.TotalMinutes);
     }
 
     /// <summary>
@@ -124,7 +139,7 @@ public enum ImeAction : byte
 
 /// <summary>
 /// Native result structure from Rust (must match core/src/lib.rs)
-/// Size: 256 UInt32 chars (1024 bytes) + 4 bytes = 1028 bytes
+/// Size: 256 UInt32 chars (1024 bytes) + 4 bytes + ushort cursor_left, padded = 1032 bytes
 /// </summary>
 [StructLayout(LayoutKind.Sequential)]
 internal struct NativeResult
@@ -134,7 +149,8 @@ internal struct NativeResult
     public byte action;
     public byte backspace;
     public byte count;
-    public byte _pad;
+    public byte flags;
+    public ushort cursor_left;
 }
 
 /// <summary>